@@ -1,9 +1,23 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::parse::{Parse, ParseStream, Result};
-use syn::{parse_macro_input, Expr, Ident, Token};
+use proc_macro2::TokenTree;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser, Result};
+use syn::{bracketed, parenthesized, parse_macro_input, Expr, Ident, Token, Type};
 
-use super::{ident_builder, ident_mixins_enum};
+use super::{
+  field_default_fn, ident_builder, ident_factory, ident_fields, ident_mixins_enum,
+  mixin_fields_const, mixin_group_const, mixin_name_const, parse_crate_path, parse_factory_ident,
+  parse_factory_module, parse_prefix,
+};
+
+mod kw {
+  syn::custom_keyword!(builder);
+  syn::custom_keyword!(key);
+  syn::custom_keyword!(mixins);
+  syn::custom_keyword!(random_mixins);
+  syn::custom_keyword!(with);
+}
 
 /// e.g. create!(ty, :mixin1, :mixin2, field1: value1, field2: value2)
 ///
@@ -17,11 +31,103 @@ use super::{ident_builder, ident_mixins_enum};
 /// }
 ///
 /// fields and values can also be the transient ones
+///
+/// The mixins and the fields can be given in any order, e.g.
+/// create!(ty, field1: value1, :mixin1), the mixins being applied in their
+/// order and the fields after all of them.
+///
+/// `..default` can be written to make it explicit that the default values
+/// are applied before the mixins, and `..no_default` to require
+/// the mixins and fields to set every field instead. Any other expression,
+/// e.g. `..preset`, is a builder of the factory which is used in place of the
+/// defaults, kept in `base`.
+///
+/// A mixin can be followed by a condition, e.g. `:mixin1 if condition`, in
+/// which case it's only applied if the condition is true.
+///
+/// Parameterized mixins are passed their arguments in parentheses, e.g.
+/// `:mixin1(value1)`, which are kept as is in `arguments`.
+///
+/// `random_mixins: [mixin1, mixin2]` applies a random subset of the mixins,
+/// which become mixins with a random condition, e.g.
+/// `:mixin1 if rng().gen_bool(), :mixin2 if rng().gen_bool()`.
+///
+/// A field of a field can be set with a dotted path, e.g.
+/// `field1.inner: value1`. The overrides of a field's fields are merged into
+/// a value for the field, which updates what it would otherwise be:
+///
+/// Create {
+///   fields: vec!['field1'],
+///   values: vec!['{ let mut nested = default; nested.inner = value1; nested }'],
+///   nested: vec![true],
+/// }
+///
+/// The builder and the mixins enum can be given explicitly instead of the
+/// type, e.g. create!(@builder Builder, @mixins Mixins, :mixin1), in which
+/// case they are kept in `explicit`.
 struct Create {
+  krate: proc_macro2::TokenStream,
+  prefix: String,
+  /// The module of a factory defined with `factori!(in module; ...)`, e.g.
+  /// `module::`, or empty
+  module: proc_macro2::TokenStream,
   ty: Ident,
   mixins: Vec<Ident>,
+  arguments: Vec<proc_macro2::TokenStream>,
+  conditions: Vec<Option<Expr>>,
+  defaults: bool,
+  base: Option<Expr>,
   fields: Vec<Ident>,
   values: Vec<Expr>,
+  nested: Vec<bool>,
+  explicit: Option<ExplicitTypes>,
+}
+
+/// The builder and the mixins enum given to create!(@builder Builder,
+/// @mixins Mixins, ...), rather than named after the type. The fields enum
+/// isn't known then, so the fields are set on the builder as is and the
+/// checks using it are left out.
+struct ExplicitTypes {
+  builder: syn::Path,
+  mixins: Option<syn::Path>,
+}
+
+impl ExplicitTypes {
+  /// Parses `@builder Builder`, followed by `, @mixins Mixins` if any, and
+  /// returns them with the name of the builder, which stands for the type
+  fn parse(input: ParseStream) -> Result<(Ident, Self)> {
+    input.parse::<Token![@]>()?;
+    input.parse::<kw::builder>()?;
+    let builder: syn::Path = input.parse()?;
+
+    let mut mixins = None;
+    if input.peek(Token![,]) && input.peek2(Token![@]) {
+      input.parse::<Token![,]>()?;
+      input.parse::<Token![@]>()?;
+      input.parse::<kw::mixins>()?;
+      mixins = Some(input.parse()?);
+    }
+
+    let ty = builder
+      .segments
+      .last()
+      .expect("paths have a segment")
+      .ident
+      .clone();
+
+    Ok((ty, ExplicitTypes { builder, mixins }))
+  }
+}
+
+/// Parses the type passed to create!(...), or the explicit builder and
+/// mixins enum, e.g. `@builder Builder, @mixins Mixins`
+fn parse_create_type(input: ParseStream) -> Result<(Ident, Option<ExplicitTypes>)> {
+  if input.peek(Token![@]) {
+    let (ty, explicit) = ExplicitTypes::parse(input)?;
+    return Ok((ty, Some(explicit)));
+  }
+
+  Ok((parse_factory_ident(input)?, None))
 }
 
 impl Create {
@@ -33,149 +139,1810 @@ impl Create {
   ///
   /// ty is extracted and input is the rest of the token stream
   /// in effect it parses everything after `ty,` or `ty, count,`
-  fn build_after_type(ty: Ident, input: ParseStream) -> Result<Self> {
+  fn build_after_type(
+    krate: proc_macro2::TokenStream,
+    prefix: String,
+    module: proc_macro2::TokenStream,
+    ty: Ident,
+    input: ParseStream,
+  ) -> Result<Self> {
     if input.peek(Token![,]) {
       input.parse::<Token![,]>()?;
     }
 
     let mut mixins = Vec::new();
-    while input.peek(Token![:]) {
-      input.parse::<Token![:]>()?;
-      mixins.push(input.parse()?);
-
-      if input.peek(Token![,]) {
-        input.parse::<Token![,]>()?;
-      }
-    }
-
-    let mut fields = Vec::new();
-    let mut values = Vec::new();
+    let mut arguments = Vec::new();
+    let mut conditions = Vec::new();
+    let mut defaults = true;
+    let mut base = None;
+    let mut fields: Vec<Ident> = Vec::new();
+    let mut values: Vec<Option<Expr>> = Vec::new();
+    let mut assignments: Vec<Vec<(Ident, Expr)>> = Vec::new();
+    // The mixins, the base and the fields can be given in any order, the
+    // mixins are applied in theirs and the fields override them regardless.
     loop {
-      if input.is_empty() {
+      // A closure can follow in create_vec!(...), and `; with` in create!(...)
+      if input.is_empty()
+        || input.peek(Token![|])
+        || input.peek(Token![move])
+        || input.peek(Token![;])
+      {
         break;
       }
 
-      fields.push(input.parse()?);
-      input.parse::<Token![:]>()?;
-      values.push(input.parse()?);
+      if input.peek(Token![:]) {
+        input.parse::<Token![:]>()?;
+        mixins.push(input.parse()?);
+
+        if input.peek(syn::token::Paren) {
+          let group: TokenTree = input.parse()?;
+          arguments.push(group.into_token_stream());
+        } else {
+          arguments.push(proc_macro2::TokenStream::new());
+        }
+
+        if input.peek(Token![if]) {
+          input.parse::<Token![if]>()?;
+          conditions.push(Some(input.parse()?));
+        } else {
+          conditions.push(None);
+        }
+      } else if input.peek(Token![..]) {
+        let dots = input.parse::<Token![..]>()?;
+        if !defaults || base.is_some() {
+          return Err(syn::Error::new_spanned(
+            dots,
+            "the base of the builder can only be given once",
+          ));
+        }
+
+        let expr: Expr = input.parse()?;
+        match &expr {
+          Expr::Path(path) if path.path.is_ident("default") => {}
+          Expr::Path(path) if path.path.is_ident("no_default") => defaults = false,
+          _ => base = Some(expr),
+        }
+      } else if input.peek(kw::random_mixins) && input.peek2(Token![:]) {
+        // Each of the candidates is a mixin applied if a random bool is true.
+        input.parse::<kw::random_mixins>()?;
+        input.parse::<Token![:]>()?;
+        let list;
+        bracketed!(list in input);
+        loop {
+          if list.is_empty() {
+            break;
+          }
+
+          mixins.push(list.parse()?);
+          if list.peek(syn::token::Paren) {
+            let group: TokenTree = list.parse()?;
+            arguments.push(group.into_token_stream());
+          } else {
+            arguments.push(proc_macro2::TokenStream::new());
+          }
+          conditions.push(Some(syn::parse_quote! { #krate::rng().gen_bool() }));
+
+          if list.peek(Token![,]) {
+            list.parse::<Token![,]>()?;
+          }
+        }
+      } else {
+        let field: Ident = input.parse()?;
+        if input.peek(Token![.]) {
+          input.parse::<Token![.]>()?;
+          let inner = input.parse()?;
+          input.parse::<Token![:]>()?;
+          let value = input.parse()?;
+
+          match fields.iter().position(|other| *other == field) {
+            Some(i) if values[i].is_some() => {
+              return Err(syn::Error::new(
+                field.span(),
+                format!(
+                  "`{}` is already set, so `{}.{}` can't be set",
+                  field, field, inner
+                ),
+              ));
+            }
+            Some(i) => assignments[i].push((inner, value)),
+            None => {
+              fields.push(field);
+              values.push(None);
+              assignments.push(vec![(inner, value)]);
+            }
+          }
+        } else {
+          let nested = fields.iter().position(|other| *other == field);
+          if let Some(i) = nested.filter(|&i| values[i].is_none()) {
+            let (inner, _) = &assignments[i][0];
+            return Err(syn::Error::new(
+              field.span(),
+              format!(
+                "`{}.{}` is already set, so `{}` can't be set",
+                field, inner, field
+              ),
+            ));
+          }
+
+          input.parse::<Token![:]>()?;
+          fields.push(field);
+          values.push(Some(input.parse()?));
+          assignments.push(Vec::new());
+        }
+      }
 
       if input.peek(Token![,]) {
         input.parse::<Token![,]>()?;
       }
     }
 
+    let nested = values.iter().map(Option::is_none).collect();
+    let values = values
+      .into_iter()
+      .zip(assignments)
+      .map(|(value, assignments)| nested_value(value, assignments))
+      .collect();
+
     Ok(Create {
+      krate,
+      prefix,
+      module,
       ty,
       mixins,
+      arguments,
+      conditions,
+      defaults,
+      base,
       fields,
       values,
+      nested,
+      explicit: None,
     })
   }
 
+  /// Sets the explicit builder and mixins enum, which the mixins need if
+  /// there are any, and which can't be used with `..no_default`
+  fn with_explicit(mut self, explicit: Option<ExplicitTypes>) -> Result<Self> {
+    if let Some(explicit) = &explicit {
+      if let (Some(mixin), None) = (self.mixins.first(), &explicit.mixins) {
+        return Err(syn::Error::new(
+          mixin.span(),
+          "the mixins enum must be given with `@mixins` to apply mixins with `@builder`",
+        ));
+      }
+
+      if !self.defaults {
+        return Err(syn::Error::new_spanned(
+          &explicit.builder,
+          "`..no_default` can't be used with `@builder`",
+        ));
+      }
+    }
+
+    self.explicit = explicit;
+    Ok(self)
+  }
+
   /// Generates the code for its create!(...) call
   fn generate_code(&self) -> proc_macro2::TokenStream {
+    self.generate_build(self.generate_builder_code())
+  }
+
+  /// Generates the code building the given builder, registering the value
+  /// with the `registry` feature and timing it with the `trace` feature
+  fn generate_build(&self, builder_code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let krate = &self.krate;
+    self.wrap_build(quote! {
+        #krate::Builder::build(#builder_code)
+    })
+  }
+
+  /// Generates the code of a future for its create_async!(...) call. The
+  /// builder is made right away, and only built when the future is awaited.
+  #[cfg(feature = "async")]
+  fn generate_code_async(&self) -> proc_macro2::TokenStream {
+    let krate = &self.krate;
+    let builder_code = self.generate_builder_code();
+    let build = self.wrap_build(quote! {
+        #krate::AsyncBuilder::build(__factori_builder).await
+    });
+
+    quote! {
+        {
+            let __factori_builder = #builder_code;
+            async move { #build }
+        }
+    }
+  }
+
+  /// Wraps the code building the value to register it with the `registry`
+  /// feature and time it with the `trace` feature
+  fn wrap_build(&self, mut build: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let krate = &self.krate;
+
+    if cfg!(feature = "registry") {
+      build = quote! {
+          {
+              use #krate::registry::{RegisterClone as _, RegisterSkip as _};
+              let value = #build;
+              (&&#krate::registry::Register(&value)).register();
+              value
+          }
+      };
+    }
+
+    if cfg!(feature = "trace") {
+      let factory = self.ty.unraw().to_string();
+      build = quote! {
+          {
+              let __factori_span = #krate::trace::Span::new(#factory);
+              let value = #build;
+              __factori_span.finish();
+              value
+          }
+      };
+    }
+
+    build
+  }
+
+  /// Generates the code for the builder with mixins and overrides applied,
+  /// without building it
+  fn generate_builder_code(&self) -> proc_macro2::TokenStream {
     let Self {
+      krate,
+      prefix,
+      module,
       ty,
       mixins,
+      arguments,
+      conditions,
+      defaults,
+      base,
       fields,
       values,
+      nested,
+      explicit,
     } = self;
 
-    let ident_builder = ident_builder(ty);
-    let ident_mixins_enum = ident_mixins_enum(ty);
+    let factory_check = factory_check(prefix, module, ty);
+    let ident_builder = ident_builder(prefix, ty);
+    let ident_builder = quote! { #module #ident_builder };
+    let ident_mixins_enum = ident_mixins_enum(prefix, ty);
+    let ident_mixins_enum = quote! { #module #ident_mixins_enum };
+    let ident_fields = ident_fields(prefix, ty);
+    let ident_fields = quote! { #module #ident_fields };
 
-    let mut mixins = mixins.iter();
-    let value = if let Some(mixin) = mixins.next() {
-      let initial = quote! {
-          factori_imp::Mixin::default(#ident_mixins_enum::#mixin)
-      };
-      mixins.fold(initial, |acc, mixin| {
-        quote! {
-            factori_imp::Mixin::extend(#ident_mixins_enum::#mixin, #acc)
-        }
+    // Without the fields enum, the fields are set as is and aren't checked.
+    let (factory_check, ident_builder, ident_mixins_enum) = match explicit {
+      Some(explicit) => (
+        quote! {},
+        explicit.builder.to_token_stream(),
+        explicit.mixins.to_token_stream(),
+      ),
+      None => (factory_check, ident_builder, ident_mixins_enum),
+    };
+    let field_value = |field: &Ident, value: proc_macro2::TokenStream| match explicit {
+      Some(_) => value,
+      None => quote! { #ident_fields::#field(#value) },
+    };
+
+    let mixin_values: Vec<_> = mixins
+      .iter()
+      .zip(arguments)
+      .map(|(mixin, arguments)| quote! { #ident_mixins_enum::#mixin #arguments })
+      .collect();
+
+    let mut mixins = mixin_values.iter().zip(conditions);
+    // The mixins extend the base builder when there is one, and the first of
+    // them creates it from the defaults otherwise.
+    let initial = match base {
+      Some(base) => Some(quote! {
+          {
+              let __factori_base: #ident_builder = #base;
+              __factori_base
+          }
+      }),
+      None => mixins.next().map(|(mixin, condition)| match condition {
+        Some(condition) => quote! {
+            if #condition {
+                #krate::Mixin::default(#mixin)
+            } else {
+                #krate::Default::default()
+            }
+        },
+        None => quote! {
+            #krate::Mixin::default(#mixin)
+        },
+      }),
+    };
+    let value = if let Some(initial) = initial {
+      mixins.fold(initial, |acc, (mixin, condition)| match condition {
+        Some(condition) => quote! {
+            {
+                let acc = #acc;
+                if #condition {
+                    #krate::Mixin::extend(#mixin, acc)
+                } else {
+                    acc
+                }
+            }
+        },
+        None => quote! {
+            #krate::Mixin::extend(#mixin, #acc)
+        },
       })
     } else {
-      quote! { factori_imp::Default::default () }
+      quote! { #krate::Default::default () }
     };
 
-    let quoted = quote! {
-        factori_imp::Builder::build(
+    // The fields set by the mixins and the overrides, for the checks below.
+    // Conditional mixins might not be applied, so they don't count, and
+    // neither do fields of which only some fields are set.
+    let set_by_mixins: Vec<_> = self
+      .mixins
+      .iter()
+      .zip(conditions)
+      .filter(|(_, condition)| condition.is_none())
+      .map(|(mixin, _)| {
+        let mixin_fields_const = mixin_fields_const(mixin);
+        quote! { #ident_mixins_enum::#mixin_fields_const }
+      })
+      .collect();
+    let set_by_overrides: Vec<_> = fields
+      .iter()
+      .zip(nested)
+      .filter(|(_, nested)| !**nested)
+      .map(|(field, _)| field.unraw().to_string())
+      .collect();
+
+    // The required transient fields have no default, so they must be set,
+    // unless the base builder was made with them already.
+    let mut check = quote! {};
+    if base.is_none() && explicit.is_none() {
+      check.extend(quote! {
+          const _: () = #krate::check::required(
+              #ident_fields::REQUIRED,
+              &[ #( #set_by_mixins ),* ],
+              &[ #( #set_by_overrides ),* ],
+          );
+      });
+    }
+
+    // The fields marked `#[factori(skip)]` can't be overridden at all.
+    let overridden: Vec<_> = fields
+      .iter()
+      .map(|field| field.unraw().to_string())
+      .collect();
+    if explicit.is_none() {
+      check.extend(quote! {
+          const _: () = #krate::check::not_skipped(
+              #ident_fields::SKIPPED,
+              &[ #( #overridden ),* ],
+          );
+      });
+    }
+
+    // Two different mixins of the same group can't be applied together.
+    let applied: Vec<_> = self
+      .mixins
+      .iter()
+      .zip(conditions)
+      .filter(|(_, condition)| condition.is_none())
+      .map(|(mixin, _)| mixin)
+      .collect();
+    for (i, first) in applied.iter().enumerate() {
+      for second in &applied[i + 1..] {
+        if first.unraw() == second.unraw() {
+          continue;
+        }
+
+        let first_group = mixin_group_const(first);
+        let second_group = mixin_group_const(second);
+        let first_name = mixin_name_const(first);
+        let second_name = mixin_name_const(second);
+        let error = format!(
+          "the mixins `{}` and `{}` are in the same `mixin_group`, only one of them can be applied",
+          first.unraw(),
+          second.unraw()
+        );
+        check.extend(quote! {
+            const _: () = #krate::check::exclusive(
+                #ident_mixins_enum::#first_group,
+                #ident_mixins_enum::#first_name,
+                #ident_mixins_enum::#second_group,
+                #ident_mixins_enum::#second_name,
+                #error,
+            );
+        });
+      }
+    }
+
+    // The default values are still used as the base of the builder, but the
+    // check makes sure all of them are replaced.
+    if !*defaults {
+      check.extend(quote! {
+          const _: () = #krate::check::no_default(
+              #ident_fields::FIELDS,
+              &[ #( #set_by_mixins ),* ],
+              &[ #( #set_by_overrides ),* ],
+          );
+      });
+    }
+
+    // Values referring to `default` need the base to be bound first, so that
+    // `default` can be bound to the base's field.
+    let default_spans: Vec<_> = values
+      .iter()
+      .map(|value| default_span(value.to_token_stream()))
+      .collect();
+
+    if default_spans.iter().all(Option::is_none) {
+      let values = fields
+        .iter()
+        .zip(values)
+        .map(|(field, value)| field_value(field, value.to_token_stream()));
+      return quote! {
+          {
+            #factory_check
+            #check
+            #[allow(clippy::needless_update)]
+            let builder = #ident_builder {
+              #(
+                  #fields: #values,
+              )*
+              .. #value
+            };
+            builder
+          }
+      };
+    }
+
+    let overrides = fields
+      .iter()
+      .zip(values)
+      .zip(default_spans)
+      .map(|((field, value), span)| {
+        let value = field_value(field, value.to_token_stream());
+        match span {
+          Some(span) => {
+            let default = Ident::new("default", span);
+            // Fields stored as an `Option` in the builder, whose value isn't
+            // known before building, are an error spanned to `default`.
+            let base_value = match explicit {
+              Some(_) => quote! { __factori_base.#field },
+              None => {
+                let default_fn = field_default_fn(field);
+                let name = field.unraw().to_string();
+                let check = quote_spanned! {span=>
+                    const _: () = #krate::check::default_known(#ident_fields::UNKNOWN_DEFAULTS, #name);
+                };
+                quote! {
+                    {
+                        #check
+                        #ident_fields::#default_fn(__factori_base.#field)
+                    }
+                }
+              }
+            };
+            quote! {
+                #field: {
+                    let #default = #base_value;
+                    #value
+                },
+            }
+          }
+          None => quote! { #field: #value, },
+        }
+      });
+
+    quote! {
+        {
+          #factory_check
+          #check
           #[allow(clippy::needless_update)]
-          #ident_builder {
-            #(
-                #fields: #values,
-            )*
-            .. #value
-        })
-    };
+          let __factori_base = #ident_builder { .. #value };
+          #[allow(clippy::needless_update)]
+          let builder = #ident_builder {
+            #( #overrides )*
+            .. __factori_base
+          };
+          builder
+        }
+    }
+  }
+}
+
+/// Refers to the marker trait of the factory, which has to come first in the
+/// generated code for the error to be the first one when it's not in scope.
+fn factory_check(
+  prefix: &str,
+  module: &proc_macro2::TokenStream,
+  ty: &Ident,
+) -> proc_macro2::TokenStream {
+  let ident_factory = ident_factory(prefix, ty);
+
+  quote! {
+      let _: ::std::marker::PhantomData<dyn #module #ident_factory>;
+  }
+}
+
+/// The value of a field whose own fields are set with dotted paths, e.g.
+/// `field.inner: value`, which updates the field's value if it's also set or
+/// else its default. The values of the inner fields can refer to the value
+/// they replace as `default`.
+fn nested_value(value: Option<Expr>, assignments: Vec<(Ident, Expr)>) -> Expr {
+  if assignments.is_empty() {
+    return value.expect("fields have a value or nested assignments");
+  }
+
+  let base = match value {
+    Some(value) => value.into_token_stream(),
+    None => quote! { default },
+  };
+
+  let assignments =
+    assignments.into_iter().map(
+      |(inner, value)| match default_span(value.to_token_stream()) {
+        Some(span) => {
+          let default = Ident::new("default", span);
+          quote! {
+              __factori_nested.#inner = {
+                  let #default = __factori_nested.#inner;
+                  #value
+              };
+          }
+        }
+        None => quote! { __factori_nested.#inner = #value; },
+      },
+    );
+
+  syn::parse_quote! {
+      {
+          let mut __factori_nested = #base;
+          #( #assignments )*
+          __factori_nested
+      }
+  }
+}
 
-    quoted
+/// The span of the first `default` identifier in the value of an override,
+/// e.g. `default + 2`, ignoring paths and methods like `Default::default()`.
+fn default_span(tokens: proc_macro2::TokenStream) -> Option<proc_macro2::Span> {
+  let mut after_path_or_dot = false;
+  for token in tokens {
+    match &token {
+      TokenTree::Ident(ident) if ident == "default" && !after_path_or_dot => {
+        return Some(ident.span());
+      }
+      TokenTree::Group(group) => {
+        if let Some(span) = default_span(group.stream()) {
+          return Some(span);
+        }
+      }
+      _ => {}
+    }
+
+    after_path_or_dot = match &token {
+      TokenTree::Punct(punct) => punct.as_char() == ':' || punct.as_char() == '.',
+      _ => false,
+    };
   }
+
+  None
 }
 
 impl Parse for Create {
   fn parse(input: ParseStream) -> Result<Self> {
-    let ty = input.parse()?;
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let (ty, explicit) = parse_create_type(input)?;
+
+    Self::build_after_type(krate, prefix, module, ty, input)?.with_explicit(explicit)
+  }
+}
+
+/// e.g. create!(ty => target, :mixin1, field1: value1; with |v| { ... })
+///
+/// Accepts the same input as create!(...), with an optional collection after
+/// the type, which the instance is added to instead of being returned, and
+/// an optional closure after `; with`, which is called with the instance
+/// once it's built.
+struct CreateTarget {
+  target: Option<Expr>,
+  create: Create,
+  with: Option<Expr>,
+}
+
+impl Parse for CreateTarget {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let (ty, explicit) = parse_create_type(input)?;
+    let target = if input.peek(Token![=>]) {
+      input.parse::<Token![=>]>()?;
+      Some(input.parse()?)
+    } else {
+      None
+    };
+    let create =
+      Create::build_after_type(krate, prefix, module, ty, input)?.with_explicit(explicit)?;
+    let with = if input.peek(Token![;]) {
+      input.parse::<Token![;]>()?;
+      input.parse::<kw::with>()?;
+      Some(input.parse()?)
+    } else {
+      None
+    };
 
-    Self::build_after_type(ty, input)
+    Ok(CreateTarget {
+      target,
+      create,
+      with,
+    })
   }
 }
 
 pub fn create_macro(input: TokenStream) -> TokenStream {
+  let CreateTarget {
+    target,
+    create,
+    with,
+  } = parse_macro_input!(input);
+  let mut create_code = create.generate_code();
+  // Passed through a function for the closure's argument to be inferred.
+  if let Some(with) = with {
+    create_code = quote! {
+      {
+        fn __factori_with<T>(mut value: T, with: impl ::std::ops::FnOnce(&mut T)) -> T {
+          with(&mut value);
+          value
+        }
+        __factori_with(#create_code, #with)
+      }
+    };
+  }
+
+  match target {
+    Some(target) => quote! {
+      ::std::iter::Extend::extend(&mut #target, ::std::iter::once(#create_code))
+    },
+    None => create_code,
+  }
+  .into()
+}
+
+#[cfg(feature = "async")]
+pub fn create_async_macro(input: TokenStream) -> TokenStream {
   let create: Create = parse_macro_input!(input);
+  create.generate_code_async().into()
+}
+
+/// e.g. create_into!(ty, :mixin1, field1: value1)
+///
+/// Accepts the same input as create!(...), but converts the values with
+/// `Into::into`, so that e.g. a `&str` can be given for a `String` field.
+/// Fields set with dotted paths are given as is, and so are number literals
+/// without a suffix, whose type is inferred from the field anyway, and which
+/// would be ambiguous once converted.
+pub fn create_into_macro(input: TokenStream) -> TokenStream {
+  let mut create: Create = parse_macro_input!(input);
+  for (value, nested) in create.values.iter_mut().zip(&create.nested) {
+    if !nested && !is_number_literal(value) {
+      *value = syn::parse_quote! { ::std::convert::Into::into(#value) };
+    }
+  }
+
   create.generate_code().into()
 }
 
-/// e.g. create_vec!(ty, 3, :mixin1, :mixin2, field1: value1, field2: value2)
+/// Whether the expression is a number literal without a suffix, e.g. `1` or
+/// `-1.5` but not `1u8`
+fn is_number_literal(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(lit) => match &lit.lit {
+      syn::Lit::Int(int) => int.suffix().is_empty(),
+      syn::Lit::Float(float) => float.suffix().is_empty(),
+      _ => false,
+    },
+    Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Neg(_)) => is_number_literal(&unary.expr),
+    Expr::Group(group) => is_number_literal(&group.expr),
+    Expr::Paren(paren) => is_number_literal(&paren.expr),
+    _ => false,
+  }
+}
+
+/// e.g. assert_factori_default!(ty, field1 == value1, field2 == value2)
 ///
 /// ... becomes:
 ///
-/// CreateVec {
-///   ty: 'ty',
-///   count: 3,
-///   create: Create {
-///     ty: 'ty',
-///     mixins: vec!['mixin1', 'mixin2'],
-///     fields: vec!['field1', 'field2'],
-///     values: vec!['value1', 'value2'],
-///   }
+/// AssertDefault {
+///   create: Create { ty: 'ty', .. },
+///   fields: vec!['field1', 'field2'],
+///   values: vec!['value1', 'value2'],
 /// }
-struct CreateVec {
-  ty: Ident,
-  count: Expr,
+struct AssertDefault {
   create: Create,
+  fields: Vec<Ident>,
+  values: Vec<Expr>,
 }
 
-impl Parse for CreateVec {
+impl Parse for AssertDefault {
   fn parse(input: ParseStream) -> Result<Self> {
-    let ty: Ident = input.parse()?;
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let ty = parse_factory_ident(input)?;
 
-    input.parse::<Token![,]>()?;
-    let count = input.parse()?;
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    while !input.is_empty() {
+      input.parse::<Token![,]>()?;
+      if input.is_empty() {
+        break;
+      }
+
+      fields.push(input.parse()?);
+      input.parse::<Token![==]>()?;
+      values.push(input.parse()?);
+    }
 
-    let create = Create::build_after_type(ty.clone(), input)?;
+    // The defaults are created without mixins or overrides.
+    let create = Parser::parse2(
+      |input: ParseStream| Create::build_after_type(krate, prefix, module, ty, input),
+      proc_macro2::TokenStream::new(),
+    )?;
 
-    Ok(CreateVec { ty, count, create })
+    Ok(AssertDefault {
+      create,
+      fields,
+      values,
+    })
   }
 }
 
-/// Generates the code for a vec of count the factory
+/// Generates the code creating the factory's defaults and asserting the
+/// values of their fields
+pub fn assert_factori_default_macro(input: TokenStream) -> TokenStream {
+  let AssertDefault {
+    create,
+    fields,
+    values,
+  } = parse_macro_input!(input);
+
+  let create_code = create.generate_code();
+  let messages = fields.iter().map(|field| {
+    format!(
+      "the default of `{}` in the factory of `{}` changed",
+      field.unraw(),
+      create.ty.unraw()
+    )
+  });
+
+  let quoted = quote! {
+    {
+      let __factori_default = #create_code;
+      #(
+          assert_eq!(__factori_default.#fields, #values, #messages);
+      )*
+    }
+  };
+
+  quoted.into()
+}
+
+/// e.g. try_create!(ty, :mixin1, field1: value1)
 ///
-/// ```
-/// // we basically want from
-/// let users = create_vec!(User, 4, :mixin, name: "blah");
-/// // to generate the following code
-/// let users = (0..4).iter()
-///   .map(|_| code_from_create_generate_code)
-///   .collect<Vec<User>>();
-/// ```
-pub fn create_vec_macro(input: TokenStream) -> TokenStream {
-  let CreateVec { ty, count, create } = parse_macro_input!(input);
+/// Accepts the same input as create!(...), but returns the result of the
+/// factory's `try_builder` block instead of panicking if it fails
+pub fn try_create_macro(input: TokenStream) -> TokenStream {
+  let create: Create = parse_macro_input!(input);
+  let krate = &create.krate;
+  let builder_code = create.generate_builder_code();
+
+  let quoted = quote! {
+      #krate::TryBuilder::try_build(#builder_code)
+  };
+
+  quoted.into()
+}
+
+/// e.g. build!(ty, :mixin1, field1: value1)
+///
+/// Accepts the same input as create!(...), but returns the builder instead of
+/// building it
+pub fn build_macro(input: TokenStream) -> TokenStream {
+  let create: Create = parse_macro_input!(input);
+  create.generate_builder_code().into()
+}
+
+/// e.g. create_inspect!(ty, :mixin1, field1: value1)
+///
+/// Accepts the same input as create!(...), but returns a clone of the builder
+/// along with the value, which needs `#[factori(clone_builder)]`
+pub fn create_inspect_macro(input: TokenStream) -> TokenStream {
+  let create: Create = parse_macro_input!(input);
+  let builder_code = create.generate_builder_code();
+  let build_code = create.generate_build(quote! { builder });
+
+  let quoted = quote! {
+      {
+          let builder = #builder_code;
+          let snapshot = ::std::clone::Clone::clone(&builder);
+          (#build_code, snapshot)
+      }
+  };
+
+  quoted.into()
+}
+
+/// e.g. create_json!(ty, :mixin1, field1: value1)
+///
+/// Accepts the same input as create!(...), but returns the value serialized
+/// to JSON with the `serde_json` crate re-exported by factori-imp
+#[cfg(feature = "serde")]
+pub fn create_json_macro(input: TokenStream) -> TokenStream {
+  let create: Create = parse_macro_input!(input);
+  let krate = &create.krate;
+  let create_code = create.generate_code();
+
+  let quoted = quote! {
+      #krate::serde_json::to_string(&#create_code).expect("the created value can't be serialized to JSON")
+  };
+
+  quoted.into()
+}
 
+/// e.g. create_cow!(ty, :mixin1, field1: value1)
+///
+/// Accepts the same input as create!(...), but returns the value in
+/// `Cow::Owned`
+pub fn create_cow_macro(input: TokenStream) -> TokenStream {
+  let create: Create = parse_macro_input!(input);
   let create_code = create.generate_code();
 
   let quoted = quote! {
-    (0..#count).map(|_| #create_code).collect::<Vec<#ty>>()
+      ::std::borrow::Cow::Owned(#create_code)
+  };
+
+  quoted.into()
+}
+
+/// e.g. factori_bench!(ty, :mixin1, field1: value1)
+///
+/// Accepts the same input as create!(...), but returns a closure creating a
+/// new instance on each call. It's passed through a function for the closure
+/// to be `Fn`, so that capturing a value by move is an error here rather than
+/// where the closure is called again.
+pub fn factori_bench_macro(input: TokenStream) -> TokenStream {
+  let create: Create = parse_macro_input!(input);
+  let create_code = create.generate_code();
+
+  let quoted = quote! {
+      {
+          fn bench<T>(create: impl Fn() -> T) -> impl Fn() -> T {
+              create
+          }
+          bench(move || #create_code)
+      }
+  };
+
+  quoted.into()
+}
+
+/// e.g. create_vec!(ty, 3, :mixin1, :mixin2, field1: value1, field2: value2)
+///
+/// ... becomes:
+///
+/// CreateVec {
+///   count: 3,
+///   create: Create {
+///     ty: 'ty',
+///     mixins: vec!['mixin1', 'mixin2'],
+///     fields: vec!['field1', 'field2'],
+///     values: vec!['value1', 'value2'],
+///   }
+/// }
+///
+/// The count can also be a closure without arguments, e.g. `|| 3`, which is
+/// called once to get the count.
+///
+/// A closure can be passed last, e.g. `|vehicles| { ... }`, which is called
+/// with a mutable reference to the vec once it's created.
+///
+/// Instead of a count, the input of create!(...) for every element can be
+/// given in a list, e.g. create_vec!(ty, [(:mixin1), (field1: value1), ()]):
+///
+/// CreateVec {
+///   creates: Creates::Each(vec![
+///     Create { mixins: vec!['mixin1'], .. },
+///     Create { fields: vec!['field1'], values: vec!['value1'], .. },
+///     Create { .. },
+///   ]),
+/// }
+///
+/// Instead of a count, the values of fields for every element can also be
+/// given, e.g. create_vec!(ty, field1 = ["a", "b"], :mixin1), in which case
+/// the count is their length:
+///
+/// CreateVec {
+///   creates: Creates::Zip(
+///     vec![('field1', '["a", "b"]')],
+///     Create { mixins: vec!['mixin1'], fields: vec!['field1'], .. },
+///   ),
+/// }
+struct CreateVec {
+  creates: Creates,
+  after: Option<Expr>,
+}
+
+enum Creates {
+  /// The same input for every element
+  Count(proc_macro2::TokenStream, Box<Create>),
+  /// An input per element
+  Each(Vec<Create>),
+  /// The same input for every element, except for the fields with a value
+  /// per element, which are taken from the given values in turn
+  Zip(Vec<(Ident, Expr)>, Box<Create>),
+}
+
+/// The binding of the iterator over the values of a field given per element
+fn each_ident(field: &Ident) -> Ident {
+  Ident::new(&format!("__factori_each_{}", field.unraw()), field.span())
+}
+
+/// Parses the count of the macros creating many instances, calling it once if
+/// it's a closure. A negative literal is an error rather than an empty range.
+fn parse_count(input: ParseStream) -> Result<proc_macro2::TokenStream> {
+  let count = match input.parse()? {
+    Expr::Unary(count)
+      if matches!(count.op, syn::UnOp::Neg(_)) && matches!(*count.expr, Expr::Lit(_)) =>
+    {
+      return Err(syn::Error::new_spanned(
+        count,
+        "the count can't be negative",
+      ));
+    }
+    Expr::Closure(closure) if closure.inputs.is_empty() => quote! {
+        {
+          #[allow(clippy::redundant_closure_call)]
+          let count = (#closure)();
+          count
+        }
+    },
+    count => quote! { #count },
+  };
+
+  Ok(count)
+}
+
+impl CreateVec {
+  /// Generates the code creating the vec
+  fn generate_vec(&self) -> proc_macro2::TokenStream {
+    if let Creates::Each(creates) = &self.creates {
+      let create_codes = creates.iter().map(Create::generate_code);
+      return quote! {
+        vec![ #( #create_codes ),* ]
+      };
+    }
+
+    self.generate_iter(|iter| quote! { #iter.collect::<Vec<_>>() })
+  }
+
+  /// Generates the code pushing the elements into the given vec
+  fn generate_extend(&self, target: &Expr) -> proc_macro2::TokenStream {
+    if let Creates::Each(creates) = &self.creates {
+      let create_codes = creates.iter().map(Create::generate_code);
+      return quote! {
+        {
+          let target: &mut Vec<_> = #target;
+          #( target.push(#create_codes); )*
+        }
+      };
+    }
+
+    self.generate_iter(|iter| quote! { ::std::iter::Extend::extend(#target, #iter) })
+  }
+
+  /// Generates the code of a future creating the vec, which awaits the
+  /// elements one after the other
+  #[cfg(feature = "async")]
+  fn generate_vec_async(&self) -> proc_macro2::TokenStream {
+    let vec_code = match &self.creates {
+      Creates::Each(creates) => {
+        let create_codes = creates.iter().map(Create::generate_code_async);
+        quote! {
+          vec![ #( #create_codes.await ),* ]
+        }
+      }
+      _ => self.generate_repeat(|count, create| {
+        let create_code = create.generate_code_async();
+        quote! {
+          {
+            let mut vec = Vec::new();
+            for _ in 0..#count {
+                vec.push(#create_code.await);
+            }
+            vec
+          }
+        }
+      }),
+    };
+
+    let vec_code = self.generate_after(vec_code);
+    quote! {
+        async { #vec_code }
+    }
+  }
+
+  /// Generates the code passing an iterator over the elements to `consume`,
+  /// for the inputs other than a list of elements
+  fn generate_iter(
+    &self,
+    consume: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+  ) -> proc_macro2::TokenStream {
+    self.generate_repeat(|count, create| {
+      let create_code = create.generate_code();
+      consume(quote! { (0..#count).map(|_| #create_code) })
+    })
+  }
+
+  /// Generates the code repeating `create` for the number of elements, with
+  /// `repeat` given the number and the create!(...) of each element, for the
+  /// inputs other than a list of elements
+  fn generate_repeat(
+    &self,
+    repeat: impl Fn(proc_macro2::TokenStream, &Create) -> proc_macro2::TokenStream,
+  ) -> proc_macro2::TokenStream {
+    match &self.creates {
+      Creates::Count(count, create) => repeat(count.clone(), create),
+      Creates::Each(_) => unreachable!("lists of elements aren't iterated over"),
+      Creates::Zip(each, create) => {
+        let idents: Vec<_> = each.iter().map(|(field, _)| each_ident(field)).collect();
+        let values = each.iter().map(|(_, values)| values);
+        let first = &idents[0];
+        let error = format!(
+          "the values given per element to `create_vec!` must have the same length as those of `{}`",
+          each[0].0.unraw()
+        );
+        let consume = repeat(quote! { __factori_count }, create);
+
+        quote! {
+          {
+            #(
+                let #idents: Vec<_> = ::std::iter::IntoIterator::into_iter(#values).collect();
+            )*
+            let __factori_count = #first.len();
+            #(
+                assert!(#idents.len() == __factori_count, #error);
+                let mut #idents = ::std::iter::IntoIterator::into_iter(#idents);
+            )*
+            #consume
+          }
+        }
+      }
+    }
+  }
+
+  /// Wraps the code creating the vec to call the closure passed last, if any
+  fn generate_after(&self, vec_code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match &self.after {
+      Some(after) => quote! {
+          {
+            // Gives the closure its signature, so its argument's type is known.
+            fn after<T>(vec: &mut Vec<T>, after: impl FnOnce(&mut Vec<T>)) {
+                after(vec)
+            }
+
+            let mut vec = #vec_code;
+            after(&mut vec, #after);
+            vec
+          }
+      },
+      None => vec_code,
+    }
+  }
+}
+
+impl Parse for CreateVec {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    Self::parse_after_crate(krate, input)
+  }
+}
+
+impl CreateVec {
+  /// Parses the input after the crate's path, which comes before the target
+  /// in create_into_vec!(...)
+  fn parse_after_crate(krate: proc_macro2::TokenStream, input: ParseStream) -> Result<Self> {
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let ty = parse_factory_ident(input)?;
+
+    input.parse::<Token![,]>()?;
+
+    let creates = if input.peek(syn::token::Bracket) {
+      let list;
+      bracketed!(list in input);
+
+      let mut creates = Vec::new();
+      loop {
+        if list.is_empty() {
+          break;
+        }
+
+        let element;
+        parenthesized!(element in list);
+        creates.push(Create::build_after_type(
+          krate.clone(),
+          prefix.clone(),
+          module.clone(),
+          ty.clone(),
+          &element,
+        )?);
+
+        if list.peek(Token![,]) {
+          list.parse::<Token![,]>()?;
+        }
+      }
+
+      if input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+      }
+
+      Creates::Each(creates)
+    } else if input.peek(Ident) && input.peek2(Token![=]) {
+      let mut each = Vec::new();
+      while input.peek(Ident) && input.peek2(Token![=]) {
+        let field: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let values: Expr = input.parse()?;
+        each.push((field, values));
+
+        if input.peek(Token![,]) {
+          input.parse::<Token![,]>()?;
+        }
+      }
+
+      // Lists whose length is known here can be checked at compile time.
+      let lengths: Vec<_> = each
+        .iter()
+        .filter_map(|(_, values)| match values {
+          Expr::Array(array) => Some((values, array.elems.len())),
+          _ => None,
+        })
+        .collect();
+      if let Some((values, _)) = lengths.iter().find(|(_, len)| *len != lengths[0].1) {
+        return Err(syn::Error::new_spanned(
+          values,
+          "the values given per element must all have the same length",
+        ));
+      }
+
+      let mut create = Create::build_after_type(krate, prefix, module, ty, input)?;
+      for (field, _) in &each {
+        let ident = each_ident(field);
+        create.fields.push(field.clone());
+        create.values.push(syn::parse_quote! {
+            ::std::iter::Iterator::next(&mut #ident).unwrap()
+        });
+        create.nested.push(false);
+      }
+
+      Creates::Zip(each, Box::new(create))
+    } else {
+      let count = parse_count(input)?;
+      let create = Create::build_after_type(krate, prefix, module, ty, input)?;
+      Creates::Count(count, Box::new(create))
+    };
+
+    let after = if input.is_empty() {
+      None
+    } else {
+      let after = input.parse()?;
+      if input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+      }
+      Some(after)
+    };
+
+    Ok(CreateVec { creates, after })
+  }
+}
+
+/// Generates the code for a vec of count the factory
+///
+/// ```
+/// // we basically want from
+/// let users = create_vec!(User, 4, :mixin, name: "blah");
+/// // to generate the following code
+/// let users = (0..4).iter()
+///   .map(|_| code_from_create_generate_code)
+///   .collect<Vec<User>>();
+///
+/// // and from
+/// let users = create_vec!(User, [(:mixin), (name: "blah")]);
+/// // to generate the following code
+/// let users = vec![code_from_create_generate_code, code_from_create_generate_code];
+/// ```
+pub fn create_vec_macro(input: TokenStream) -> TokenStream {
+  let create_vec: CreateVec = parse_macro_input!(input);
+
+  let vec_code = create_vec.generate_vec();
+
+  create_vec.generate_after(vec_code).into()
+}
+
+#[cfg(feature = "async")]
+pub fn create_vec_async_macro(input: TokenStream) -> TokenStream {
+  let create_vec: CreateVec = parse_macro_input!(input);
+  create_vec.generate_vec_async().into()
+}
+
+/// e.g. create_into_vec!(&mut vec, ty, 3, :mixin1, field1: value1)
+///
+/// ... becomes:
+///
+/// CreateIntoVec {
+///   target: '&mut vec',
+///   create_vec: CreateVec { ... },
+/// }
+struct CreateIntoVec {
+  target: Expr,
+  create_vec: CreateVec,
+}
+
+impl Parse for CreateIntoVec {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let target = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let create_vec = CreateVec::parse_after_crate(krate, input)?;
+
+    Ok(CreateIntoVec { target, create_vec })
+  }
+}
+
+/// Generates the code pushing count instances of the factory into a vec
+///
+/// ```
+/// // we basically want from
+/// create_into_vec!(&mut users, User, 4, :mixin, name: "blah");
+/// // to generate the following code
+/// Extend::extend(&mut users, (0..4).map(|_| code_from_create_generate_code));
+/// ```
+pub fn create_into_vec_macro(input: TokenStream) -> TokenStream {
+  let CreateIntoVec { target, create_vec } = parse_macro_input!(input);
+  if let Some(after) = &create_vec.after {
+    let message = "create_into_vec! doesn't take a closure over the vec, it can be used directly";
+    return syn::Error::new_spanned(after, message)
+      .to_compile_error()
+      .into();
+  }
+
+  create_vec.generate_extend(&target).into()
+}
+
+/// e.g. create_collection!(HashSet<_>, ty, 3, :mixin1, field1: value1)
+///
+/// ... becomes:
+///
+/// CreateCollection {
+///   collection: 'HashSet<_>',
+///   create_vec: CreateVec { ... },
+/// }
+struct CreateCollection {
+  collection: Type,
+  create_vec: CreateVec,
+}
+
+impl Parse for CreateCollection {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let collection = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let create_vec = CreateVec::parse_after_crate(krate, input)?;
+
+    Ok(CreateCollection {
+      collection,
+      create_vec,
+    })
+  }
+}
+
+/// Generates the code collecting count instances of the factory into the
+/// given collection, which implements `FromIterator`
+///
+/// ```
+/// // we basically want from
+/// create_collection!(HashSet<_>, User, 4, :mixin, name: "blah");
+/// // to generate the following code
+/// (0..4).map(|_| code_from_create_generate_code).collect::<HashSet<_>>();
+/// ```
+pub fn create_collection_macro(input: TokenStream) -> TokenStream {
+  let CreateCollection {
+    collection,
+    create_vec,
+  } = parse_macro_input!(input);
+  if let Some(after) = &create_vec.after {
+    let message = "create_collection! doesn't take a closure over the vec, only create_vec! does";
+    return syn::Error::new_spanned(after, message)
+      .to_compile_error()
+      .into();
+  }
+
+  if let Creates::Each(_) = &create_vec.creates {
+    let vec_code = create_vec.generate_vec();
+    return quote! {
+        ::std::iter::Iterator::collect::<#collection>(::std::iter::IntoIterator::into_iter(#vec_code))
+    }
+    .into();
+  }
+
+  create_vec
+    .generate_iter(|iter| quote! { #iter.collect::<#collection>() })
+    .into()
+}
+
+/// e.g. create_vec_unique!(ty, 3, key = field1, :mixin1, field2: value2)
+///
+/// ... becomes:
+///
+/// CreateVecUnique {
+///   count: '3',
+///   key: 'field1',
+///   create: Create { ... },
+/// }
+struct CreateVecUnique {
+  count: proc_macro2::TokenStream,
+  key: Ident,
+  create: Create,
+}
+
+impl Parse for CreateVecUnique {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let ty = parse_factory_ident(input)?;
+    input.parse::<Token![,]>()?;
+    let count = parse_count(input)?;
+    input.parse::<Token![,]>()?;
+    input.parse::<kw::key>()?;
+    input.parse::<Token![=]>()?;
+    let key = input.parse()?;
+    let create = Create::build_after_type(krate, prefix, module, ty, input)?;
+
+    Ok(CreateVecUnique { count, key, create })
+  }
+}
+
+/// Generates the code for a vec of count instances of the factory whose keys
+/// are all different, creating more of them in place of the duplicates, up
+/// to ten times the count
+///
+/// ```
+/// // we basically want from
+/// create_vec_unique!(User, 4, key = email, :mixin);
+/// // to generate the following code
+/// let mut keys = HashSet::new();
+/// let mut values = Vec::with_capacity(4);
+/// while values.len() < 4 {
+///   let value = code_from_create_generate_code;
+///   if keys.insert(value.email.clone()) {
+///     values.push(value);
+///   }
+/// }
+/// ```
+pub fn create_vec_unique_macro(input: TokenStream) -> TokenStream {
+  let CreateVecUnique { count, key, create } = parse_macro_input!(input);
+  let create_code = create.generate_code();
+  let error = format!(
+    "create_vec_unique! gave up after {{}} attempts, with {{}} of the {{}} `{}` having distinct `{}` values",
+    create.ty.unraw(),
+    key.unraw()
+  );
+
+  quote! {
+    {
+      let __factori_count: usize = #count;
+      let __factori_max_attempts = __factori_count.saturating_mul(10);
+      let mut __factori_keys = ::std::collections::HashSet::new();
+      let mut __factori_values = ::std::vec::Vec::with_capacity(__factori_count);
+      let mut __factori_attempts = 0;
+      while __factori_values.len() < __factori_count {
+        if __factori_attempts == __factori_max_attempts {
+          panic!(#error, __factori_attempts, __factori_values.len(), __factori_count);
+        }
+        __factori_attempts += 1;
+
+        let __factori_value = #create_code;
+        if __factori_keys.insert(::std::clone::Clone::clone(&__factori_value.#key)) {
+          __factori_values.push(__factori_value);
+        }
+      }
+      __factori_values
+    }
+  }
+  .into()
+}
+
+/// Generates the code for a vec of count the factory, with each instance
+/// passed through the closure passed last
+///
+/// ```
+/// // we basically want from
+/// let names = create_mapped!(User, 4, :mixin, |user| user.name);
+/// // to generate the following code
+/// let names = (0..4).iter()
+///   .map(|_| code_from_create_generate_code)
+///   .collect<Vec<User>>()
+///   .into_iter()
+///   .map(|user| user.name)
+///   .collect<Vec<_>>();
+/// ```
+pub fn create_mapped_macro(input: TokenStream) -> TokenStream {
+  let create_vec: CreateVec = parse_macro_input!(input);
+  let map = match &create_vec.after {
+    Some(map) => map,
+    None => {
+      let message = "create_mapped! needs a closure to map the instances, e.g. `|vehicle| ...`";
+      return syn::Error::new(proc_macro2::Span::call_site(), message)
+        .to_compile_error()
+        .into();
+    }
+  };
+
+  let vec_code = create_vec.generate_vec();
+
+  let quoted = quote! {
+      ::std::iter::IntoIterator::into_iter(#vec_code)
+          .map(#map)
+          .collect::<Vec<_>>()
+  };
+
+  quoted.into()
+}
+
+/// Generates the code for a vec of count clones of a single instance
+///
+/// ```
+/// // we basically want from
+/// let users = create_vec_clone!(User, 4, :mixin, name: "blah");
+/// // to generate the following code
+/// let users = {
+///   let base = code_from_create_generate_code;
+///   (0..4).map(|_| base.clone()).collect::<Vec<User>>()
+/// };
+/// ```
+pub fn create_vec_clone_macro(input: TokenStream) -> TokenStream {
+  let create_vec: CreateVec = parse_macro_input!(input);
+  let (count, create) = match &create_vec.creates {
+    Creates::Count(count, create) => (count, create),
+    Creates::Each(_) | Creates::Zip(..) => {
+      let message =
+        "create_vec_clone! needs a count, use create_vec! to give each element its own values";
+      return syn::Error::new(proc_macro2::Span::call_site(), message)
+        .to_compile_error()
+        .into();
+    }
+  };
+
+  let create_code = create.generate_code();
+
+  let quoted = create_vec.generate_after(quote! {
+    {
+      let base = #create_code;
+      (0..#count).map(|_| base.clone()).collect::<Vec<_>>()
+    }
+  });
+
+  quoted.into()
+}
+
+/// Generates the code for a vec of count instances built from clones of a
+/// single builder, which needs `#[factori(clone_builder)]`
+///
+/// ```
+/// // we basically want from
+/// let users = create_vec_from_builder!(User, 4, :mixin, name: "blah");
+/// // to generate the following code
+/// let users = {
+///   let builder = code_from_create_generate_builder_code;
+///   (0..4).map(|_| Builder::build(builder.clone())).collect::<Vec<User>>()
+/// };
+/// ```
+pub fn create_vec_from_builder_macro(input: TokenStream) -> TokenStream {
+  let create_vec: CreateVec = parse_macro_input!(input);
+  let (count, create) = match &create_vec.creates {
+    Creates::Count(count, create) => (count, create),
+    Creates::Each(_) | Creates::Zip(..) => {
+      let message =
+        "create_vec_from_builder! needs a count, use create_vec! to give each element its own values";
+      return syn::Error::new(proc_macro2::Span::call_site(), message)
+        .to_compile_error()
+        .into();
+    }
+  };
+
+  let builder_code = create.generate_builder_code();
+  let build_code = create.generate_build(quote! {
+      ::std::clone::Clone::clone(&builder)
+  });
+
+  let quoted = create_vec.generate_after(quote! {
+    {
+      let builder = #builder_code;
+      (0..#count).map(|_| #build_code).collect::<Vec<_>>()
+    }
+  });
+
+  quoted.into()
+}
+
+/// e.g. create_hashmap!(ty, 3, key = field1, :mixin1, field2: value2)
+///
+/// ... becomes:
+///
+/// CreateHashMap {
+///   count: 3,
+///   key: 'field1',
+///   create: Create { ... }
+/// }
+struct CreateHashMap {
+  count: proc_macro2::TokenStream,
+  key: Ident,
+  create: Create,
+}
+
+impl Parse for CreateHashMap {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let ty = parse_factory_ident(input)?;
+
+    input.parse::<Token![,]>()?;
+    let count = parse_count(input)?;
+    input.parse::<Token![,]>()?;
+    input.parse::<kw::key>()?;
+    input.parse::<Token![=]>()?;
+    let key = input.parse()?;
+    let create = Create::build_after_type(krate, prefix, module, ty, input)?;
+
+    Ok(CreateHashMap { count, key, create })
+  }
+}
+
+/// Generates the code for a hashmap of count instances of the factory, keyed
+/// by the value of one of their fields
+///
+/// ```
+/// // we basically want from
+/// let users = create_hashmap!(User, 4, key = id, :mixin);
+/// // to generate the following code
+/// let users = (0..4)
+///   .map(|_| code_from_create_generate_code)
+///   .map(|value| (value.id.clone(), value))
+///   .collect::<HashMap<_, User>>();
+/// ```
+pub fn create_hashmap_macro(input: TokenStream) -> TokenStream {
+  let CreateHashMap { count, key, create } = parse_macro_input!(input);
+
+  let create_code = create.generate_code();
+
+  let quoted = quote! {
+    (0..#count)
+      .map(|_| #create_code)
+      .map(|value| (::std::clone::Clone::clone(&value.#key), value))
+      .collect::<::std::collections::HashMap<_, _>>()
+  };
+
+  quoted.into()
+}
+
+/// e.g. create_dyn_vec!(dyn Trait, [ty1, (ty2, :mixin1, field1: value1)])
+///
+/// ... becomes:
+///
+/// CreateDynVec {
+///   ty: 'dyn Trait',
+///   creates: vec![Create { ty: 'ty1', .. }, Create { ty: 'ty2', ... }],
+/// }
+///
+/// An element is either the type of a factory, or the arguments of create!()
+/// in parentheses
+struct CreateDynVec {
+  ty: syn::Type,
+  creates: Vec<Create>,
+}
+
+impl Parse for CreateDynVec {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let ty = input.parse()?;
+    input.parse::<Token![,]>()?;
+
+    let list;
+    bracketed!(list in input);
+
+    let mut creates = Vec::new();
+    while !list.is_empty() {
+      if list.peek(syn::token::Paren) {
+        let element;
+        parenthesized!(element in list);
+        let prefix = parse_prefix(&element)?;
+        let module = parse_factory_module(&element)?;
+        let ty = parse_factory_ident(&element)?;
+        creates.push(Create::build_after_type(
+          krate.clone(),
+          prefix,
+          module,
+          ty,
+          &element,
+        )?);
+      } else {
+        let prefix = parse_prefix(&list)?;
+        let module = parse_factory_module(&list)?;
+        let ty = parse_factory_ident(&list)?;
+        creates.push(Parser::parse2(
+          |input: ParseStream| Create::build_after_type(krate.clone(), prefix, module, ty, input),
+          proc_macro2::TokenStream::new(),
+        )?);
+      }
+
+      if list.peek(Token![,]) {
+        list.parse::<Token![,]>()?;
+      }
+    }
+
+    if input.peek(Token![,]) {
+      input.parse::<Token![,]>()?;
+    }
+
+    Ok(CreateDynVec { ty, creates })
+  }
+}
+
+/// Generates the code for a vec of boxed instances of different factories
+///
+/// ```
+/// // we basically want from
+/// let shapes = create_dyn_vec!(dyn Shape, [Circle, (Square, side: 2)]);
+/// // to generate the following code
+/// let shapes: Vec<Box<dyn Shape>> = vec![
+///   Box::new(code_from_create_generate_code),
+///   Box::new(code_from_create_generate_code),
+/// ];
+/// ```
+pub fn create_dyn_vec_macro(input: TokenStream) -> TokenStream {
+  let CreateDynVec { ty, creates } = parse_macro_input!(input);
+
+  let create_codes = creates.iter().map(Create::generate_code);
+
+  // The type is given for an empty list too.
+  let quoted = quote! {
+    {
+      let vec: Vec<::std::boxed::Box<#ty>> = vec![
+        #( ::std::boxed::Box::new(#create_codes) ),*
+      ];
+      vec
+    }
+  };
+
+  quoted.into()
+}
+
+/// e.g. create_with_seed!(ty, 1234, :mixin1, field1: value1)
+///
+/// ... becomes:
+///
+/// CreateWithSeed {
+///   seed: 1234,
+///   create: Create { ... }
+/// }
+struct CreateWithSeed {
+  seed: Expr,
+  create: Create,
+}
+
+impl Parse for CreateWithSeed {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let ty = parse_factory_ident(input)?;
+
+    input.parse::<Token![,]>()?;
+    let seed = input.parse()?;
+
+    let create = Create::build_after_type(krate, prefix, module, ty, input)?;
+
+    Ok(CreateWithSeed { seed, create })
+  }
+}
+
+/// Generates the code for the factory with the rng seeded while it runs
+///
+/// The guard restores the previous rng state when dropped, so calls without
+/// a seed are unaffected.
+pub fn create_with_seed_macro(input: TokenStream) -> TokenStream {
+  let CreateWithSeed { seed, create } = parse_macro_input!(input);
+
+  let krate = &create.krate;
+  let create_code = create.generate_code();
+
+  let quoted = quote! {
+    {
+      let _seed = #krate::rng::SeedGuard::new(#seed);
+      #create_code
+    }
+  };
+
+  quoted.into()
+}
+
+/// Generates the code to get the shared instance of the factory
+///
+/// The instance is only created the first time for the type and the mixins
+/// and fields of the call, as written, which are its key.
+pub fn create_shared_macro(input: TokenStream) -> TokenStream {
+  let key = input.to_string();
+  let create: Create = parse_macro_input!(input);
+
+  let krate = &create.krate;
+  let create_code = create.generate_code();
+
+  let quoted = quote! {
+    #krate::shared::get_or_insert_with(#key, || #create_code)
+  };
+
+  quoted.into()
+}
+
+/// Generates the code to remove the shared instance of the factory
+/// e.g. reset_shared!(ty)
+struct ResetShared {
+  krate: proc_macro2::TokenStream,
+  prefix: String,
+  module: proc_macro2::TokenStream,
+  ty: Ident,
+}
+
+impl Parse for ResetShared {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let prefix = parse_prefix(input)?;
+    let module = parse_factory_module(input)?;
+    let ty = parse_factory_ident(input)?;
+
+    Ok(ResetShared {
+      krate,
+      prefix,
+      module,
+      ty,
+    })
+  }
+}
+
+pub fn reset_shared_macro(input: TokenStream) -> TokenStream {
+  let ResetShared {
+    krate,
+    prefix,
+    module,
+    ty,
+  } = parse_macro_input!(input);
+
+  let factory_check = factory_check(&prefix, &module, &ty);
+  let ident_builder = ident_builder(&prefix, &ty);
+
+  let quoted = quote! {
+    {
+      #factory_check
+      #krate::shared::reset::<<#module #ident_builder as #krate::Builder>::Ty>()
+    }
   };
 
   quoted.into()
@@ -1,14 +1,39 @@
-use proc_macro2::{Ident, TokenStream, TokenTree};
-use quote::quote;
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{braced, parse_macro_input, Expr, Token, Type};
-
-use super::{ident_builder, ident_mixins_enum};
+use syn::{
+  braced, parse_macro_input, parse_quote, Attribute, Expr, GenericArgument, Generics, Item, Lit,
+  Path, PathArguments, Stmt, Token, Type,
+};
+
+use super::{
+  factory_ident, field_default_fn, ident_builder, ident_factory, ident_fields, ident_mixins_enum,
+  mixin_fields_const, mixin_group_const, mixin_name_const, parse_crate_path, parse_options,
+  DEFAULT_PREFIX,
+};
+
+mod kw {
+  syn::custom_keyword!(alias);
+  syn::custom_keyword!(lazy);
+  syn::custom_keyword!(map);
+  syn::custom_keyword!(reset);
+  syn::custom_keyword!(weak);
+}
 
 struct DefaultBlock {
+  attrs: Vec<Vec<Attribute>>,
+  lazy: Vec<bool>,
+  /// Whether the field is marked `#[factori(skip)]`, so that `create!` can't
+  /// override it
+  skip: Vec<bool>,
   fields: Vec<Ident>,
+  renames: Vec<Option<Ident>>,
   types: Vec<Option<Type>>,
   values: Vec<Expr>,
+  /// The closure after `map`, applied to the field's final value when
+  /// building, e.g. `name: String = "x".into() map |n| n.to_lowercase()`
+  maps: Vec<Option<Expr>>,
 }
 
 impl Parse for DefaultBlock {
@@ -16,17 +41,57 @@ impl Parse for DefaultBlock {
     let inner;
     braced!(inner in input);
 
+    let mut attrs = Vec::new();
+    let mut lazy = Vec::new();
+    let mut skip = Vec::new();
     let mut fields = Vec::new();
+    let mut renames = Vec::new();
     let mut types = Vec::new();
     let mut values = Vec::new();
+    let mut maps = Vec::new();
 
     loop {
       if inner.is_empty() {
         break;
       }
 
+      // Outer attributes (e.g. #[cfg(...)]) are forwarded to the generated
+      // field, except for `#[factori(skip)]`.
+      let mut field_attrs = inner.call(Attribute::parse_outer)?;
+      let mut field_skip = false;
+      for attr in &field_attrs {
+        if attr.path.is_ident("factori") {
+          let option: Ident = attr.parse_args()?;
+          if option != "skip" {
+            return Err(syn::Error::new(option.span(), "expected `skip`"));
+          }
+          field_skip = true;
+        }
+      }
+      field_attrs.retain(|attr| !attr.path.is_ident("factori"));
+      attrs.push(field_attrs);
+      skip.push(field_skip);
+
+      // Optional `lazy` marker, e.g. `lazy token: String = expensive()`. A field
+      // can also be named `lazy`, so it's only a marker if a name follows.
+      if inner.peek(kw::lazy) && inner.peek2(syn::Ident) {
+        inner.parse::<kw::lazy>()?;
+        lazy.push(true);
+      } else {
+        lazy.push(false);
+      }
+
       fields.push(inner.parse()?);
 
+      // Optional name of the struct's field, when it differs from the name used
+      // in the factory, e.g. `kind as r#type`.
+      if inner.peek(Token![as]) {
+        inner.parse::<Token![as]>()?;
+        renames.push(Some(inner.parse()?));
+      } else {
+        renames.push(None);
+      }
+
       // Optional type. If it's specified for one field it needs to be specified for all.
       // Should be specified only if there is a builder {} block.
       // This is enforced in Definition::validate().
@@ -38,34 +103,98 @@ impl Parse for DefaultBlock {
       }
 
       inner.parse::<Token![=]>()?;
-      values.push(inner.parse()?);
+      let value = inner.parse()?;
+      values.push(once_value(value, types.last().expect("pushed above"))?);
 
-      if inner.peek(Token![,]) {
-        inner.parse::<Token![,]>()?;
+      if inner.peek(kw::map) {
+        inner.parse::<kw::map>()?;
+        maps.push(Some(inner.parse()?));
+      } else {
+        maps.push(None);
       }
+
+      parse_field_separator(&inner)?;
     }
 
     Ok(Self {
+      attrs,
+      lazy,
+      skip,
       fields,
+      renames,
       types,
       values,
+      maps,
     })
   }
 }
 
+#[derive(Clone)]
 struct MixinBlock {
   name: Ident,
+  /// The parameters of a parameterized mixin, e.g. `mixin wheels(n: u8) {}`
+  params: Vec<(Ident, Type)>,
+  /// The other names of the mixin, e.g. `mixin electric alias ev {}`
+  aliases: Vec<Ident>,
+  /// The `mixin_group` of the mixin, if any, whose mixins can't be applied
+  /// together
+  group: Option<Ident>,
+  /// Whether the mixin is `weak`, only setting the fields which the mixins
+  /// before it didn't set, e.g. `mixin weak stock {}`
+  weak: bool,
+  attrs: Vec<Vec<Attribute>>,
   fields: Vec<Ident>,
-  values: Vec<Expr>,
+  /// None for fields reset to their default, e.g. `reset number_wheels`
+  values: Vec<Option<Expr>>,
 }
 
 impl Parse for MixinBlock {
   fn parse(input: ParseStream) -> Result<Self> {
+    // A mixin can also be named `weak`, so it's only a marker if a name follows.
+    let weak = input.peek(kw::weak) && input.peek2(syn::Ident);
+    if weak {
+      input.parse::<kw::weak>()?;
+    }
+
     let name = input.parse()?;
 
+    let mut params = Vec::new();
+    if input.peek(syn::token::Paren) {
+      let list;
+      syn::parenthesized!(list in input);
+
+      loop {
+        if list.is_empty() {
+          break;
+        }
+
+        let param = list.parse()?;
+        list.parse::<Token![:]>()?;
+        params.push((param, list.parse()?));
+
+        if list.peek(Token![,]) {
+          list.parse::<Token![,]>()?;
+        }
+      }
+    }
+
+    let mut aliases = Vec::new();
+    if input.peek(kw::alias) {
+      input.parse::<kw::alias>()?;
+      loop {
+        aliases.push(input.parse()?);
+
+        if !input.peek(Token![,]) {
+          break;
+        }
+        input.parse::<Token![,]>()?;
+      }
+    }
+
     let inner;
     braced!(inner in input);
 
+    let mut attrs = Vec::new();
     let mut fields = Vec::new();
     let mut values = Vec::new();
 
@@ -74,34 +203,198 @@ impl Parse for MixinBlock {
         break;
       }
 
-      fields.push(inner.parse()?);
-      inner.parse::<Token![=]>()?;
-      values.push(inner.parse()?);
+      attrs.push(inner.call(Attribute::parse_outer)?);
 
-      if inner.peek(Token![,]) {
-        inner.parse::<Token![,]>()?;
+      // A field can also be named `reset`, so it's only a marker if a name follows.
+      if inner.peek(kw::reset) && inner.peek2(syn::Ident) {
+        inner.parse::<kw::reset>()?;
+        fields.push(inner.parse()?);
+        values.push(None);
+      } else {
+        fields.push(inner.parse()?);
+        inner.parse::<Token![=]>()?;
+        values.push(Some(inner.parse()?));
       }
+
+      parse_field_separator(&inner)?;
     }
 
     Ok(Self {
       name,
+      params,
+      aliases,
+      group: None,
+      weak,
+      attrs,
       fields,
       values,
     })
   }
 }
 
+/// Parses the `,` after a field of a block, if any. A `;` is an error which
+/// says so, rather than the one about the next field.
+fn parse_field_separator(input: ParseStream) -> Result<()> {
+  if input.peek(Token![;]) {
+    let semi = input.parse::<Token![;]>()?;
+    return Err(syn::Error::new(
+      semi.span,
+      "use `,` to separate factory fields",
+    ));
+  }
+
+  if input.peek(Token![,]) {
+    input.parse::<Token![,]>()?;
+  }
+
+  Ok(())
+}
+
+/// Parses the mixins of a group, e.g. `mixin_group state { mixin shipped {}
+/// mixin cancelled {} }`, after `mixin_group`
+fn parse_mixin_group(input: ParseStream) -> Result<Vec<MixinBlock>> {
+  let group: Ident = input.parse()?;
+  let inner;
+  braced!(inner in input);
+
+  let mut mixins = Vec::new();
+  loop {
+    if inner.is_empty() {
+      break;
+    }
+
+    let key: Ident = inner.parse()?;
+    if key != "mixin" {
+      return Err(syn::Error::new(key.span(), "expected `mixin`"));
+    }
+
+    let mut mixin: MixinBlock = inner.parse()?;
+    mixin.group = Some(group.clone());
+    mixins.push(mixin);
+  }
+
+  Ok(mixins)
+}
+
+/// `partial { email: String }`, with the fields which have no default and
+/// are left unset until they're given, e.g. with the builder's setters
+struct PartialBlock {
+  attrs: Vec<Vec<Attribute>>,
+  fields: Vec<Ident>,
+  types: Vec<Type>,
+}
+
+impl Parse for PartialBlock {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let inner;
+    braced!(inner in input);
+
+    let mut attrs = Vec::new();
+    let mut fields = Vec::new();
+    let mut types = Vec::new();
+
+    loop {
+      if inner.is_empty() {
+        break;
+      }
+
+      attrs.push(inner.call(Attribute::parse_outer)?);
+      fields.push(inner.parse()?);
+      inner.parse::<Token![:]>()?;
+      types.push(inner.parse()?);
+      parse_field_separator(&inner)?;
+    }
+
+    Ok(Self {
+      attrs,
+      fields,
+      types,
+    })
+  }
+}
+
+impl DefaultBlock {
+  /// Adds the fields of a partial {} block, which are lazy fields whose
+  /// default value is a panic, as they have none.
+  fn extend_partial(&mut self, partial: PartialBlock) {
+    for ((attrs, field), ty) in partial
+      .attrs
+      .into_iter()
+      .zip(partial.fields)
+      .zip(partial.types)
+    {
+      let message = partial_message(&field);
+      self.attrs.push(attrs);
+      self.lazy.push(true);
+      self.skip.push(false);
+      self.fields.push(field);
+      self.renames.push(None);
+      self.types.push(Some(ty));
+      self.values.push(parse_quote! { panic!(#message) });
+      self.maps.push(None);
+    }
+  }
+}
+
+/// `meta { category = "auth", priority = 1 }`, whose values become consts
+/// of the type
+struct MetaBlock {
+  span: Span,
+  keys: Vec<Ident>,
+  values: Vec<Lit>,
+}
+
+impl Parse for MetaBlock {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let span = input.span();
+    let inner;
+    braced!(inner in input);
+
+    let mut keys: Vec<Ident> = Vec::new();
+    let mut values = Vec::new();
+
+    loop {
+      if inner.is_empty() {
+        break;
+      }
+
+      let key: Ident = inner.parse()?;
+      if keys.iter().any(|other| other.unraw() == key.unraw()) {
+        let message = format!("`{}` is set twice in the meta {{}} block", key.unraw());
+        return Err(syn::Error::new(key.span(), message));
+      }
+      inner.parse::<Token![=]>()?;
+      let value: Lit = inner.parse()?;
+      if !matches!(value, Lit::Str(_) | Lit::Int(_)) {
+        let message = "expected a string or integer literal";
+        return Err(syn::Error::new(value.span(), message));
+      }
+
+      keys.push(key);
+      values.push(value);
+      parse_field_separator(&inner)?;
+    }
+
+    Ok(Self { span, keys, values })
+  }
+}
+
 struct TransientBlock {
+  span: Span,
+  attrs: Vec<Vec<Attribute>>,
   fields: Vec<Ident>,
-  values: Vec<Expr>,
+  /// None for required fields, which have no default, e.g. `tenant_id: u64`
+  values: Vec<Option<Expr>>,
   types: Vec<Type>,
 }
 
 impl Parse for TransientBlock {
   fn parse(input: ParseStream) -> Result<Self> {
+    let span = input.span();
     let inner;
     braced!(inner in input);
 
+    let mut attrs = Vec::new();
     let mut fields = Vec::new();
     let mut values = Vec::new();
     let mut types = Vec::new();
@@ -111,19 +404,23 @@ impl Parse for TransientBlock {
         break;
       }
 
-      // parse a: type = value and  take ending , if there
+      // parse #[attr] a: type = value and  take ending , if there
+      attrs.push(inner.call(Attribute::parse_outer)?); // #[attr]
       fields.push(inner.parse()?); // a
       inner.parse::<Token![:]>()?; // :
       types.push(inner.parse()?); // type
-      inner.parse::<Token![=]>()?; // =
-      values.push(inner.parse()?); // value
-      if inner.peek(Token![,]) {
-        // maybe ,
-        inner.parse::<Token![,]>()?;
+      if inner.peek(Token![=]) {
+        inner.parse::<Token![=]>()?; // =
+        values.push(Some(inner.parse()?)); // value
+      } else {
+        values.push(None);
       }
+      parse_field_separator(&inner)?;
     }
 
     Ok(Self {
+      span,
+      attrs,
       fields,
       values,
       types,
@@ -132,26 +429,91 @@ impl Parse for TransientBlock {
 }
 
 struct Definition {
-  ty: Ident,
+  /// The path of the factori-imp crate
+  krate: TokenStream,
+  ty: Path,
+  /// The type parameter of a generic factory, e.g. `<T: Default>`
+  generics: Generics,
+  /// The name of the factory when it's one of a `factories!` group
+  name: Option<Ident>,
+  /// The prefix of the generated types, `_Factori` by default
+  prefix: String,
+  /// Set by `#[factori(exhaustive)]`, to check that the `default` block has
+  /// every field of the struct
+  exhaustive: Option<Ident>,
+  /// Set by `#[factori(clone_builder)]`, to make the builder `Clone`
+  clone_builder: Option<Ident>,
+  /// Set by `#[factori(builder_derive(...))]`, with the traits to derive on
+  /// the builder
+  builder_derive: Option<(Ident, Vec<Path>)>,
+  /// Set by `#[factori(mixins_derive(...))]`, with the traits to derive on
+  /// the mixins enum
+  mixins_derive: Vec<Path>,
+  /// Set by `#[factori(allow(...))]`, with the lints to allow in the
+  /// generated items
+  allow: Vec<Path>,
+  /// The visibility of the generated types, which is `pub` except for the
+  /// local factories of `factori_local!`, whose types can be private
+  vis: TokenStream,
 
   default: DefaultBlock,
   transient: Option<TransientBlock>,
   builder: Option<TokenTree>,
+  /// The error type of a `try_builder(Error) {}` block
+  builder_error: Option<Type>,
+  /// Set by a `builder async {}` block, which is built by `AsyncBuilder`
+  /// instead of `Builder`
+  builder_async: Option<Token![async]>,
   mixins: Vec<MixinBlock>,
+  meta: Option<MetaBlock>,
+  /// The closure of a `validate {}` block, called with a reference to the
+  /// builder before building
+  validate: Option<(Ident, Expr)>,
+  /// The statements of a `computed {}` block, e.g. `let area = w * h;`,
+  /// whose bindings the builder {} block can use but create! can't set
+  computed: Option<(Ident, Vec<Stmt>)>,
 }
 
-impl Parse for Definition {
-  fn parse(input: ParseStream) -> Result<Self> {
-    let ty = input.parse()?;
+impl Definition {
+  fn parse(krate: TokenStream, input: ParseStream) -> Result<Self> {
+    let options = parse_options(input)?;
+    let mut ty = input.parse()?;
+    let generics = take_generics(&mut ty)?;
     input.parse::<Token![,]>()?;
 
     let inner;
     braced!(inner in input);
 
+    let mut definition = Self::parse_blocks(krate, ty, None, &inner)?;
+    definition.generics = generics;
+    definition.prefix = options.prefix;
+    definition.exhaustive = options.exhaustive;
+    definition.clone_builder = options.clone_builder;
+    definition.builder_derive = options.builder_derive;
+    definition.mixins_derive = options
+      .mixins_derive
+      .map_or_else(Vec::new, |(_, traits)| traits);
+    definition.allow = options.allow.map_or_else(Vec::new, |(_, lints)| lints);
+    Ok(definition)
+  }
+
+  /// Parses the blocks in the braces of a definition, e.g. `default { ... }`
+  fn parse_blocks(
+    krate: TokenStream,
+    ty: Path,
+    name: Option<Ident>,
+    inner: ParseStream,
+  ) -> Result<Self> {
     let mut default: Option<DefaultBlock> = None;
     let mut transient: Option<TransientBlock> = None;
     let mut builder = None;
+    let mut builder_error = None;
+    let mut builder_async: Option<Token![async]> = None;
     let mut mixins = Vec::new();
+    let mut meta: Option<MetaBlock> = None;
+    let mut partial: Option<PartialBlock> = None;
+    let mut validate = None;
+    let mut computed = None;
 
     loop {
       if inner.is_empty() {
@@ -168,235 +530,2113 @@ impl Parse for Definition {
         if builder.is_some() {
           return Err(inner.error("builder {} block is defined twice"));
         }
+        builder_async = inner.parse()?;
+        if let (Some(token), false) = (&builder_async, cfg!(feature = "async")) {
+          let message = "`builder async` needs the `async` feature of factori-imp";
+          return Err(syn::Error::new(token.span, message));
+        }
+        builder = Some(inner.parse()?);
+      } else if key == "try_builder" {
+        if builder.is_some() {
+          return Err(inner.error("builder {} block is defined twice"));
+        }
+        let error;
+        syn::parenthesized!(error in inner);
+        builder_error = Some(error.parse()?);
         builder = Some(inner.parse()?);
       } else if key == "mixin" {
         mixins.push(inner.parse()?);
+      } else if key == "mixin_group" {
+        mixins.extend(parse_mixin_group(inner)?);
       } else if key == "transient" {
         if transient.is_some() {
           return Err(inner.error("transient {} block defined twice"));
         }
         transient = Some(inner.parse()?);
+      } else if key == "meta" {
+        if meta.is_some() {
+          return Err(inner.error("meta {} block defined twice"));
+        }
+        meta = Some(inner.parse()?);
+      } else if key == "validate" {
+        if validate.is_some() {
+          return Err(inner.error("validate {} block defined twice"));
+        }
+        let closure;
+        braced!(closure in inner);
+        validate = Some((key, closure.parse()?));
+      } else if key == "partial" {
+        if partial.is_some() {
+          return Err(inner.error("partial {} block defined twice"));
+        }
+        partial = Some(inner.parse()?);
+      } else if key == "computed" {
+        if computed.is_some() {
+          return Err(inner.error("computed {} block defined twice"));
+        }
+        let block;
+        braced!(block in inner);
+        computed = Some((key, block.call(syn::Block::parse_within)?));
       }
     }
 
-    if transient.is_some() && builder.is_none() {
-      return Err(inner.error("transient attributes require a builder {} block"));
+    let mut default = default.ok_or_else(|| inner.error("missing default {} block"))?;
+    if let Some(partial) = partial {
+      default.extend_partial(partial);
     }
 
-    let default = default.ok_or_else(|| inner.error("missing default {} block"))?;
-
     Ok(Self {
+      krate,
       ty,
+      generics: Generics::default(),
+      name,
+      prefix: DEFAULT_PREFIX.to_string(),
+      exhaustive: None,
+      clone_builder: None,
+      builder_derive: None,
+      mixins_derive: Vec::new(),
+      allow: Vec::new(),
+      vis: quote! { pub },
       default,
       builder,
+      builder_error,
+      builder_async,
       mixins,
       transient,
+      meta,
+      validate,
+      computed,
     })
   }
-}
 
-impl Definition {
-  fn validate(&self) -> Option<TokenStream> {
-    let missing_type = self
+  /// The generated types are named after the last segment of the type's path,
+  /// e.g. `other_crate::Vehicle` generates `_Factori_Builder_Vehicle`, or after
+  /// the factory's name in a `factories!` group.
+  fn ty_ident(&self) -> Ident {
+    match &self.name {
+      Some(name) => name.clone(),
+      None => factory_ident(self.ty.segments.last().expect("paths have a segment")),
+    }
+  }
+
+  /// The builder with its type parameter, for use in types and impls
+  fn builder_ty(&self) -> TokenStream {
+    let ident_builder = ident_builder(&self.prefix, &self.ty_ident());
+    let (_, ty_generics, _) = self.generics.split_for_impl();
+
+    quote! { #ident_builder #ty_generics }
+  }
+
+  /// Whether the type mentions the factory's type parameter
+  fn is_generic_type(&self, ty: &Type) -> bool {
+    let params: Vec<_> = self
+      .generics
+      .type_params()
+      .map(|param| &param.ident)
+      .collect();
+
+    fn mentions(tokens: TokenStream, params: &[&Ident]) -> bool {
+      tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => params.contains(&&ident),
+        TokenTree::Group(group) => mentions(group.stream(), params),
+        _ => false,
+      })
+    }
+
+    mentions(quote! { #ty }, &params)
+  }
+
+  fn has_renames(&self) -> bool {
+    self.default.renames.iter().any(Option::is_some)
+  }
+
+  /// Whether the `default` or `transient` block has a field of that name
+  fn has_field(&self, name: &str) -> bool {
+    let transient = self
+      .transient
+      .iter()
+      .flat_map(|transient| &transient.fields);
+    self
       .default
       .fields
       .iter()
-      .zip(&self.default.types)
-      .find(|(_, ty)| ty.is_none());
+      .chain(transient)
+      .any(|field| field.unraw() == name)
+  }
 
-    if let Some((name, _)) = missing_type {
-      if self.builder.is_some() {
-        let error = syn::Error::new(
-          name.span(),
-          "Type must be specified if using a custom `builder {}` block.",
-        )
-        .to_compile_error();
+  fn has_lazy(&self) -> bool {
+    self.default.lazy.contains(&true)
+  }
 
-        return Some(error);
-      }
-    }
+  fn has_weak_mixins(&self) -> bool {
+    self.mixins.iter().any(|mixin| mixin.weak)
+  }
 
-    None
+  fn has_maps(&self) -> bool {
+    self.default.maps.iter().any(Option::is_some)
   }
 
-  fn generate_transient_parts(&self) -> (TokenStream, TokenStream, TokenStream) {
-    if let Some(transient) = &self.transient {
-      let trans_fields = &transient.fields;
-      let trans_types = &transient.types;
-      let trans_values = &transient.values;
+  /// Whether the builder is a separate struct, rather than the type itself.
+  /// This is the case when there's a builder {} block or when fields are
+  /// renamed, as create!(...) uses the factory's names for the fields, or
+  /// when fields are lazy, as the struct keeps track of the unset fields, or
+  /// when mixins are weak, as it keeps track of the applied mixins, or when
+  /// fields are mapped, as the values are only final when building.
+  /// Named factories always have one, as the type can only implement the
+  /// builder traits once.
+  fn has_builder_struct(&self) -> bool {
+    self.builder.is_some()
+      || self.has_renames()
+      || self.has_lazy()
+      || self.has_weak_mixins()
+      || self.has_maps()
+      || self.name.is_some()
+  }
 
-      (
-        quote! {
-          #( pub #trans_fields: #trans_types ),*
-        },
-        quote! {
-          #( #trans_fields: #trans_values ),*
-        },
-        quote! {
-          #(
-            #[allow(unused_variable)]
-            let #trans_fields = self.#trans_fields;
-          )*
-        },
-      )
-    } else {
-      (quote! {}, quote! {}, quote! {})
+  /// The type's path for expressions and patterns, which need a turbofish
+  /// for its arguments
+  fn expr_ty(&self) -> Path {
+    let mut ty = self.ty.clone();
+    for segment in &mut ty.segments {
+      if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+        arguments.colon2_token = Some(Default::default());
+      }
     }
+
+    ty
   }
 
-  fn generate_builder(&self) -> TokenStream {
-    let ident_builder = ident_builder(&self.ty);
+  /// The code turning the fields of the builder struct into the type
+  fn builder_body(&self) -> TokenStream {
+    if let Some(builder) = &self.builder {
+      return quote! { #builder };
+    }
 
-    let ty = &self.ty;
+    let ty = self.expr_ty();
     let fields = &self.default.fields;
-    let types = &self.default.types;
-    let values = &self.default.values;
-
-    let (transient_field_decl, transient_default_values, transient_build_group) =
-      self.generate_transient_parts();
+    let cfgs = self.default.attrs.iter().map(|attrs| cfg_attrs(attrs));
+    let struct_fields = fields
+      .iter()
+      .zip(&self.default.renames)
+      .map(|(field, rename)| rename.as_ref().unwrap_or(field));
 
-    match &self.builder {
-      None => {
-        quote! {
-            #[allow(non_camel_case_types)]
-            pub type #ident_builder = #ty;
+    quote! {
+        #ty {
+            #( #(#cfgs)* #struct_fields: #fields ),*
+        }
+    }
+  }
 
-            impl factori_imp::Default for #ident_builder {
-                fn default() -> Self {
-                    #ty {
-                        #( #fields: #values ),*
-                    }
-                }
-            }
+  /// Whether the default value of the i-th field is evaluated when building
+  /// rather than in `Default::default()`, and only if it wasn't set. This is
+  /// the case for `lazy` fields, and for `association!()`, `repeat!()`,
+  /// `has_many!()` and `create_vec!()` values when there is a builder {}
+  /// block, which lets them refer to the other fields and transient fields.
+  fn is_deferred(&self, i: usize) -> bool {
+    self.default.lazy[i] || (self.builder.is_some() && is_deferred_macro(&self.default.values[i]))
+  }
 
-            impl factori_imp::Builder for #ident_builder {
-                type Ty = #ty;
+  /// The value of a field reset by a mixin, which is its value in the
+  /// `default` or `transient` block, evaluated again
+  fn reset_value(&self, field: &Ident) -> TokenStream {
+    let is_field = |other: &Ident| other.unraw() == field.unraw();
 
-                fn build(self) -> Self::Ty {
-                    self
-                }
-            }
-        }
+    if let Some(i) = self.default.fields.iter().position(is_field) {
+      if self.is_deferred(i) {
+        return quote! { None };
       }
 
-      Some(builder) => {
-        quote! {
-            #[allow(non_camel_case_types, dead_code)]
-            pub struct #ident_builder {
-                #( pub #fields: #types ),*
-                ,
-                #transient_field_decl
-            }
-
-            impl factori_imp::Default for #ident_builder {
-                fn default() -> Self {
-                    #ident_builder {
-                        #( #fields: #values ),*
-                        ,
-                        #transient_default_values
-                    }
-                }
-            }
+      let value = &self.default.values[i];
+      return quote! { #value };
+    }
 
-            impl factori_imp::Builder for #ident_builder {
-                type Ty = #ty;
+    let transient = self.transient.as_ref().expect("checked by validate");
+    let i = transient
+      .fields
+      .iter()
+      .position(is_field)
+      .expect("checked by validate");
+    let value = transient.values[i].as_ref().expect("checked by validate");
 
-                fn build(self) -> Self::Ty {
-                    #(
-                        #[allow(unused_variable)]
-                        let #fields = self.#fields;
-                    )*
-                    #transient_build_group
+    quote! { #value }
+  }
 
-                    #builder
-                }
-            }
-        }
-      }
+  /// The transient fields without a default, which must be set by a mixin or
+  /// in create!(...)
+  fn required_transients(&self) -> Vec<&Ident> {
+    match &self.transient {
+      Some(transient) => transient
+        .fields
+        .iter()
+        .zip(&transient.values)
+        .filter(|(_, value)| value.is_none())
+        .map(|(field, _)| field)
+        .collect(),
+      None => Vec::new(),
     }
   }
 
-  fn generate_mixins(&self) -> TokenStream {
-    let ident_builder = ident_builder(&self.ty);
-    let ident_mixins_enum = ident_mixins_enum(&self.ty);
+  fn validate(&self) -> Option<TokenStream> {
+    // Without a builder {} block there'd be nothing to use the values.
+    if let (Some(transient), None) = (&self.transient, &self.builder) {
+      let error = syn::Error::new(transient.span, "transient {} requires a builder {} block")
+        .to_compile_error();
 
-    let idents_builder = &ident_builder;
-    let idents_mixins_enum = &ident_mixins_enum;
+      return Some(error);
+    }
 
-    let mixin_names: Vec<_> = self.mixins.iter().map(|mixin| &mixin.name).collect();
-    let mixin_fields: Vec<_> = self.mixins.iter().map(|mixin| &mixin.fields).collect();
-    let mixin_values: Vec<_> = self.mixins.iter().map(|mixin| &mixin.values).collect();
+    let mut known_fields = self.default.fields.clone();
+    if let Some(transient) = &self.transient {
+      known_fields.extend(transient.fields.iter().cloned());
+    }
 
-    quote! {
-        #[allow(non_camel_case_types)]
-        pub enum #ident_mixins_enum {
-            #( #mixin_names ),*
-        }
+    for (i, mixin) in self.mixins.iter().enumerate() {
+      if self.mixins[..i]
+        .iter()
+        .any(|other| other.name == mixin.name)
+      {
+        let error = syn::Error::new(
+          mixin.name.span(),
+          format!("mixin `{}` is defined twice", mixin.name.unraw()),
+        )
+        .to_compile_error();
 
-        impl factori_imp::Mixin<#ident_builder> for #ident_mixins_enum {
-            fn default(self) -> #ident_builder {
-                self.extend(factori_imp::Default::default())
-            }
+        return Some(error);
+      }
 
-            #[allow(unused_variable)]
-            fn extend(self, other: #ident_builder) -> #ident_builder {
-                match self {
-                    #(
-                        #idents_mixins_enum::#mixin_names => {
-                            #idents_builder {
-                                #(
-                                    #mixin_fields: #mixin_values
-                                ),* ,
-                                .. other
-                            }
-                        }
-                    ),*
-                }
-            }
+      for (j, alias) in mixin.aliases.iter().enumerate() {
+        let message = if self
+          .mixins
+          .iter()
+          .any(|other| other.name.unraw() == alias.unraw())
+        {
+          Some("is also the name of a mixin")
+        } else if mixin.aliases[..j]
+          .iter()
+          .chain(self.mixins[..i].iter().flat_map(|other| &other.aliases))
+          .any(|other| other.unraw() == alias.unraw())
+        {
+          Some("is defined twice")
+        } else {
+          None
+        };
+
+        if let Some(message) = message {
+          let error = syn::Error::new(
+            alias.span(),
+            format!("mixin alias `{}` {}", alias.unraw(), message),
+          )
+          .to_compile_error();
+
+          return Some(error);
         }
-    }
-  }
+      }
 
-  fn into_token_stream(self) -> TokenStream {
-    let builder = self.generate_builder();
-    let mixins = self.generate_mixins();
+      let unknown_field = mixin.fields.iter().find(|field| {
+        !known_fields
+          .iter()
+          .any(|known| known.unraw() == field.unraw())
+      });
 
-    quote! {
-        #builder
-        #mixins
-    }
-  }
-}
+      if let Some(field) = unknown_field {
+        let error = syn::Error::new(
+          field.span(),
+          format!(
+            "`{}` is not a field of the default {{}} or transient {{}} block, so mixin `{}` can't set it",
+            field.unraw(),
+            mixin.name.unraw()
+          ),
+        )
+        .to_compile_error();
 
-struct MultipleDefinition {
-  definitions: Vec<Definition>,
-}
+        return Some(error);
+      }
 
-impl Parse for MultipleDefinition {
-  fn parse(input: ParseStream) -> Result<Self> {
-    let mut definitions = Vec::new();
+      let reset_required = mixin
+        .fields
+        .iter()
+        .zip(&mixin.values)
+        .filter(|(_, value)| value.is_none())
+        .map(|(field, _)| field)
+        .find(|field| {
+          self
+            .required_transients()
+            .iter()
+            .any(|required| required.unraw() == field.unraw())
+        });
+
+      if let Some(field) = reset_required {
+        let error = syn::Error::new(
+          field.span(),
+          format!(
+            "`{}` is a required transient field, so mixin `{}` can't reset it",
+            field.unraw(),
+            mixin.name.unraw()
+          ),
+        )
+        .to_compile_error();
 
-    loop {
-      if input.is_empty() {
-        break;
+        return Some(error);
       }
-      definitions.push(input.parse()?);
     }
 
-    Ok(Self { definitions })
-  }
-}
+    // The consts are on the type, which the other factories of the type would
+    // define too.
+    if let (Some(meta), Some(_)) = (&self.meta, &self.name) {
+      let error =
+        syn::Error::new(meta.span, "meta {} isn't supported in factories!").to_compile_error();
 
-pub fn define_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-  let MultipleDefinition { definitions } = parse_macro_input!(input);
+      return Some(error);
+    }
 
+    // The errors of validation are `FactoriError`s, which neither a
+    // try_builder {} block's own errors nor an async build can return.
+    if let Some((validate, _)) = &self.validate {
+      let conflict = if self.builder_error.is_some() {
+        Some(
+          "validate {} can't be used with a try_builder {} block, which can return its own errors",
+        )
+      } else if self.builder_async.is_some() {
+        Some("validate {} can't be used with a builder async {} block")
+      } else {
+        None
+      };
+
+      if let Some(message) = conflict {
+        let error = syn::Error::new(validate.span(), message).to_compile_error();
+
+        return Some(error);
+      }
+    }
+
+    // The bindings are only used by the builder {} block.
+    if let (Some((computed, _)), None) = (&self.computed, &self.builder) {
+      let error = syn::Error::new(
+        computed.span(),
+        "computed {} requires a `builder {}` block, which uses its bindings",
+      )
+      .to_compile_error();
+
+      return Some(error);
+    }
+
+    // Without a builder struct, the builder is the type itself.
+    if let (Some((builder_derive, _)), false) = (&self.builder_derive, self.has_builder_struct()) {
+      let error = syn::Error::new(
+        builder_derive.span(),
+        "`builder_derive` requires a `builder {}` block, the builder is the type itself otherwise",
+      )
+      .to_compile_error();
+
+      return Some(error);
+    }
+
+    if self.builder.is_some() {
+      if let Some(rename) = self.default.renames.iter().flatten().next() {
+        let error = syn::Error::new(
+          rename.span(),
+          "Fields can't be renamed with `as` if using a custom `builder {}` block.",
+        )
+        .to_compile_error();
+
+        return Some(error);
+      }
+    }
+
+    let missing_type = self
+      .default
+      .fields
+      .iter()
+      .zip(&self.default.types)
+      .find(|(_, ty)| ty.is_none());
+
+    if let Some((name, _)) = missing_type {
+      let message = if self.builder.is_some() {
+        Some("Type must be specified if using a custom `builder {}` block.")
+      } else if self.name.is_some() {
+        Some("Type must be specified for the fields of a factory in `factories!`.")
+      } else if self.has_lazy() {
+        Some("Type must be specified if a field is `lazy` or partial.")
+      } else if self.has_maps() {
+        Some("Type must be specified if a field has a `map`.")
+      } else if self.has_renames() {
+        Some("Type must be specified if renaming fields with `as`.")
+      } else if self.has_weak_mixins() {
+        Some("Type must be specified if a mixin is `weak`.")
+      } else {
+        None
+      };
+
+      if let Some(message) = message {
+        let error = syn::Error::new(name.span(), message).to_compile_error();
+
+        return Some(error);
+      }
+    }
+
+    None
+  }
+
+  fn generate_transient_parts(&self) -> (TokenStream, TokenStream, TokenStream) {
+    if let Some(transient) = &self.transient {
+      let mut decls = Vec::new();
+      let mut defaults = Vec::new();
+      let mut bindings = Vec::new();
+
+      // Required fields are stored as an Option, as they have no default.
+      for (i, field) in transient.fields.iter().enumerate() {
+        let attrs = &transient.attrs[i];
+        let cfgs = cfg_attrs(attrs);
+        let ty = &transient.types[i];
+
+        match &transient.values[i] {
+          Some(value) => {
+            decls.push(quote! { #(#attrs)* pub #field: #ty, });
+            defaults.push(quote! { #(#cfgs)* #field: #value, });
+            bindings.push(quote! {
+                #(#cfgs)*
+                #[allow(unused_variables)]
+                let #field = self.#field;
+            });
+          }
+          None => {
+            let message = required_message(field);
+            decls.push(quote! { #(#attrs)* pub #field: Option<#ty>, });
+            defaults.push(quote! { #(#cfgs)* #field: None, });
+            bindings.push(quote! {
+                #(#cfgs)*
+                #[allow(unused_variables)]
+                let #field = match self.#field {
+                    Some(#field) => #field,
+                    None => panic!(#message),
+                };
+            });
+          }
+        }
+      }
+
+      (
+        quote! { #( #decls )* },
+        quote! { #( #defaults )* },
+        quote! { #( #bindings )* },
+      )
+    } else {
+      (quote! {}, quote! {}, quote! {})
+    }
+  }
+
+  /// Generates a check that the `default` block has every field of the
+  /// struct, with a pattern destructuring it without `..`. Without a builder
+  /// block, the struct expression built from the `default` block already
+  /// requires every field.
+  fn generate_exhaustive(&self) -> TokenStream {
+    let exhaustive = match &self.exhaustive {
+      Some(exhaustive) if self.builder.is_some() => exhaustive,
+      _ => return quote! {},
+    };
+
+    let ty = &self.ty;
+    let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+    let expr_ty = self.expr_ty();
+    let cfgs = self.default.attrs.iter().map(|attrs| cfg_attrs(attrs));
+    let struct_fields = self
+      .default
+      .fields
+      .iter()
+      .zip(&self.default.renames)
+      .map(|(field, rename)| rename.as_ref().unwrap_or(field));
+
+    // The pattern is spanned to `exhaustive` for the error about a missing
+    // field to point at it, and to check the fields from the caller's crate.
+    let pattern = quote_spanned! {exhaustive.span()=>
+        #expr_ty { #( #(#cfgs)* #struct_fields: _ ),* }
+    };
+
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn exhaustive #impl_generics (value: #ty) #where_clause {
+                let #pattern = value;
+            }
+        };
+    }
+  }
+
+  /// Generates a marker trait for the factory, which create!() refers to
+  /// before the other generated types. When the factory isn't in scope, the
+  /// first error is then about the factory rather than one of its types.
+  fn generate_factory(&self) -> TokenStream {
+    let ident_factory = ident_factory(&self.prefix, &self.ty_ident());
+    let vis = &self.vis;
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        #vis trait #ident_factory {}
+    }
+  }
+
+  /// The consts of the meta {} block, e.g. `FACTORI_META_CATEGORY` for
+  /// `category`. Integers without a suffix are `i64`.
+  fn generate_meta(&self) -> TokenStream {
+    let meta = match &self.meta {
+      Some(meta) => meta,
+      None => return quote! {},
+    };
+
+    let ty = &self.ty;
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+    let consts = meta.keys.iter().zip(&meta.values).map(|(key, value)| {
+      let name = format!("FACTORI_META_{}", key.unraw().to_string().to_uppercase());
+      let name = Ident::new(&name, key.span());
+      let const_ty = match value {
+        Lit::Int(int) if !int.suffix().is_empty() => {
+          let suffix = Ident::new(int.suffix(), int.span());
+          quote! { #suffix }
+        }
+        Lit::Int(_) => quote! { i64 },
+        _ => quote! { &'static str },
+      };
+
+      quote! { pub const #name: #const_ty = #value; }
+    });
+
+    quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #ty {
+            #( #consts )*
+        }
+    }
+  }
+
+  /// Generates a function per field that create!(...) and mixins pass the
+  /// values through. This converts the values into how they are stored in the
+  /// builder (e.g. deferred fields are stored as an Option) without create!
+  /// having to know about it.
+  fn generate_fields(&self) -> TokenStream {
+    let ident_fields = ident_fields(&self.prefix, &self.ty_ident());
+    let vis = &self.vis;
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+
+    // Functions for types using the type parameter have it as well.
+    let generics_of = |ty: &Type| {
+      if self.is_generic_type(ty) {
+        quote! { #impl_generics }
+      } else {
+        quote! {}
+      }
+    };
+
+    // The fields whose value isn't known until building, which `default`
+    // can't refer to, with the error if it does.
+    let mut unknown_defaults = Vec::new();
+    let mut functions = Vec::new();
+    for (i, field) in self.default.fields.iter().enumerate() {
+      let cfgs = cfg_attrs(&self.default.attrs[i]);
+      let default_fn = field_default_fn(field);
+
+      let function = match &self.default.types[i] {
+        Some(ty) if self.has_builder_struct() => {
+          let generics = generics_of(ty);
+          if self.is_deferred(i) {
+            let reason = if self.default.lazy[i] {
+              "is `lazy` or partial"
+            } else {
+              "is evaluated when building, as it uses `association!()`, `repeat!()`, `has_many!()` or `create_vec!()`"
+            };
+            let message = format!(
+              "`{}` {}, so `default` can't refer to its value in `create!`",
+              field.unraw(),
+              reason
+            );
+            // Fields behind #[cfg] aren't listed, as they may not exist.
+            if cfgs.is_empty() {
+              let name = field.unraw().to_string();
+              unknown_defaults.push(quote! { (#name, #message) });
+            }
+
+            quote! {
+                pub fn #field #generics(value: #ty) -> Option<#ty> {
+                    Some(value)
+                }
+
+                pub fn #default_fn #generics(value: Option<#ty>) -> #ty {
+                    value.expect(#message)
+                }
+            }
+          } else {
+            quote! {
+                pub fn #field #generics(value: #ty) -> #ty {
+                    value
+                }
+
+                pub fn #default_fn #generics(value: #ty) -> #ty {
+                    value
+                }
+            }
+          }
+        }
+        _ => {
+          quote! {
+              pub fn #field<T>(value: T) -> T {
+                  value
+              }
+
+              pub fn #default_fn<T>(value: T) -> T {
+                  value
+              }
+          }
+        }
+      };
+
+      functions.push(quote! {
+          #(#cfgs)*
+          #function
+      });
+    }
+
+    if let Some(transient) = &self.transient {
+      for (i, field) in transient.fields.iter().enumerate() {
+        let cfgs = cfg_attrs(&transient.attrs[i]);
+        let ty = &transient.types[i];
+        let generics = generics_of(ty);
+        let default_fn = field_default_fn(field);
+
+        let function = if transient.values[i].is_some() {
+          quote! {
+              pub fn #field #generics(value: #ty) -> #ty {
+                  value
+              }
+
+              pub fn #default_fn #generics(value: #ty) -> #ty {
+                  value
+              }
+          }
+        } else {
+          let message = format!(
+            "`{}` is a required transient field, so `default` can't refer to its value in `create!`",
+            field.unraw()
+          );
+          if cfgs.is_empty() {
+            let name = field.unraw().to_string();
+            unknown_defaults.push(quote! { (#name, #message) });
+          }
+
+          quote! {
+              pub fn #field #generics(value: #ty) -> Option<#ty> {
+                  Some(value)
+              }
+
+              pub fn #default_fn #generics(value: Option<#ty>) -> #ty {
+                  value.expect(#message)
+              }
+          }
+        };
+
+        functions.push(quote! {
+            #(#cfgs)*
+            #function
+        });
+      }
+    }
+
+    let mut all_fields: Vec<_> = self
+      .default
+      .attrs
+      .iter()
+      .zip(&self.default.fields)
+      .collect();
+    if let Some(transient) = &self.transient {
+      all_fields.extend(transient.attrs.iter().zip(&transient.fields));
+    }
+    // Fields behind #[cfg] aren't listed, as they may not exist.
+    let names = all_fields
+      .into_iter()
+      .filter(|(attrs, _)| cfg_attrs(attrs).is_empty())
+      .map(|(_, field)| field.unraw().to_string());
+
+    let mut required = Vec::new();
+    if let Some(transient) = &self.transient {
+      for (i, field) in transient.fields.iter().enumerate() {
+        if transient.values[i].is_none() && cfg_attrs(&transient.attrs[i]).is_empty() {
+          let name = field.unraw().to_string();
+          let message = required_message(field);
+          required.push(quote! { (#name, #message) });
+        }
+      }
+    }
+
+    let mut skipped = Vec::new();
+    for (i, field) in self.default.fields.iter().enumerate() {
+      if self.default.skip[i] && cfg_attrs(&self.default.attrs[i]).is_empty() {
+        let name = field.unraw().to_string();
+        let message = format!(
+          "`{}` is marked `#[factori(skip)]` in the factory, so it can't be overridden",
+          field.unraw()
+        );
+        skipped.push(quote! { (#name, #message) });
+      }
+    }
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        #vis struct #ident_fields;
+
+        #[allow(non_snake_case)]
+        impl #ident_fields {
+            pub const FIELDS: &'static [&'static str] = &[ #( #names ),* ];
+
+            /// The required transient fields, with the error if they aren't set
+            pub const REQUIRED: &'static [(&'static str, &'static str)] = &[ #( #required ),* ];
+
+            /// The fields marked `#[factori(skip)]`, with the error if they're overridden
+            pub const SKIPPED: &'static [(&'static str, &'static str)] = &[ #( #skipped ),* ];
+
+            /// The fields whose value isn't known before building, with the
+            /// error if an override refers to it as `default`
+            pub const UNKNOWN_DEFAULTS: &'static [(&'static str, &'static str)] = &[ #( #unknown_defaults ),* ];
+
+            #(
+                #[inline]
+                #functions
+            )*
+        }
+    }
+  }
+
+  fn generate_builder(&self) -> TokenStream {
+    let krate = &self.krate;
+    let vis = &self.vis;
+    let ident_builder = ident_builder(&self.prefix, &self.ty_ident());
+    let builder_ty = self.builder_ty();
+    let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+
+    let ty = &self.ty;
+    let attrs = &self.default.attrs;
+    let fields = &self.default.fields;
+    let values = &self.default.values;
+
+    let (transient_field_decl, transient_default_values, transient_build_group) =
+      self.generate_transient_parts();
+
+    if !self.has_builder_struct() {
+      let build_impls = self.generate_build_impls(quote! { self });
+      let cfgs = attrs.iter().map(|attrs| cfg_attrs(attrs));
+
+      // The builder is the type itself, which has to be `Clone` already. The
+      // check is spanned to `clone_builder` for the error to point at it.
+      let clone_check = match &self.clone_builder {
+        Some(clone_builder) if self.generics.params.is_empty() => {
+          quote_spanned! {clone_builder.span()=>
+              const _: fn() = || {
+                  fn clone_builder<T: ::std::clone::Clone>() {}
+                  clone_builder::<#ty>();
+              };
+          }
+        }
+        _ => quote! {},
+      };
+
+      return quote! {
+          #[allow(non_camel_case_types)]
+          #vis type #ident_builder #ty_generics = #ty;
+
+          #clone_check
+
+          impl #impl_generics #krate::Default for #builder_ty {
+              fn default() -> Self {
+                  #ident_builder {
+                      #( #(#cfgs)* #fields: #values ),*
+                  }
+              }
+          }
+
+          #build_impls
+      };
+    }
+
+    let mut field_decls = Vec::new();
+    let mut field_defaults = Vec::new();
+    let mut field_bindings = Vec::new();
+    let mut deferred_bindings = Vec::new();
+    let mut setters = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+      let attrs = &attrs[i];
+      let ty = &self.default.types[i];
+      let value = &values[i];
+      let cfgs = cfg_attrs(attrs);
+
+      if self.is_deferred(i) {
+        setters.push(quote! {
+            #(#cfgs)*
+            pub fn #field(mut self, value: #ty) -> Self {
+                self.#field = Some(value);
+                self
+            }
+        });
+        field_decls.push(quote! { #(#attrs)* pub #field: Option<#ty> });
+        field_defaults.push(quote! { #(#cfgs)* #field: None });
+        deferred_bindings.push(quote! {
+            #(#cfgs)*
+            #[allow(unused_variables)]
+            let #field = match self.#field {
+                Some(#field) => #field,
+                None => #value,
+            };
+        });
+      } else {
+        setters.push(quote! {
+            #(#cfgs)*
+            pub fn #field(mut self, value: #ty) -> Self {
+                self.#field = value;
+                self
+            }
+        });
+        field_decls.push(quote! { #(#attrs)* pub #field: #ty });
+        field_defaults.push(quote! { #(#cfgs)* #field: #value });
+        field_bindings.push(quote! {
+            #(#cfgs)*
+            #[allow(unused_variables)]
+            let #field = self.#field;
+        });
+      }
+    }
+
+    if let Some(transient) = &self.transient {
+      for (i, field) in transient.fields.iter().enumerate() {
+        let cfgs = cfg_attrs(&transient.attrs[i]);
+        let ty = &transient.types[i];
+        let value = match transient.values[i] {
+          Some(_) => quote! { value },
+          None => quote! { Some(value) },
+        };
+
+        setters.push(quote! {
+            #(#cfgs)*
+            pub fn #field(mut self, value: #ty) -> Self {
+                self.#field = #value;
+                self
+            }
+        });
+      }
+    }
+
+    // Builds without importing `Builder`, unless a setter has the name.
+    if !self.has_field("build") {
+      setters.push(match &self.builder_async {
+        Some(_) => quote! {
+            pub fn build(self) -> impl ::std::future::Future<Output = #ty> {
+                #krate::AsyncBuilder::build(self)
+            }
+        },
+        None => quote! {
+            #[track_caller]
+            pub fn build(self) -> #ty {
+                #krate::Builder::build(self)
+            }
+        },
+      });
+    }
+
+    let builder = self.builder_body();
+    let map_bindings = self.generate_map_bindings();
+    let computed = self.computed.iter().flat_map(|(_, stmts)| stmts);
+    // Spanned to the builder {} block so that it's in scope of its code.
+    let mixins_binding = match &self.builder {
+      Some(builder) => Ident::new("__mixins", builder.span()),
+      None => Ident::new("__mixins", Span::call_site()),
+    };
+
+    let build_impls = self.generate_build_impls(quote! {
+        {
+            let __factori_mixins = self.__factori_mixins;
+            let #mixins_binding: &[&'static str] = &__factori_mixins;
+            #( #field_bindings )*
+            #transient_build_group
+            #( #deferred_bindings )*
+            #map_bindings
+            #( #computed )*
+
+            #builder
+        }
+    });
+
+    // Lets the builder be used where `impl Into<Ty>` is expected.
+    let conversion = match &self.builder_error {
+      _ if self.builder_async.is_some() => quote! {},
+      Some(error) => quote! {
+          impl #impl_generics ::std::convert::TryFrom<#builder_ty> for #ty {
+              type Error = #error;
+
+              fn try_from(builder: #builder_ty) -> Result<Self, Self::Error> {
+                  #krate::TryBuilder::try_build(builder)
+              }
+          }
+      },
+      None => quote! {
+          impl #impl_generics ::std::convert::From<#builder_ty> for #ty {
+              fn from(builder: #builder_ty) -> Self {
+                  #krate::Builder::build(builder)
+              }
+          }
+      },
+    };
+
+    // The type parameter might not be used by any of the fields.
+    let (generics_decl, generics_default) = if self.generics.params.is_empty() {
+      (quote! {}, quote! {})
+    } else {
+      (
+        quote! {
+            #[doc(hidden)]
+            pub __factori_ty: ::std::marker::PhantomData<#ty>,
+        },
+        quote! { __factori_ty: ::std::marker::PhantomData, },
+      )
+    };
+
+    let derive_clone = match &self.clone_builder {
+      Some(_) => quote! { #[derive(Clone)] },
+      None => quote! {},
+    };
+    let derive_traits = match &self.builder_derive {
+      Some((_, traits)) => quote! { #[derive(#( #traits ),*)] },
+      None => quote! {},
+    };
+
+    quote! {
+        #[allow(non_camel_case_types, dead_code)]
+        #derive_clone
+        #derive_traits
+        #[must_use = "builders do nothing unless built with `factori_imp::Builder::build` or `create!`"]
+        #vis struct #ident_builder #impl_generics {
+            #( #field_decls, )*
+            #transient_field_decl
+            #[doc(hidden)]
+            pub __factori_mixins: Vec<&'static str>,
+            #generics_decl
+        }
+
+        // Setters to change the builder's fields without the macros.
+        #[allow(dead_code)]
+        impl #impl_generics #builder_ty {
+            #( #setters )*
+        }
+
+        impl #impl_generics #krate::Default for #builder_ty {
+            fn default() -> Self {
+                #ident_builder {
+                    #( #field_defaults, )*
+                    #transient_default_values
+                    __factori_mixins: Vec::new(),
+                    #generics_default
+                }
+            }
+        }
+
+        #build_impls
+
+        #conversion
+    }
+  }
+
+  /// The bindings applying the `map` closures of the fields to their final
+  /// values. The closures are passed to a function for their argument's type
+  /// to be inferred from the field.
+  fn generate_map_bindings(&self) -> TokenStream {
+    if !self.has_maps() {
+      return quote! {};
+    }
+
+    let bindings = self
+      .default
+      .fields
+      .iter()
+      .zip(&self.default.attrs)
+      .zip(&self.default.maps)
+      .filter_map(|((field, attrs), map)| {
+        let map = map.as_ref()?;
+        let cfgs = cfg_attrs(attrs);
+        Some(quote! {
+            #(#cfgs)*
+            #[allow(unused_variables)]
+            let #field = __factori_map(#field, #map);
+        })
+      });
+
+    quote! {
+        fn __factori_map<T>(value: T, map: impl ::std::ops::FnOnce(T) -> T) -> T {
+            map(value)
+        }
+        #( #bindings )*
+    }
+  }
+
+  /// The implementations of `Builder` and `TryBuilder`, with the body of the
+  /// one doing the building
+  fn generate_build_impls(&self, body: TokenStream) -> TokenStream {
+    let krate = &self.krate;
+    let builder_ty = self.builder_ty();
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+    let ty = &self.ty;
+
+    if self.builder_async.is_some() {
+      return quote! {
+          impl #impl_generics #krate::AsyncBuilder for #builder_ty {
+              type Ty = #ty;
+
+              fn build(self) -> impl ::std::future::Future<Output = Self::Ty> {
+                  async move { #body }
+              }
+          }
+      };
+    }
+
+    if let Some((_, validate)) = &self.validate {
+      let last = self.ty.segments.last().expect("paths have a segment");
+      let factory = last.ident.unraw().to_string();
+      return quote! {
+          impl #impl_generics #krate::Builder for #builder_ty {
+              type Ty = #ty;
+
+              #[track_caller]
+              fn build(self) -> Self::Ty {
+                  match #krate::TryBuilder::try_build(self) {
+                      Ok(value) => value,
+                      Err(error) => panic!("{}", error),
+                  }
+              }
+          }
+
+          impl #impl_generics #krate::TryBuilder for #builder_ty {
+              type Error = #krate::FactoriError;
+
+              #[track_caller]
+              fn try_build(self) -> Result<Self::Ty, Self::Error> {
+                  // The closure's argument is inferred from the function.
+                  fn __factori_validate<B>(
+                      builder: &B,
+                      validate: impl ::std::ops::FnOnce(&B) -> Result<(), String>,
+                  ) -> Result<(), String> {
+                      validate(builder)
+                  }
+
+                  if let Err(message) = __factori_validate(&self, #validate) {
+                      return Err(#krate::FactoriError::new(#factory, message));
+                  }
+
+                  Ok(#body)
+              }
+          }
+      };
+    }
+
+    let error = match &self.builder_error {
+      Some(error) => error,
+      None => {
+        return quote! {
+            impl #impl_generics #krate::Builder for #builder_ty {
+                type Ty = #ty;
+
+                #[track_caller]
+                fn build(self) -> Self::Ty {
+                    #body
+                }
+            }
+
+            impl #impl_generics #krate::TryBuilder for #builder_ty {
+                type Error = ::std::convert::Infallible;
+
+                #[track_caller]
+                fn try_build(self) -> Result<Self::Ty, Self::Error> {
+                    Ok(#krate::Builder::build(self))
+                }
+            }
+        };
+      }
+    };
+
+    let message = format!(
+      "the builder of `{}` failed: {{:?}}",
+      self.ty.segments.last().expect("paths have a segment").ident
+    );
+
+    quote! {
+        impl #impl_generics #krate::Builder for #builder_ty {
+            type Ty = #ty;
+
+            #[track_caller]
+            fn build(self) -> Self::Ty {
+                match #krate::TryBuilder::try_build(self) {
+                    Ok(value) => value,
+                    Err(error) => panic!(#message, error),
+                }
+            }
+        }
+
+        impl #impl_generics #krate::TryBuilder for #builder_ty {
+            type Error = #error;
+
+            #[track_caller]
+            fn try_build(self) -> Result<Self::Ty, Self::Error> {
+                Ok(#body)
+            }
+        }
+    }
+  }
+
+  fn generate_mixins(&self) -> TokenStream {
+    let krate = &self.krate;
+    let vis = &self.vis;
+    let ident_builder = ident_builder(&self.prefix, &self.ty_ident());
+    let builder_ty = self.builder_ty();
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+    let ident_mixins_enum = ident_mixins_enum(&self.prefix, &self.ty_ident());
+    let ident_fields = ident_fields(&self.prefix, &self.ty_ident());
+
+    let idents_mixins_enum = &ident_mixins_enum;
+
+    let mixin_strs: Vec<_> = self
+      .mixins
+      .iter()
+      .map(|mixin| mixin.name.unraw().to_string())
+      .collect();
+
+    // Parameterized mixins are tuple variants, which can't be made from a name.
+    let mixin_variants = self.mixins.iter().map(|mixin| {
+      let name = &mixin.name;
+      let types = mixin.params.iter().map(|(_, ty)| ty);
+      if mixin.params.is_empty() {
+        quote! { #name }
+      } else {
+        quote! { #name( #( #types ),* ) }
+      }
+    });
+    let mixin_patterns: Vec<_> = self
+      .mixins
+      .iter()
+      .map(|mixin| {
+        let name = &mixin.name;
+        if mixin.params.is_empty() {
+          quote! { #ident_mixins_enum::#name }
+        } else {
+          quote! { #ident_mixins_enum::#name(..) }
+        }
+      })
+      .collect();
+    let (named_mixins, named_strs): (Vec<_>, Vec<_>) = self
+      .mixins
+      .iter()
+      .zip(&mixin_strs)
+      .filter(|(mixin, _)| mixin.params.is_empty())
+      .map(|(mixin, name_str)| (&mixin.name, name_str))
+      .unzip();
+    let mixin_fields_consts: Vec<_> = self
+      .mixins
+      .iter()
+      .map(|mixin| mixin_fields_const(&mixin.name))
+      .collect();
+    let mixin_groups: Vec<_> = self
+      .mixins
+      .iter()
+      .map(|mixin| match &mixin.group {
+        Some(group) => {
+          let group = group.unraw().to_string();
+          quote! { Some(#group) }
+        }
+        None => quote! { None },
+      })
+      .collect();
+
+    let mixin_fields: Vec<Vec<_>> = self
+      .mixins
+      .iter()
+      .map(|mixin| {
+        mixin
+          .fields
+          .iter()
+          .map(|field| field.unraw().to_string())
+          .collect()
+      })
+      .collect();
+
+    // The consts for the checks of create!(...) are also defined for the
+    // aliases, which are consts and functions making the mixin's variant.
+    let mut check_consts = Vec::new();
+    let mut aliases = Vec::new();
+    for (((mixin, name_str), fields), group) in self
+      .mixins
+      .iter()
+      .zip(&mixin_strs)
+      .zip(&mixin_fields)
+      .zip(&mixin_groups)
+    {
+      let name = &mixin.name;
+      for ident in std::iter::once(name).chain(&mixin.aliases) {
+        let fields_const = mixin_fields_const(ident);
+        let group_const = mixin_group_const(ident);
+        let name_const = mixin_name_const(ident);
+        check_consts.push(quote! {
+            /// The fields set by the mixin, for the checks of create!(...)
+            pub const #fields_const: &'static [&'static str] = &[ #( #fields ),* ];
+            /// The group of the mixin, for the checks of create!(...)
+            pub const #group_const: Option<&'static str> = #group;
+            /// The name of the mixin, for the checks of create!(...)
+            pub const #name_const: &'static str = #name_str;
+        });
+      }
+
+      for alias in &mixin.aliases {
+        if mixin.params.is_empty() {
+          aliases.push(quote! {
+              pub const #alias: Self = #ident_mixins_enum::#name;
+          });
+        } else {
+          let params: Vec<_> = mixin.params.iter().map(|(param, _)| param).collect();
+          let types = mixin.params.iter().map(|(_, ty)| ty);
+          aliases.push(quote! {
+              pub fn #alias( #( #params: #types ),* ) -> Self {
+                  #ident_mixins_enum::#name( #( #params ),* )
+              }
+          });
+        }
+      }
+    }
+    let (alias_mixins, alias_strs): (Vec<_>, Vec<_>) = self
+      .mixins
+      .iter()
+      .filter(|mixin| mixin.params.is_empty())
+      .flat_map(|mixin| {
+        mixin
+          .aliases
+          .iter()
+          .map(move |alias| (&mixin.name, alias.unraw().to_string()))
+      })
+      .unzip();
+
+    let mut mixin_arms = Vec::new();
+    for (mixin, name_str) in self.mixins.iter().zip(&mixin_strs) {
+      let name = &mixin.name;
+      let cfgs = mixin.attrs.iter().map(|attrs| cfg_attrs(attrs));
+      let fields = &mixin.fields;
+      let values = fields.iter().zip(&mixin.values).map(|(field, value)| {
+        let value = match value {
+          Some(value) => quote! { #ident_fields::#field(#value) },
+          None => self.reset_value(field),
+        };
+
+        // Weak mixins keep the values set by the mixins applied before them.
+        if mixin.weak {
+          let field_str = field.unraw().to_string();
+          quote! {
+              if #ident_mixins_enum::is_set(&other.__factori_mixins, #field_str) {
+                  other.#field
+              } else {
+                  #value
+              }
+          }
+        } else {
+          value
+        }
+      });
+
+      // Builder structs keep track of the applied mixins for the builder {} block.
+      let applied_mixins = if self.has_builder_struct() {
+        quote! {
+            __factori_mixins: {
+                let mut mixins = other.__factori_mixins;
+                mixins.push(#name_str);
+                mixins
+            },
+        }
+      } else {
+        quote! {}
+      };
+
+      let params = mixin.params.iter().map(|(param, _)| param);
+      let pattern = if mixin.params.is_empty() {
+        quote! { #ident_mixins_enum::#name }
+      } else {
+        quote! { #ident_mixins_enum::#name( #( #params ),* ) }
+      };
+
+      mixin_arms.push(quote! {
+          #pattern => {
+              #[allow(clippy::needless_update)]
+              #ident_builder {
+                  #(
+                      #(#cfgs)*
+                      #fields: #values,
+                  )*
+                  #applied_mixins
+                  .. other
+              }
+          }
+      });
+    }
+
+    let derive_traits = if self.mixins_derive.is_empty() {
+      quote! {}
+    } else {
+      let traits = &self.mixins_derive;
+      quote! { #[derive(#( #traits ),*)] }
+    };
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        #derive_traits
+        #vis enum #ident_mixins_enum {
+            #( #mixin_variants ),*
+        }
+
+        #[allow(non_upper_case_globals)]
+        impl #ident_mixins_enum {
+            /// The mixins which can be applied by name
+            pub const NAMES: &'static [&'static str] = &[ #( #named_strs ),* ];
+
+            #( #check_consts )*
+
+            #( #aliases )*
+
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    #( #mixin_patterns => #mixin_strs ),*
+                }
+            }
+
+            pub fn group(&self) -> Option<&'static str> {
+                match *self {
+                    #( #mixin_patterns => #mixin_groups ),*
+                }
+            }
+
+            /// Whether one of the given mixins sets the field, for weak mixins
+            pub fn is_set(mixins: &[&str], field: &str) -> bool {
+                mixins.iter().any(|mixin| {
+                    let fields: &[&str] = match *mixin {
+                        #( #mixin_strs => #idents_mixins_enum::#mixin_fields_consts, )*
+                        _ => &[],
+                    };
+                    fields.contains(&field)
+                })
+            }
+
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #( #named_strs => Some(#idents_mixins_enum::#named_mixins), )*
+                    #( #alias_strs => Some(#idents_mixins_enum::#alias_mixins), )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl ::std::fmt::Debug for #ident_mixins_enum {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl #impl_generics #krate::Mixin<#builder_ty> for #ident_mixins_enum {
+            fn default(self) -> #builder_ty {
+                self.extend(#krate::Default::default())
+            }
+
+            #[allow(unused_variables)]
+            fn extend(self, other: #builder_ty) -> #builder_ty {
+                match self {
+                    #( #mixin_arms ),*
+                }
+            }
+        }
+    }
+  }
+
+  fn generate_factori_default(&self) -> TokenStream {
+    let krate = &self.krate;
+    let builder_ty = self.builder_ty();
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+    let ty = &self.ty;
+
+    quote! {
+        impl #impl_generics #krate::FactoriDefault<#builder_ty> for #ty {
+            fn factori_default() -> Self {
+                #krate::Builder::build(
+                    <#builder_ty as #krate::Default>::default()
+                )
+            }
+        }
+    }
+  }
+
+  fn generate_factori_builder(&self) -> TokenStream {
+    let krate = &self.krate;
+    let builder_ty = self.builder_ty();
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+    let ty = &self.ty;
+
+    quote! {
+        impl #impl_generics #krate::FactoriBuilder<#builder_ty> for #ty {
+            fn factori_builder() -> #builder_ty {
+                <#builder_ty as #krate::Default>::default()
+            }
+        }
+    }
+  }
+
+  fn generate_factori_mixins(&self) -> TokenStream {
+    let krate = &self.krate;
+    let builder_ty = self.builder_ty();
+    let (impl_generics, _, _) = self.generics.split_for_impl();
+    let ident_mixins_enum = ident_mixins_enum(&self.prefix, &self.ty_ident());
+    let ty = &self.ty;
+
+    quote! {
+        impl #impl_generics #krate::FactoriMixins<#builder_ty> for #ty {
+            fn factori_mixins() -> &'static [&'static str] {
+                #ident_mixins_enum::NAMES
+            }
+
+            fn factori_with_mixins(names: &[&str]) -> Option<Self> {
+                let mut builder = <#builder_ty as #krate::Default>::default();
+                // The group and name of the grouped mixins applied so far.
+                let mut groups: Vec<(&'static str, &'static str)> = Vec::new();
+                for name in names {
+                    let mixin = #ident_mixins_enum::from_name(name)?;
+                    if let Some(group) = mixin.group() {
+                        // An alias is the same mixin as its name.
+                        let name = mixin.name();
+                        if groups.iter().any(|(other, other_name)| *other == group && *other_name != name) {
+                            return None;
+                        }
+                        groups.push((group, name));
+                    }
+                    builder = #krate::Mixin::extend(mixin, builder);
+                }
+                Some(#krate::Builder::build(builder))
+            }
+        }
+    }
+  }
+
+  fn into_token_stream(self) -> TokenStream {
+    let factory = self.generate_factory();
+    let meta = self.generate_meta();
+    let exhaustive = self.generate_exhaustive();
+    let fields = self.generate_fields();
+    let builder = self.generate_builder();
+    let mixins = self.generate_mixins();
+
+    // A named factory is picked by its name rather than inferred from the type,
+    // which would be ambiguous with the type's other factories.
+    if self.name.is_some() {
+      return quote! {
+          #factory
+          #exhaustive
+          #fields
+          #builder
+          #mixins
+      };
+    }
+
+    let factori_builder = self.generate_factori_builder();
+
+    // These build synchronously, which an async builder can't.
+    if self.builder_async.is_some() {
+      return quote! {
+          #factory
+          #meta
+          #exhaustive
+          #fields
+          #builder
+          #mixins
+          #factori_builder
+      };
+    }
+
+    let factori_default = self.generate_factori_default();
+    let factori_mixins = self.generate_factori_mixins();
+
+    quote! {
+        #factory
+        #meta
+        #exhaustive
+        #fields
+        #builder
+        #mixins
+        #factori_default
+        #factori_mixins
+        #factori_builder
+    }
+  }
+}
+
+/// Adds `#[allow(...)]` with the given lints to each of the generated items,
+/// for lints triggered by the code of the factory's values and blocks
+fn allow_lints(stream: TokenStream, lints: &[Path]) -> TokenStream {
+  if lints.is_empty() {
+    return stream;
+  }
+
+  let mut file: syn::File = match syn::parse2(stream) {
+    Ok(file) => file,
+    Err(error) => {
+      let message = format!("couldn't add `allow` to the generated items: {}", error);
+      return syn::Error::new_spanned(&lints[0], message).to_compile_error();
+    }
+  };
+
+  let allow: Attribute = parse_quote! { #[allow(#( #lints ),*)] };
+  for item in &mut file.items {
+    let attrs = match item {
+      Item::Const(item) => &mut item.attrs,
+      Item::Enum(item) => &mut item.attrs,
+      Item::Fn(item) => &mut item.attrs,
+      Item::Impl(item) => &mut item.attrs,
+      Item::Macro(item) => &mut item.attrs,
+      Item::Mod(item) => &mut item.attrs,
+      Item::Static(item) => &mut item.attrs,
+      Item::Struct(item) => &mut item.attrs,
+      Item::Trait(item) => &mut item.attrs,
+      Item::Type(item) => &mut item.attrs,
+      Item::Use(item) => &mut item.attrs,
+      _ => continue,
+    };
+    attrs.push(allow.clone());
+  }
+
+  quote! { #file }
+}
+
+/// Takes the type parameter out of the type of a generic factory, e.g. turns
+/// `Wrapper<T: Default>` into `Wrapper<T>` and returns `<T: Default>`
+fn take_generics(ty: &mut Path) -> Result<Generics> {
+  let segment = ty.segments.last_mut().expect("paths have a segment");
+  let arguments = match &mut segment.arguments {
+    PathArguments::AngleBracketed(arguments) => &mut arguments.args,
+    _ => return Ok(Generics::default()),
+  };
+
+  let is_param = |argument: &GenericArgument| matches!(argument, GenericArgument::Constraint(_));
+  if !arguments.iter().any(is_param) {
+    return Ok(Generics::default());
+  }
+
+  if arguments.len() > 1 {
+    return Err(syn::Error::new_spanned(
+      arguments,
+      "Only a single type parameter is supported, e.g. `Wrapper<T: Default>`.",
+    ));
+  }
+
+  let argument = arguments.first_mut().expect("checked above");
+  let generics = match argument {
+    GenericArgument::Constraint(constraint) => {
+      let ident = &constraint.ident;
+      let bounds = &constraint.bounds;
+      let generics: Generics = parse_quote! { <#ident: #bounds> };
+      *argument = GenericArgument::Type(parse_quote! { #ident });
+      generics
+    }
+    _ => unreachable!("checked above"),
+  };
+
+  Ok(generics)
+}
+
+/// Whether the expression is a call to one of the macros whose values are
+/// evaluated when building, e.g. `association!(...)`
+fn is_deferred_macro(value: &Expr) -> bool {
+  if let Expr::Macro(expr) = value {
+    if let Some(segment) = expr.mac.path.segments.last() {
+      return segment.ident == "association"
+        || segment.ident == "repeat"
+        || segment.ident == "has_many"
+        || segment.ident == "create_vec";
+    }
+  }
+
+  false
+}
+
+/// Replaces a `once!(value)` default with a read of a static holding the
+/// value, which is only evaluated the first time. The static needs the
+/// field's type, so it must be given.
+fn once_value(value: Expr, ty: &Option<Type>) -> Result<Expr> {
+  let mac = match &value {
+    Expr::Macro(expr) if matches!(expr.mac.path.segments.last(), Some(s) if s.ident == "once") => {
+      &expr.mac
+    }
+    _ => return Ok(value),
+  };
+
+  let ty = match ty {
+    Some(ty) => ty,
+    None => {
+      let message = "Type must be specified when using `once!()`.";
+      return Err(syn::Error::new_spanned(mac, message));
+    }
+  };
+
+  let inner: Expr = mac.parse_body()?;
+  // A panic while evaluating the value leaves it unset, so the lock being
+  // poisoned is fine.
+  Ok(parse_quote! {
+    {
+      static __FACTORI_ONCE: ::std::sync::Mutex<::std::option::Option<#ty>> =
+        ::std::sync::Mutex::new(::std::option::Option::None);
+      let mut __factori_once = __FACTORI_ONCE
+        .lock()
+        .unwrap_or_else(::std::sync::PoisonError::into_inner);
+      ::std::clone::Clone::clone(__factori_once.get_or_insert_with(|| #inner))
+    }
+  })
+}
+
+/// The panic when a field of the partial {} block isn't set when building
+fn partial_message(field: &Ident) -> String {
+  format!(
+    "the partial field `{}` isn't set, it must be set by a mixin, in `create!` or with the builder's setter",
+    field.unraw()
+  )
+}
+
+/// The error when a required transient field isn't set
+fn required_message(field: &Ident) -> String {
+  format!(
+    "the transient field `{}` is required, it must be set by a mixin or in `create!`",
+    field.unraw()
+  )
+}
+
+/// Only the #[cfg] attributes, for generated items other than the field itself
+fn cfg_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+  attrs
+    .iter()
+    .filter(|attr| attr.path.is_ident("cfg"))
+    .collect()
+}
+
+struct MultipleDefinition {
+  /// The module to generate the types in, from `in module;` before the
+  /// definitions
+  module: Option<Ident>,
+  definitions: Vec<Definition>,
+}
+
+impl Parse for MultipleDefinition {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+
+    let mut module = None;
+    if input.peek(Token![in]) {
+      input.parse::<Token![in]>()?;
+      module = Some(input.parse()?);
+      input.parse::<Token![;]>()?;
+    }
+
+    let mut definitions = Vec::new();
+
+    loop {
+      if input.is_empty() {
+        break;
+      }
+      definitions.push(Definition::parse(krate.clone(), input)?);
+    }
+
+    Ok(Self {
+      module,
+      definitions,
+    })
+  }
+}
+
+/// Named factories for a single type, e.g.
+/// `factories!(User, { admin { default { ... } } guest { default { ... } } })`.
+/// Mixins defined outside of a named factory are shared by all of them.
+struct Group {
+  definitions: Vec<Definition>,
+}
+
+impl Group {
+  fn parse(krate: &TokenStream, input: ParseStream) -> Result<Self> {
+    let options = parse_options(input)?;
+    let mut ty: Path = input.parse()?;
+    let generics = take_generics(&mut ty)?;
+    input.parse::<Token![,]>()?;
+
+    let inner;
+    braced!(inner in input);
+
+    let mut definitions = Vec::new();
+    let mut shared_mixins: Vec<MixinBlock> = Vec::new();
+
+    loop {
+      if inner.is_empty() {
+        break;
+      }
+
+      let name: Ident = inner.parse()?;
+      if name == "mixin" {
+        shared_mixins.push(inner.parse()?);
+      } else if name == "mixin_group" {
+        shared_mixins.extend(parse_mixin_group(&inner)?);
+      } else {
+        let blocks;
+        braced!(blocks in inner);
+        let mut definition =
+          Definition::parse_blocks(krate.clone(), ty.clone(), Some(name), &blocks)?;
+        definition.generics = generics.clone();
+        definition.prefix = options.prefix.clone();
+        definition.exhaustive = options.exhaustive.clone();
+        definition.clone_builder = options.clone_builder.clone();
+        definition.builder_derive = options.builder_derive.clone();
+        definition.mixins_derive = options
+          .mixins_derive
+          .clone()
+          .map_or_else(Vec::new, |(_, traits)| traits);
+        definition.allow = options
+          .allow
+          .clone()
+          .map_or_else(Vec::new, |(_, lints)| lints);
+        definitions.push(definition);
+      }
+    }
+
+    for definition in &mut definitions {
+      definition.mixins.extend(shared_mixins.iter().cloned());
+    }
+
+    Ok(Self { definitions })
+  }
+}
+
+struct MultipleGroup {
+  groups: Vec<Group>,
+}
+
+impl Parse for MultipleGroup {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let krate = parse_crate_path(input)?;
+    let mut groups = Vec::new();
+
+    loop {
+      if input.is_empty() {
+        break;
+      }
+      groups.push(Group::parse(&krate, input)?);
+    }
+
+    Ok(Self { groups })
+  }
+}
+
+fn definitions_into_token_stream(definitions: Vec<Definition>) -> TokenStream {
   let mut stream = TokenStream::new();
   for definition in definitions {
     if let Some(error) = definition.validate() {
-      return error.into();
+      return error;
+    }
+    let allow = definition.allow.clone();
+    stream.extend(allow_lints(definition.into_token_stream(), &allow));
+  }
+
+  stream
+}
+
+pub fn define_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input: proc_macro::TokenStream = match expand_use_mixins(input.into(), "define") {
+    Ok(input) => input.into(),
+    Err(expansion) => return expansion.into(),
+  };
+
+  let MultipleDefinition {
+    module,
+    definitions,
+  } = parse_macro_input!(input);
+
+  let stream = definitions_into_token_stream(definitions);
+  match module {
+    // The module has everything in scope where the factory is defined, for
+    // the type and the values of its fields.
+    Some(module) => quote! {
+        pub mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #stream
+        }
+    }
+    .into(),
+    None => stream.into(),
+  }
+}
+
+/// Same as define_macro, but the generated types are private, so that the
+/// factory can be defined in a function for private types
+pub fn define_local_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input: proc_macro::TokenStream = match expand_use_mixins(input.into(), "define_local") {
+    Ok(input) => input.into(),
+    Err(expansion) => return expansion.into(),
+  };
+
+  let MultipleDefinition {
+    module,
+    mut definitions,
+  } = parse_macro_input!(input);
+
+  if let Some(module) = module {
+    return syn::Error::new(module.span(), "local factories can't have a module")
+      .to_compile_error()
+      .into();
+  }
+
+  for definition in &mut definitions {
+    definition.vis = TokenStream::new();
+  }
+
+  definitions_into_token_stream(definitions).into()
+}
+
+pub fn factories_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input: proc_macro::TokenStream = match expand_use_mixins(input.into(), "factories") {
+    Ok(input) => input.into(),
+    Err(expansion) => return expansion.into(),
+  };
+
+  let MultipleGroup { groups } = parse_macro_input!(input);
+
+  let definitions = groups
+    .into_iter()
+    .flat_map(|group| group.definitions)
+    .collect();
+
+  definitions_into_token_stream(definitions).into()
+}
+
+/// Same as define_macro, but also prints the generated code when compiling
+#[cfg(feature = "debug")]
+pub fn debug_define_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let stream = define_macro(input);
+  eprintln!("factori! generated:\n{}", stream);
+  stream
+}
+
+/// e.g. shared_mixins! { archived { archived = true } }
+///
+/// ... becomes a macro per mixin, which passes the mixin back to the macro
+/// defining a factory with `use_mixins archived;`:
+///
+/// macro_rules! __factori_mixin_archived {
+///   ([krate] callback input) => {
+///     krate::factori_imp_impl::callback! { [krate] @mixin archived (archived { archived = true }) input }
+///   };
+/// }
+pub fn shared_mixins_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let SharedMixins { mixins } = parse_macro_input!(input);
+
+  let macros = mixins.iter().map(|(name, payload)| {
+    let ident_macro = shared_mixin_macro(name);
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #ident_macro {
+            ([$($krate:tt)*] $callback:ident $($input:tt)*) => {
+                $($krate)*::factori_imp_impl::$callback! {
+                    [$($krate)*] @mixin #name (#payload) $($input)*
+                }
+            };
+        }
+    }
+  });
+
+  quote! { #( #macros )* }.into()
+}
+
+/// The mixins of shared_mixins! {}, with the tokens of each after `mixin`
+struct SharedMixins {
+  mixins: Vec<(Ident, TokenStream)>,
+}
+
+impl Parse for SharedMixins {
+  fn parse(input: ParseStream) -> Result<Self> {
+    parse_crate_path(input)?;
+
+    let mut mixins = Vec::new();
+    while !input.is_empty() {
+      // Parsed as a mixin for the errors, but kept as tokens to be passed on.
+      let fork = input.fork();
+      let mixin: MixinBlock = fork.parse()?;
+
+      let mut payload = TokenStream::new();
+      loop {
+        let token: TokenTree = input.parse()?;
+        let is_body =
+          matches!(&token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace);
+        payload.extend(Some(token));
+        if is_body {
+          break;
+        }
+      }
+
+      mixins.push((mixin.name, payload));
+    }
+
+    Ok(SharedMixins { mixins })
+  }
+}
+
+fn shared_mixin_macro(name: &Ident) -> Ident {
+  Ident::new(&format!("__factori_mixin_{}", name.unraw()), name.span())
+}
+
+/// Replaces the `use_mixins a, b;` in the braces of the definitions with the
+/// shared mixins, one at a time. The first name is passed to its macro, which
+/// passes the mixin back before the rest of the input, e.g. `@mixin a (a {
+/// ... })`, where it takes the place of the name. The input without any
+/// `use_mixins` left is returned as `Ok`, and the call to the macro of the
+/// next shared mixin as `Err` otherwise.
+fn expand_use_mixins(
+  input: TokenStream,
+  callback: &str,
+) -> std::result::Result<TokenStream, TokenStream> {
+  let named = callback == "factories";
+  let mut rest: Vec<TokenTree> = input.into_iter().collect();
+  let krate = match rest.first() {
+    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+      let krate = group.stream();
+      rest.remove(0);
+      krate
+    }
+    _ => quote! { factori_imp },
+  };
+
+  while rest.len() >= 4 && is_punct(&rest[0], '@') && is_ident(&rest[1], "mixin") {
+    let payload = match rest.drain(..4).nth(3) {
+      Some(TokenTree::Group(payload)) => payload.stream(),
+      _ => unreachable!("passed by the macros of shared_mixins!"),
+    };
+
+    with_first_use_mixins(&mut rest, named, &mut |tokens, i| {
+      // The mixin takes the place of its name, which comes first.
+      tokens.remove(i + 1);
+      if matches!(tokens.get(i + 1), Some(token) if is_punct(token, ',')) {
+        tokens.remove(i + 1);
+      }
+      match tokens.get(i + 1) {
+        Some(token) if is_punct(token, ';') => drop(tokens.drain(i..i + 2)),
+        None => drop(tokens.remove(i)),
+        Some(_) => {}
+      }
+
+      tokens.splice(i..i, quote! { mixin #payload });
+    });
+  }
+
+  let next = with_first_use_mixins(&mut rest, named, &mut |tokens, i| match &tokens[i + 1] {
+    TokenTree::Ident(name) => Ok(name.clone()),
+    token => Err(syn::Error::new(
+      token.span(),
+      "expected the name of a shared mixin",
+    )),
+  });
+
+  let rest: TokenStream = rest.into_iter().collect();
+  match next {
+    None => Ok(quote! { [#krate] #rest }),
+    Some(Ok(name)) => {
+      let ident_macro = shared_mixin_macro(&name);
+      let callback = Ident::new(callback, Span::call_site());
+      Err(quote! { #ident_macro! { [#krate] #callback #rest } })
+    }
+    Some(Err(error)) => Err(error.to_compile_error()),
+  }
+}
+
+/// Calls `f` with the tokens of the braces with the first `use_mixins`, and
+/// the index of `use_mixins` in them. It's only looked for in place of a
+/// block, in the braces of the definitions and, with `named`, of the named
+/// factories in them, so fields named `use_mixins` are left alone.
+fn with_first_use_mixins<R>(
+  tokens: &mut [TokenTree],
+  named: bool,
+  f: &mut dyn FnMut(&mut Vec<TokenTree>, usize) -> R,
+) -> Option<R> {
+  for i in 0..tokens.len() {
+    if !is_brace(&tokens[i]) {
+      continue;
+    }
+
+    let result = in_braces(tokens, i, &mut |inner| in_definition(inner, named, &mut *f));
+    if result.is_some() {
+      return result;
     }
-    stream.extend(definition.into_token_stream());
   }
 
-  stream.into()
+  None
+}
+
+/// Looks for `use_mixins` in the braces of a definition, e.g. `{ default { ...
+/// } use_mixins a; }`, and in the braces of its named factories, e.g. `admin
+/// { ... }`, with `named`
+fn in_definition<R>(
+  tokens: &mut Vec<TokenTree>,
+  named: bool,
+  f: &mut dyn FnMut(&mut Vec<TokenTree>, usize) -> R,
+) -> Option<R> {
+  for i in 0..tokens.len() {
+    let is_block = i == 0 || is_punct(&tokens[i - 1], ';') || is_brace(&tokens[i - 1]);
+    if !is_block || i + 1 >= tokens.len() {
+      continue;
+    }
+
+    if is_ident(&tokens[i], "use_mixins") {
+      return Some(f(tokens, i));
+    }
+
+    let is_factory =
+      matches!(&tokens[i], TokenTree::Ident(name) if name != "mixin" && name != "mixin_group");
+    if named && is_factory && is_brace(&tokens[i + 1]) {
+      let result = in_braces(tokens, i + 1, &mut |inner| {
+        in_definition(inner, false, &mut *f)
+      });
+      if result.is_some() {
+        return result;
+      }
+    }
+  }
+
+  None
+}
+
+/// Calls `f` with the tokens of the braces at `i`, which are replaced by the
+/// tokens left by `f` if it returns `Some`
+fn in_braces<R>(
+  tokens: &mut [TokenTree],
+  i: usize,
+  f: &mut dyn FnMut(&mut Vec<TokenTree>) -> Option<R>,
+) -> Option<R> {
+  let group = match &tokens[i] {
+    TokenTree::Group(group) => group,
+    _ => return None,
+  };
+
+  let mut inner: Vec<TokenTree> = group.stream().into_iter().collect();
+  let result = f(&mut inner)?;
+
+  let mut replaced = proc_macro2::Group::new(group.delimiter(), inner.into_iter().collect());
+  replaced.set_span(group.span());
+  tokens[i] = TokenTree::Group(replaced);
+  Some(result)
+}
+
+fn is_brace(token: &TokenTree) -> bool {
+  matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+}
+
+fn is_punct(token: &TokenTree, ch: char) -> bool {
+  matches!(token, TokenTree::Punct(punct) if punct.as_char() == ch)
+}
+
+fn is_ident(token: &TokenTree, name: &str) -> bool {
+  matches!(token, TokenTree::Ident(ident) if ident == name)
 }
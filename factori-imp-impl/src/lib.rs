@@ -3,29 +3,405 @@ extern crate proc_macro;
 mod create;
 mod define;
 
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{ParseStream, Result};
+use syn::{bracketed, Token};
+use syn::{
+  Attribute, GenericArgument, Lit, Meta, MetaList, NestedMeta, Path, PathArguments, PathSegment,
+};
 
-fn ident_builder(ty: &Ident) -> Ident {
-  let ident = format!("_Factori_Builder_{}", ty);
+/// The prefix of the generated types, unless another one is given with
+/// `#[factori(prefix = "...")]`
+const DEFAULT_PREFIX: &str = "_Factori";
+
+fn ident_builder(prefix: &str, ty: &Ident) -> Ident {
+  let ident = format!("{}_Builder_{}", prefix, ty);
+  Ident::new(&ident, ty.span())
+}
+
+fn ident_mixins_enum(prefix: &str, ty: &Ident) -> Ident {
+  let ident = format!("{}_Mixins_{}", prefix, ty);
   Ident::new(&ident, ty.span())
 }
 
-fn ident_mixins_enum(ty: &Ident) -> Ident {
-  let ident = format!("_Factori_Mixins_{}", ty);
+fn ident_fields(prefix: &str, ty: &Ident) -> Ident {
+  let ident = format!("{}_Fields_{}", prefix, ty);
   Ident::new(&ident, ty.span())
 }
 
+fn ident_factory(prefix: &str, ty: &Ident) -> Ident {
+  let ident = format!("{}_Factory_{}", prefix, ty);
+  Ident::new(&ident, ty.span())
+}
+
+/// The constant of a mixins enum listing the fields set by a mixin, e.g.
+/// `FIELDS_OF_bike`
+fn mixin_fields_const(mixin: &Ident) -> Ident {
+  let ident = format!("FIELDS_OF_{}", mixin.unraw());
+  Ident::new(&ident, mixin.span())
+}
+
+/// The constant of a mixins enum with the group of a mixin, e.g.
+/// `GROUP_OF_shipped`
+fn mixin_group_const(mixin: &Ident) -> Ident {
+  let ident = format!("GROUP_OF_{}", mixin.unraw());
+  Ident::new(&ident, mixin.span())
+}
+
+/// The function of a fields struct turning the value of a field in the
+/// builder into the value `default` refers to in `create!`, e.g.
+/// `__default_of_number_wheels`
+fn field_default_fn(field: &Ident) -> Ident {
+  let ident = format!("__default_of_{}", field.unraw());
+  Ident::new(&ident, field.span())
+}
+
+/// The constant of a mixins enum with the name of a mixin, which is the
+/// mixin's own name for its aliases too, e.g. `NAME_OF_shipped`
+fn mixin_name_const(mixin: &Ident) -> Ident {
+  let ident = format!("NAME_OF_{}", mixin.unraw());
+  Ident::new(&ident, mixin.span())
+}
+
+/// The name of a factory's generated types, which is the type's name followed
+/// by its const arguments, e.g. `Buffer_16` for `Buffer<16>`, so that each
+/// instantiation of a type with const generics can have a factory
+fn factory_ident(segment: &PathSegment) -> Ident {
+  let arguments = match &segment.arguments {
+    PathArguments::AngleBracketed(arguments) => &arguments.args,
+    _ => return segment.ident.clone(),
+  };
+
+  let mut ident = segment.ident.to_string();
+  for argument in arguments {
+    if let GenericArgument::Const(value) = argument {
+      let value = value.to_token_stream().to_string();
+      ident.push('_');
+      ident.extend(value.chars().filter(|c| !c.is_whitespace()).map(|c| {
+        if c.is_alphanumeric() {
+          c
+        } else {
+          '_'
+        }
+      }));
+    }
+  }
+
+  Ident::new(&ident, segment.ident.span())
+}
+
+/// Parses the module before the type passed to the macros instantiating a
+/// factory, e.g. `vehicles::` in `vehicles::Vehicle`, for factories defined
+/// with `factori!(in vehicles; ...)`. It's empty for other factories.
+fn parse_factory_module(input: ParseStream) -> Result<TokenStream> {
+  let mut module = TokenStream::new();
+  if input.peek(Token![::]) {
+    module.extend(input.parse::<Token![::]>()?.into_token_stream());
+  }
+
+  while input.peek(Ident::peek_any) && input.peek2(Token![::]) && !input.peek3(Token![<]) {
+    module.extend(input.call(Ident::parse_any)?.into_token_stream());
+    module.extend(input.parse::<Token![::]>()?.into_token_stream());
+  }
+
+  Ok(module)
+}
+
+/// Parses the type passed to the macros instantiating a factory, which can
+/// have const arguments, e.g. `Buffer<16>`
+fn parse_factory_ident(input: ParseStream) -> Result<Ident> {
+  let segment: PathSegment = input.parse()?;
+  if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+    let not_const = arguments
+      .args
+      .iter()
+      .find(|argument| !matches!(argument, GenericArgument::Const(_)));
+    if let Some(argument) = not_const {
+      return Err(syn::Error::new_spanned(
+        argument,
+        "only const arguments can be given with the type, e.g. `Buffer<16>`",
+      ));
+    }
+  }
+
+  Ok(factory_ident(&segment))
+}
+
+/// The options given in `#[factori(...)]` attributes before the type
+struct Options {
+  /// `prefix = "..."`, which replaces the `_Factori` prefix of the generated
+  /// types
+  prefix: String,
+  /// `exhaustive`, which requires the `default` block to have every field of
+  /// the struct
+  exhaustive: Option<Ident>,
+  /// `clone_builder`, which makes the builder `Clone` for
+  /// `create_vec_from_builder!`
+  clone_builder: Option<Ident>,
+  /// `builder_derive(...)`, with the traits to derive on the builder
+  builder_derive: Option<(Ident, Vec<Path>)>,
+  /// `mixins_derive(...)`, with the traits to derive on the mixins enum
+  mixins_derive: Option<(Ident, Vec<Path>)>,
+  /// `allow(...)`, with the lints to allow in the generated code
+  allow: Option<(Ident, Vec<Path>)>,
+}
+
+fn parse_options(input: ParseStream) -> Result<Options> {
+  let mut options = Options {
+    prefix: DEFAULT_PREFIX.to_string(),
+    exhaustive: None,
+    clone_builder: None,
+    builder_derive: None,
+    mixins_derive: None,
+    allow: None,
+  };
+
+  for attr in input.call(Attribute::parse_outer)? {
+    let list = match attr.parse_meta()? {
+      Meta::List(list) if list.path.is_ident("factori") => list,
+      _ => return Err(syn::Error::new_spanned(attr, "expected `#[factori(...)]`")),
+    };
+
+    for option in list.nested {
+      match option {
+        NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("prefix") => {
+          let value = match &name_value.lit {
+            Lit::Str(value) => value,
+            lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+          };
+          if syn::parse_str::<Ident>(&value.value()).is_err() {
+            return Err(syn::Error::new_spanned(
+              value,
+              "the prefix must be an identifier",
+            ));
+          }
+
+          options.prefix = value.value();
+        }
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("exhaustive") => {
+          options.exhaustive = path.get_ident().cloned();
+        }
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("clone_builder") => {
+          options.clone_builder = path.get_ident().cloned();
+        }
+        NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("builder_derive") => {
+          options.builder_derive = Some(parse_paths(list, "expected a trait")?);
+        }
+        NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("mixins_derive") => {
+          options.mixins_derive = Some(parse_paths(list, "expected a trait")?);
+        }
+        NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("allow") => {
+          options.allow = Some(parse_paths(list, "expected a lint")?);
+        }
+        option => {
+          return Err(syn::Error::new_spanned(
+            option,
+            "expected `prefix = \"...\"`, `exhaustive`, `clone_builder`, `builder_derive(...)`, `mixins_derive(...)` or `allow(...)`",
+          ))
+        }
+      }
+    }
+  }
+
+  Ok(options)
+}
+
+/// Parses the paths of an option, e.g. `builder_derive(Debug, PartialEq)`,
+/// with the option's name
+fn parse_paths(list: MetaList, error: &str) -> Result<(Ident, Vec<Path>)> {
+  let mut paths = Vec::new();
+  for nested in list.nested {
+    match nested {
+      NestedMeta::Meta(Meta::Path(path)) => paths.push(path),
+      nested => return Err(syn::Error::new_spanned(nested, error)),
+    }
+  }
+
+  let ident = list
+    .path
+    .get_ident()
+    .cloned()
+    .expect("checked by the caller");
+  Ok((ident, paths))
+}
+
+/// Parses the options before the type in the macros instantiating a factory,
+/// where only the prefix applies
+fn parse_prefix(input: ParseStream) -> Result<String> {
+  let options = parse_options(input)?;
+  let builder_derive = options.builder_derive.map(|(ident, _)| ident);
+  let mixins_derive = options.mixins_derive.map(|(ident, _)| ident);
+  let allow = options.allow.map(|(ident, _)| ident);
+  if let Some(option) = options
+    .exhaustive
+    .or(options.clone_builder)
+    .or(builder_derive)
+    .or(mixins_derive)
+    .or(allow)
+  {
+    return Err(syn::Error::new_spanned(
+      &option,
+      format!("`{}` only applies where the factory is defined", option),
+    ));
+  }
+
+  Ok(options.prefix)
+}
+
+/// The macros of factori-imp pass `[$crate]` before their input, so that the
+/// generated code refers to the crate even when it's renamed or re-exported.
+fn parse_crate_path(input: ParseStream) -> Result<TokenStream> {
+  if !input.peek(syn::token::Bracket) {
+    return Ok(quote! { factori_imp });
+  }
+
+  let content;
+  bracketed!(content in input);
+  content.parse()
+}
+
 #[proc_macro]
 pub fn define(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
   define::define_macro(input)
 }
 
+#[proc_macro]
+pub fn shared_mixins(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  define::shared_mixins_macro(input)
+}
+
+#[proc_macro]
+pub fn define_local(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  define::define_local_macro(input)
+}
+
+#[proc_macro]
+pub fn factories(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  define::factories_macro(input)
+}
+
+#[cfg(feature = "debug")]
+#[proc_macro]
+pub fn debug_define(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  define::debug_define_macro(input)
+}
+
 #[proc_macro]
 pub fn create(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
   create::create_macro(input)
 }
 
+#[cfg(feature = "async")]
+#[proc_macro]
+pub fn create_async(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_async_macro(input)
+}
+
+#[proc_macro]
+pub fn try_create(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::try_create_macro(input)
+}
+
+#[proc_macro]
+pub fn build(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::build_macro(input)
+}
+
+#[proc_macro]
+pub fn create_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_inspect_macro(input)
+}
+
+#[cfg(feature = "serde")]
+#[proc_macro]
+pub fn create_json(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_json_macro(input)
+}
+
+#[proc_macro]
+pub fn create_cow(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_cow_macro(input)
+}
+
+#[proc_macro]
+pub fn factori_bench(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::factori_bench_macro(input)
+}
+
+#[proc_macro]
+pub fn create_into(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_into_macro(input)
+}
+
+#[proc_macro]
+pub fn assert_factori_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::assert_factori_default_macro(input)
+}
+
 #[proc_macro]
 pub fn create_vec(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
   create::create_vec_macro(input)
 }
+
+#[cfg(feature = "async")]
+#[proc_macro]
+pub fn create_vec_async(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_vec_async_macro(input)
+}
+
+#[proc_macro]
+pub fn create_into_vec(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_into_vec_macro(input)
+}
+
+#[proc_macro]
+pub fn create_collection(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_collection_macro(input)
+}
+
+#[proc_macro]
+pub fn create_vec_unique(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_vec_unique_macro(input)
+}
+
+#[proc_macro]
+pub fn create_mapped(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_mapped_macro(input)
+}
+
+#[proc_macro]
+pub fn create_vec_clone(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_vec_clone_macro(input)
+}
+
+#[proc_macro]
+pub fn create_vec_from_builder(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_vec_from_builder_macro(input)
+}
+
+#[proc_macro]
+pub fn create_hashmap(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_hashmap_macro(input)
+}
+
+#[proc_macro]
+pub fn create_dyn_vec(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_dyn_vec_macro(input)
+}
+
+#[proc_macro]
+pub fn create_with_seed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_with_seed_macro(input)
+}
+
+#[proc_macro]
+pub fn create_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::create_shared_macro(input)
+}
+
+#[proc_macro]
+pub fn reset_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  create::reset_shared_macro(input)
+}
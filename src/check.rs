@@ -0,0 +1,112 @@
+//! Compile time checks of the calls to the `create*` macros, evaluated in
+//! constants so that failing them is a compile error.
+
+const fn str_eq(a: &str, b: &str) -> bool {
+  let (a, b) = (a.as_bytes(), b.as_bytes());
+  if a.len() != b.len() {
+    return false;
+  }
+
+  let mut i = 0;
+  while i < a.len() {
+    if a[i] != b[i] {
+      return false;
+    }
+    i += 1;
+  }
+
+  true
+}
+
+const fn contains(names: &[&str], name: &str) -> bool {
+  let mut i = 0;
+  while i < names.len() {
+    if str_eq(names[i], name) {
+      return true;
+    }
+    i += 1;
+  }
+
+  false
+}
+
+const fn is_set(field: &str, mixins: &[&[&str]], overrides: &[&str]) -> bool {
+  let mut set = contains(overrides, field);
+
+  let mut i = 0;
+  while i < mixins.len() {
+    set = set || contains(mixins[i], field);
+    i += 1;
+  }
+
+  set
+}
+
+/// Checks that every field of a factory is set by the mixins or the
+/// overrides of a `create!(..., ..no_default, ...)` call.
+pub const fn no_default(fields: &[&str], mixins: &[&[&str]], overrides: &[&str]) {
+  let mut i = 0;
+  while i < fields.len() {
+    if !is_set(fields[i], mixins, overrides) {
+      panic!("`..no_default` requires every field to be set by a mixin or an override");
+    }
+    i += 1;
+  }
+}
+
+/// Checks that the required transient fields of a factory, given with their
+/// error, are set by the mixins or the overrides of a `create!()` call.
+pub const fn required(fields: &[(&str, &str)], mixins: &[&[&str]], overrides: &[&str]) {
+  let mut i = 0;
+  while i < fields.len() {
+    let (field, error) = fields[i];
+    if !is_set(field, mixins, overrides) {
+      panic!("{}", error);
+    }
+    i += 1;
+  }
+}
+
+/// Checks that the fields of a factory marked `#[factori(skip)]`, given with
+/// their error, aren't overridden by a `create!()` call.
+pub const fn not_skipped(fields: &[(&str, &str)], overrides: &[&str]) {
+  let mut i = 0;
+  while i < fields.len() {
+    let (field, error) = fields[i];
+    if contains(overrides, field) {
+      panic!("{}", error);
+    }
+    i += 1;
+  }
+}
+
+/// Checks that the value of a field, whose value passed to `create!()` refers
+/// to it as `default`, is known before building, given the fields whose value
+/// isn't with their error.
+pub const fn default_known(fields: &[(&str, &str)], field: &str) {
+  let mut i = 0;
+  while i < fields.len() {
+    let (unknown, error) = fields[i];
+    if str_eq(unknown, field) {
+      panic!("{}", error);
+    }
+    i += 1;
+  }
+}
+
+/// Checks that two mixins applied by a `create!()` call aren't in the same
+/// `mixin_group`, given their groups and names. The names are the same for a
+/// mixin and its aliases, which can be applied together.
+pub const fn exclusive(
+  first: Option<&str>,
+  first_name: &str,
+  second: Option<&str>,
+  second_name: &str,
+  error: &str,
+) {
+  if let (Some(first), Some(second)) = (first, second) {
+    if str_eq(first, second) && !str_eq(first_name, second_name) {
+      panic!("{}", error);
+    }
+  }
+}
@@ -9,11 +9,95 @@
 //!    factories.
 //!  - A [`create_vec!()`] macro which is used to instantiate many objects from
 //!    factories
+//!  - A [`create_into_vec!()`] macro which is used to instantiate many
+//!    objects from factories into an existing vec
+//!  - A [`create_collection!()`] macro which is used to instantiate many
+//!    objects from factories into any collection
+//!  - A [`create_vec_unique!()`] macro which is used to instantiate many
+//!    objects from factories with distinct values of a field
+//!  - A [`create_vec_clone!()`] macro which is used to instantiate many copies
+//!    of a single object from factories
+//!  - A [`create_vec_from_builder!()`] macro which is used to instantiate
+//!    many objects from clones of a single builder
+//!  - A [`create_mapped!()`] macro which is used to instantiate many objects
+//!    from factories and map them
+//!  - A [`create_hashmap!()`] macro which is used to instantiate many objects
+//!    from factories, keyed by one of their fields
+//!  - A [`create_dyn_vec!()`] macro which is used to instantiate objects
+//!    from different factories as trait objects
+//!  - A [`create_into!()`] macro which is used to instantiate objects from
+//!    factories, converting the values passed to it with `Into`
+//!  - A [`try_create!()`] macro which is used to instantiate objects from
+//!    factories which can fail
+//!  - [`create_async!()`] and [`create_vec_async!()`] macros which are used
+//!    to instantiate objects from factories which build asynchronously, with
+//!    the `async` feature
+//!  - An [`assert_factori_default!()`] macro which is used to assert the
+//!    defaults of factories
+//!  - A [`build!()`] macro which is used to get a factory's builder without
+//!    building it
+//!  - A [`create_inspect!()`] macro which is used to instantiate objects from
+//!    factories along with their builders
+//!  - A [`create_cow!()`] macro which is used to instantiate objects from
+//!    factories as an owned `Cow`
+//!  - A [`factori_bench!()`] macro which is used to get a closure
+//!    instantiating objects from factories, for benchmarks
+//!  - A [`create_default!()`] macro which is used to instantiate an object
+//!    whose type is inferred from the context
+//!  - An [`association!()`] macro which is used to instantiate associated
+//!    objects in factories
+//!  - A [`has_many!()`] macro which is used to instantiate many associated
+//!    objects in factories
+//!  - A [`once!()`] macro which is used to evaluate a default value once for
+//!    every instance of a factory
+//!  - A [`clone!()`] macro which is used to clone a shared default value for
+//!    every instance of a factory
+//!  - A [`create_with_seed!()`] macro which is used to instantiate objects
+//!    with reproducible random values from [`rng()`]
+//!  - A [`create_shared!()`] macro which is used to instantiate an object once
+//!    and share it
+//!  - A [`factori_reset_all!()`] macro which is used to reset the state kept
+//!    by the factories between tests
+//!  - A [`shared_mixins!()`] macro which is used to define mixins for
+//!    several factories
+//!  - A [`factori_local!()`] macro which is used to define factories in
+//!    functions
+//!  - A [`factories!()`] macro which is used to define several named factories
+//!    for a type
 //!
 //! [FactoryBot]: https://github.com/thoughtbot/factory_bot
 //! [`factori!()`]: macro.factori.html
 //! [`create!()`]: macro.create.html
 //! [`create_vec!()`]: macro.create_vec.html
+//! [`create_into_vec!()`]: macro.create_into_vec.html
+//! [`create_collection!()`]: macro.create_collection.html
+//! [`create_vec_unique!()`]: macro.create_vec_unique.html
+//! [`create_vec_clone!()`]: macro.create_vec_clone.html
+//! [`create_vec_from_builder!()`]: macro.create_vec_from_builder.html
+//! [`create_mapped!()`]: macro.create_mapped.html
+//! [`create_hashmap!()`]: macro.create_hashmap.html
+//! [`create_dyn_vec!()`]: macro.create_dyn_vec.html
+//! [`create_into!()`]: macro.create_into.html
+//! [`try_create!()`]: macro.try_create.html
+//! [`create_async!()`]: macro.create_async.html
+//! [`create_vec_async!()`]: macro.create_vec_async.html
+//! [`assert_factori_default!()`]: macro.assert_factori_default.html
+//! [`build!()`]: macro.build.html
+//! [`create_inspect!()`]: macro.create_inspect.html
+//! [`create_cow!()`]: macro.create_cow.html
+//! [`factori_bench!()`]: macro.factori_bench.html
+//! [`create_default!()`]: macro.create_default.html
+//! [`association!()`]: macro.association.html
+//! [`has_many!()`]: macro.has_many.html
+//! [`once!()`]: macro.once.html
+//! [`clone!()`]: macro.clone.html
+//! [`create_with_seed!()`]: macro.create_with_seed.html
+//! [`rng()`]: rng/fn.rng.html
+//! [`create_shared!()`]: macro.create_shared.html
+//! [`factori_reset_all!()`]: macro.factori_reset_all.html
+//! [`shared_mixins!()`]: macro.shared_mixins.html
+//! [`factori_local!()`]: macro.factori_local.html
+//! [`factories!()`]: macro.factories.html
 //!
 //! ## Example
 //!
@@ -74,7 +158,7 @@
 //! a glob import to bring all of the required types into scope without
 //! having them cluttering up your project's namespaces.
 //!
-//! ```
+//! ```no_run
 //! # #[macro_use] extern crate factori_imp;
 //! # fn main() { }
 //! #
@@ -104,6 +188,35 @@
 //! }
 //! ```
 //!
+//! Rather than sharing a module with other items, the generated types can
+//! have a module of their own, given with `in` before the type. The factory
+//! is then instantiated through that module, or after importing it, so that
+//! the glob import doesn't bring anything else into scope. The module has
+//! everything in scope where the factory is defined:
+//!
+//! ```
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! struct Vehicle {
+//!   number_wheels: u8,
+//! }
+//!
+//! factori!(in vehicle_factory; Vehicle, {
+//!   default {
+//!     number_wheels = 4
+//!   }
+//! });
+//!
+//! fn main() {
+//!   let vehicle = create!(vehicle_factory::Vehicle);
+//!   assert_eq!(vehicle.number_wheels, 4);
+//!
+//!   use vehicle_factory::*;
+//!   let vehicle = create!(Vehicle, number_wheels: 2);
+//!   assert_eq!(vehicle.number_wheels, 2);
+//! }
+//! ```
+//!
 //! The implementation details of the [`factori!()`] and [`create!()`] macros
 //! are considered private and you should not rely on any of the generated
 //! types or their names. However, the implementation is quite simple and you
@@ -113,6 +226,29 @@
 //! clash with any types in your crate. It is a little gross but it is all
 //! in the name of testing convenience.
 //!
+//! Should they clash anyway, e.g. with the types generated by another macro,
+//! the prefix can be changed with an attribute before the type. The same
+//! attribute has to be passed wherever the factory is instantiated:
+//!
+//! ```
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! struct Vehicle {
+//!   number_wheels: u8,
+//! }
+//!
+//! factori!(#[factori(prefix = "_MyFactory")] Vehicle, {
+//!   default {
+//!     number_wheels = 4
+//!   }
+//! });
+//!
+//! fn main() {
+//!   let vehicle = create!(#[factori(prefix = "_MyFactory")] Vehicle);
+//!   assert_eq!(vehicle.number_wheels, 4);
+//! }
+//! ```
+//!
 //! [`cargo-expand`]: https://github.com/dtolnay/cargo-expand
 //!
 //! ## Error messages
@@ -121,10 +257,79 @@
 //! considering what they're doing. However, if you encounter weird error
 //! messages that aren't self-explanatory, please raise an issue on the GitHub
 //! repository.
+//!
+//! When the factory of a type isn't in scope, because of a typo in the type
+//! or a missing import, the first error is that the factory's
+//! `_Factori_Factory_` trait can't be found, along with where to import it
+//! from. The errors about the other `_Factori` types which follow it have the
+//! same cause.
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! mod factories {
+//!   pub struct Vehicle {
+//!     pub number_wheels: u8,
+//!   }
+//!
+//!   factori!(Vehicle, {
+//!     default {
+//!       number_wheels = 4
+//!     }
+//!   });
+//! }
+//!
+//! use factories::Vehicle;
+//!
+//! fn main() {
+//!   // error[E0405]: cannot find trait `_Factori_Factory_Vehicle` in this scope
+//!   let vehicle = create!(Vehicle);
+//! }
+//! ```
+//!
+//! Panics while building an instance, such as an `unwrap()` failing in a
+//! `builder` block or a required field which isn't set, are reported at the
+//! [`create!()`] call, or the call of `build()` on a builder, rather than in
+//! the factory, as the generated build functions are `#[track_caller]`.
+//!
+//! The fields of the blocks of a factory are separated with `,`, and a `;`
+//! in their place is an error which says so:
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! pub struct Vehicle {
+//!   number_wheels: u8,
+//!   electric: bool,
+//! }
+//!
+//! factori!(Vehicle, {
+//!   default {
+//!     // error: use `,` to separate factory fields
+//!     number_wheels = 4;
+//!     electric = false;
+//!   }
+//! });
+//! #
+//! # fn main() {}
+//! ```
 
 // Clippy seems to get confused when testing procedural macros in doctests:
 #![allow(clippy::needless_doctest_main)]
 
+#[doc(hidden)]
+pub mod check;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod reset;
+pub mod rng;
+#[doc(hidden)]
+pub mod shared;
+#[cfg(feature = "trace")]
+pub mod trace;
+
+pub use rng::rng;
+
 /// A macro to instantiate an instance of a factory.
 ///
 /// The type must already have had a factory defined using the [`factori!()`]
@@ -133,6 +338,18 @@
 /// The `create!()` macro accepts:
 ///
 ///  - The type to be instantiated using its factory.
+///
+///    The builder and the mixins enum of the factory can be given instead,
+///    as `@builder Builder, @mixins Mixins`, for when they're re-exported
+///    under other names than the ones derived from the type. `@mixins` can
+///    be left out when no mixins are applied. The fields are then set on the
+///    builder as they are, so this doesn't support `lazy` and partial fields,
+///    required transient fields or `..no_default`, and doesn't check for
+///    fields marked `#[factori(skip)]`.
+///  - Optionally `=> target` after the type, where `target` is a collection
+///    such as a `Vec` or a `HashSet`. The instance is then added to it with
+///    `Extend::extend` instead of being returned, and `create!()` evaluates
+///    to `()`.
 ///  - Zero or more comma-separated mixins using the syntax `:name`.
 ///
 ///    These are applied in the order that they are passed to `create!()`,
@@ -142,12 +359,118 @@
 ///    You can think of the default values defined in the factory's `default`
 ///    block as an implicit mixin which is always included first in every call
 ///    to `create!()`.
+///
+///    A mixin can be followed by a condition, `:name if condition`, in which
+///    case it's only applied if the condition is true. When it is, it's
+///    applied in its place in the order, like the other mixins.
+///
+///    `random_mixins: [name, other_name]` applies a random subset of the
+///    listed mixins, each being applied if a random `bool` from [`rng()`] is
+///    true. The ones which are applied are in the listed order, in the place
+///    of `random_mixins` among the other mixins. Like conditional mixins,
+///    they aren't checked for `mixin_group`s and required transient fields.
+///    [`create_with_seed!()`] seeds [`rng()`] to pick the same mixins for the
+///    same seed.
+///  - Optionally `..default`, which makes this explicit and changes nothing,
+///    or `..no_default`.
+///
+///    With `..no_default`, every field of the `default` and `transient`
+///    blocks must be set by the mixins or the named fields, otherwise it is a
+///    compile error. The default values are still evaluated, but none of
+///    them end up in the instance.
+///
+///    Any other expression after `..` is a builder made with [`build!()`],
+///    which is used instead of the defaults. The mixins are applied on top of
+///    it, and the named fields on top of them. To use the same builder in
+///    several calls, pass a clone of it, e.g. `..preset.clone()`, which
+///    requires `#[factori(clone_builder)]` on a factory with a `builder`
+///    block.
 ///  - Zero or more named fields with values, `field: value`.
 ///
 ///    These override both the factory's default and transient values and the
 ///    provided mixins. Each field from the `default` block can appear zero or
 ///    one times.
 ///
+///    The mixins, `..` and the named fields can be given in any order, e.g.
+///    `create!(Vehicle, number_wheels: 3, :bike)`. The named fields still
+///    override every mixin, wherever they are.
+///
+///    A value can refer to the value the field would otherwise have as
+///    `default`, e.g. `number_wheels: default + 1`. This is a compile error
+///    for the fields whose value isn't known before building: `lazy` and
+///    partial fields, required transient fields and, in a factory with a
+///    `builder` block, fields using [`association!()`], `repeat!()`,
+///    [`has_many!()`] or [`create_vec!()`].
+///
+///    A field of a struct field can be set with a dotted path, e.g.
+///    `engine.power: 200`, which keeps the rest of the field's value. Its
+///    value can refer to the nested field's value as `default`. Only one
+///    level of nesting is supported, and such fields don't count as set for
+///    `..no_default`. A field can't be set both as a whole and with a dotted
+///    path in the same call, in either order:
+///
+///    ```compile_fail
+///    # #[macro_use] extern crate factori_imp;
+///    #
+///    struct Engine {
+///      power: u32,
+///      cylinders: u8,
+///    }
+///
+///    struct Vehicle {
+///      engine: Engine,
+///    }
+///
+///    factori!(Vehicle, {
+///      default {
+///        engine = Engine { power: 100, cylinders: 4 },
+///      }
+///    });
+///
+///    fn main() {
+///      // error: `engine.power` is already set, so `engine` can't be set
+///      let vehicle = create!(
+///        Vehicle,
+///        engine.power: 200,
+///        engine: Engine { power: 150, cylinders: 6 },
+///      );
+///    }
+///    ```
+///
+///    ```compile_fail
+///    # #[macro_use] extern crate factori_imp;
+///    #
+///    # struct Engine {
+///    #   power: u32,
+///    #   cylinders: u8,
+///    # }
+///    #
+///    # struct Vehicle {
+///    #   engine: Engine,
+///    # }
+///    #
+///    # factori!(Vehicle, {
+///    #   default {
+///    #     engine = Engine { power: 100, cylinders: 4 },
+///    #   }
+///    # });
+///    #
+///    # fn main() {
+///    // error: `engine` is already set, so `engine.power` can't be set
+///    let vehicle = create!(
+///      Vehicle,
+///      engine: Engine { power: 150, cylinders: 6 },
+///      engine.power: 200,
+///    );
+///    # }
+///    ```
+///  - Optionally `; with` followed by a closure, e.g.
+///    `create!(Vehicle, :bike; with |v| v.paint("red"))`.
+///
+///    The closure is called with a mutable reference to the instance once
+///    it's built, for changes which are easier to make on the instance than
+///    through its fields.
+///
 /// # Example
 ///
 /// ```
@@ -201,180 +524,3103 @@
 ///   assert_eq!(trike.number_seats, 1);
 ///   let user = create!(User, double_age: true);
 ///   assert_eq!(user.age, 84);
+///
+///   let bike = create!(Vehicle, :motorbike, ..no_default, registration: "Y12 BKE");
+///   assert_eq!(bike.number_seats, 1);
+///
+///   let bus = create!(Vehicle, number_wheels: default * 2, number_seats: default * 10);
+///   assert_eq!(bus.number_seats, 50);
+///
+///   let is_trike = false;
+///   let bike = create!(Vehicle, :motorbike, :trike if is_trike);
+///   assert_eq!(bike.number_wheels, 2);
+///
+///   let preset = build!(Vehicle, registration: "PRE 5ET", number_seats: 2);
+///   let trike = create!(Vehicle, :trike, ..preset, number_seats: 3);
+///   assert_eq!(trike.registration, "PRE 5ET");
+///   assert_eq!(trike.number_wheels, 3);
+///   assert_eq!(trike.number_seats, 3);
+///
+///   let mut garage = Vec::new();
+///   create!(Vehicle => garage, :motorbike);
+///   assert_eq!(garage[0].number_wheels, 2);
+///
+///   let trike = create!(Vehicle, :motorbike; with |v| v.number_wheels += 1);
+///   assert_eq!(trike.number_wheels, 3);
+/// }
+/// ```
+///
+/// Leaving a field unset with `..no_default` fails to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// struct Vehicle {
+///   registration: &'static str,
+///   number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     registration = "",
+///     number_wheels = 4,
+///   }
+/// });
+///
+/// fn main () {
+///   // error: `..no_default` requires every field to be set by a mixin or an override
+///   let vehicle = create!(Vehicle, ..no_default, registration: "J105 SRA");
+/// }
+/// ```
+///
+/// So does referring to a `lazy` field as `default`:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// struct Session {
+///   token: String,
+/// }
+///
+/// factori!(Session, {
+///   default {
+///     lazy token: String = "secret".to_string(),
+///   }
+/// });
+///
+/// fn main () {
+///   // error: `token` is `lazy` or partial, so `default` can't refer to its
+///   // value in `create!`
+///   let session = create!(Session, token: default + "-2");
 /// }
 /// ```
 ///
 /// [`factori!()`]: macro.factori.html
+/// [`association!()`]: macro.association.html
+/// [`has_many!()`]: macro.has_many.html
+/// [`create_vec!()`]: macro.create_vec.html
+/// [`build!()`]: macro.build.html
+/// [`rng()`]: rng/fn.rng.html
+/// [`create_with_seed!()`]: macro.create_with_seed.html
 #[macro_export]
 macro_rules! create {
   // We define a simple macro so that the documentation doesn't state this
   // is a re-export from factori-imp-impl. This also allows us to write docs here.
   ($($input:tt)*) => {
-      $crate::factori_imp_impl::create!($($input)*);
+      $crate::factori_imp_impl::create!([$crate] $($input)*);
   }
 }
 
-/// A macro to instantiate multiple instances of a factory.
+/// A macro to instantiate an instance of a factory, converting the values
+/// passed to it.
 ///
-/// Supports everything that [`create!()`] supports but additionally takes a
-/// count to create a vec of count instances.
+/// Accepts the same arguments as [`create!()`], but passes every value
+/// through `Into::into`, so that e.g. a `&str` can be given for a `String`
+/// field. Number literals without a suffix, whose type is inferred from the
+/// field anyway, and the values of fields set with a dotted path, e.g.
+/// `db.name: ...`, are given as is.
 ///
-/// Count can be any expression that evaluates into a number.
+/// [`create!()`] doesn't do this, as a value whose type is only known from
+/// the field, such as `Vec::new()` or `"1".parse().unwrap()`, would be
+/// ambiguous once converted.
 ///
 /// # Example
 ///
 /// ```
-/// #  #[macro_use] extern crate factori_imp;
+/// # #[macro_use] extern crate factori_imp;
 /// #
-/// struct Vehicle {
-///     registration: &'static str,
-///     number_wheels: u8,
-///     number_seats: u8,
+/// pub struct User {
+///   name: String,
+///   age: u8,
 /// }
 ///
-/// factori!(Vehicle, {
-///     default {
-///         registration = "",
-///         number_wheels = 4,
-///         number_seats = 5,
-///     }
+/// factori!(User, {
+///   default {
+///     name = "Alice".to_string(),
+///     age = 30,
+///   }
 /// });
 ///
 /// fn main () {
-///     let many_vehicles = create_vec!(Vehicle, 2+5, number_wheels: 2);
-///     assert_eq!(many_vehicles.len(), 7);
+///   let user = create_into!(User, name: "Bob", age: 40);
+///   assert_eq!(user.name, "Bob");
+///   assert_eq!(user.age, 40);
 /// }
 /// ```
+///
+/// [`create!()`]: macro.create.html
 #[macro_export]
-macro_rules! create_vec {
+macro_rules! create_into {
   ($($input:tt)*) => {
-    $crate::factori_imp_impl::create_vec!($($input)*);
+    $crate::factori_imp_impl::create_into!([$crate] $($input)*);
   }
 }
 
-#[doc(hidden)]
-pub use factori_imp_impl;
-
-/// A macro to define a factory for a type.
+/// A macro to assert the values of fields in a factory's defaults.
 ///
-/// The macro accepts:
+/// Creates the factory's defaults, without any mixins or overrides, and
+/// asserts that each of the given fields of the created object equals the
+/// value after `==`. When one doesn't, it panics like `assert_eq!()`, saying
+/// which default changed. This catches the defaults of shared factories
+/// drifting from what the tests rely on.
 ///
-///  - The type to be constructed by the factory.
-///  - A `default { }` block.
+/// # Example
 ///
-///    This provides default values for all fields in the struct.
-///  - A optional `transient { }` block.
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
 ///
-///    This allows using values that are not part of the type in the builder
-///    block, these values can also be set in mixins, see more below.
-///  - Zero or more `mixin name { }` blocks.
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///     electric = false,
+///   }
+/// });
 ///
-///    These provide values to override the default values of one or more
-///    fields. They are typically used to define groups of values which allow
-///    you to quickly create test objects which are in certain states.
+/// fn main () {
+///   assert_factori_default!(Vehicle, number_wheels == 4, electric == false);
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_factori_default {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::assert_factori_default!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate an instance of a factory which can fail to build.
 ///
-///    Multiple mixin blocks can set the same attributes and the precedence is
-///    determined by the order that they are included in calls to [`create!()`].
+/// Accepts the same arguments as [`create!()`], but returns a `Result` with
+/// the error of the factory's `try_builder` block instead of panicking. See
+/// [`factori!()`] for how to define such a factory. For a factory with a
+/// `validate` block, the error is a [`FactoriError`] when the validation
+/// fails.
 ///
-/// [`create!()`]: macro.create.html
+/// It can be used with any other factory, in which case it never fails.
 ///
-/// ## Example
+/// # Example
 ///
 /// ```
 /// # #[macro_use] extern crate factori_imp;
 /// #
-/// struct Order {
-///   id: u64,
-///   shipped: bool,
+/// use std::convert::TryFrom;
+///
+/// pub struct Percentage(u8);
+///
+/// impl TryFrom<u8> for Percentage {
+///   type Error = String;
+///
+///   fn try_from(value: u8) -> Result<Self, String> {
+///     if value > 100 {
+///       return Err(format!("{} is over 100", value));
+///     }
+///     Ok(Percentage(value))
+///   }
 /// }
 ///
-/// factori!(Order, {
+/// factori!(Percentage, {
 ///   default {
-///     id = 1,
-///     shipped = false,
+///     value: u8 = 50,
 ///   }
 ///
-///   mixin shipped {
-///     shipped = true,
+///   try_builder(String) {
+///     Percentage::try_from(value)?
 ///   }
 /// });
 ///
-/// fn main() {
-///   let order = create!(Order, :shipped);
+/// fn main () {
+///   assert_eq!(try_create!(Percentage).unwrap().0, 50);
+///   assert_eq!(try_create!(Percentage, value: 150).err().unwrap(), "150 is over 100");
 /// }
 /// ```
 ///
-/// ## Constructing complex types
-///
-/// Under the hood, the example above constructs `Vehicle` using the struct
-/// literal syntax, passing the values defined in the `default` and `mixin`
-/// blocks.
+/// [`create!()`]: macro.create.html
+/// [`factori!()`]: macro.factori.html
+/// [`FactoriError`]: struct.FactoriError.html
+#[macro_export]
+macro_rules! try_create {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::try_create!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate an instance of a factory with a `builder async`
+/// block.
 ///
-/// This isn't always possible, such as for types which can't be constructed
-/// with struct literal syntax (enums and tuple structs) or types with private
-/// fields. For these more complex types, a `builder` block can be provided to
-/// tell `factori!()` how to turn the fields in the `default` and `mixin`
-/// blocks into the factory's type.
+/// Accepts the same arguments as [`create!()`], but returns a future of the
+/// instance instead. The defaults, mixins and overrides are applied right
+/// away, and only the `builder async` block runs when the future is awaited.
+/// See the [`factori!()`] docs for how to define such a factory.
 ///
-/// When a `builder` block is provided, the fields in `default` define an
-/// anonymous, temporary struct that is used during factory construction. To
-/// achieve this, the types of fields must be provided inside the `default`
-/// block.
+/// It is only available with the `async` feature, which needs Rust 1.75.
 ///
-/// When using `transient` { } block you must also use `builder` block so
-/// that you can change how the type and values are created based on the
-/// transient fields
+/// # Example
 ///
 /// ```
-/// #  #[macro_use] extern crate factori_imp;
+/// # #[macro_use] extern crate factori_imp;
 /// #
-/// pub struct Order(u64, bool);
-///
-/// factori!(Order, {
-///   default {
-///     id: u64 = 1,
-///     shipped: bool = false,
-///   }
+/// # struct Noop;
+/// # impl std::task::Wake for Noop {
+/// #   fn wake(self: std::sync::Arc<Self>) {}
+/// # }
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #   let mut future = Box::pin(future);
+/// #   let waker = std::task::Waker::from(std::sync::Arc::new(Noop));
+/// #   let mut context = std::task::Context::from_waker(&waker);
+/// #   loop {
+/// #     if let std::task::Poll::Ready(value) = future.as_mut().poll(&mut context) {
+/// #       return value;
+/// #     }
+/// #   }
+/// # }
+/// pub struct Client {
+///   url: String,
+/// }
 ///
-///   transient {
-///     base_id: u64 = 0,
-///   }
+/// async fn connect(url: String) -> Client {
+///   Client { url }
+/// }
 ///
-///   builder {
-///     // All fields from default { } and transient { } are in scope here
-///     // with their values. We construct a tuple struct here, but we could
-///     // easily call a method like Order::new().
-///     let id = base_id + id;
-///     Order(id, shipped)
+/// factori!(Client, {
+///   default {
+///     url: String = "http://localhost".to_string(),
 ///   }
 ///
-///   mixin shipped {
-///     shipped = true,
+///   builder async {
+///     connect(url).await
 ///   }
 /// });
 ///
 /// fn main() {
-///   let order = create!(Order, :shipped, id: 2);
-///   let another_order = create!(Order, id: 5, base_id: 200);
-///   assert_eq!(another_order.0, 205);
+///   block_on(async {
+///     let client = create_async!(Client, url: "http://example.com".to_string()).await;
+///     assert_eq!(client.url, "http://example.com");
+///   });
 /// }
 /// ```
-#[macro_export]
-macro_rules! factori {
+///
+/// [`create!()`]: macro.create.html
+/// [`factori!()`]: macro.factori.html
+#[cfg(feature = "async")]
+#[macro_export]
+macro_rules! create_async {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_async!([$crate] $($input)*);
+  }
+}
+
+/// A macro to get the builder of a factory without building it.
+///
+/// Accepts the same arguments as [`create!()`], but returns the factory's
+/// builder with the defaults, mixins and overrides applied instead of the
+/// built type. Pass it to [`Builder::build()`] to get the built type.
+///
+/// When the factory has no `builder` block, the builder is the type itself.
+/// Otherwise the type implements `From` the builder, or `TryFrom` for a
+/// `try_builder` block, so the builder can be passed where an `impl Into`
+/// of the type is expected.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle(u8);
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///   }
+///
+///   builder {
+///     Vehicle(number_wheels)
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+/// });
+///
+/// fn main () {
+///   let builder = build!(Vehicle, :bike);
+///   assert_eq!(builder.number_wheels, 2);
+///
+///   let bike = factori_imp::Builder::build(builder);
+///   assert_eq!(bike.0, 2);
+///
+///   let trike: Vehicle = build!(Vehicle, number_wheels: 3).into();
+///   assert_eq!(trike.0, 3);
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+/// [`Builder::build()`]: trait.Builder.html#tymethod.build
+#[macro_export]
+macro_rules! build {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::build!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate an instance of a factory along with its builder.
+///
+/// Accepts the same arguments as [`create!()`], but returns a tuple of the
+/// instance and a clone of the builder it was built from, as [`build!()`]
+/// returns it. This helps to test the factory itself, by checking the values
+/// of the fields and transient fields that the `builder` block was given.
+///
+/// The builder is only `Clone` when the factory is defined with
+/// `#[factori(clone_builder)]`, which requires every field and transient
+/// field to be `Clone`. Without a `builder` block, the builder is the type
+/// itself, which then has to be `Clone`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   name: String,
+/// }
+///
+/// factori!(#[factori(clone_builder)] Vehicle, {
+///   default {
+///     name: String = "car".to_string(),
+///   }
+///
+///   transient {
+///     brand: String = "Generic".to_string(),
+///   }
+///
+///   builder {
+///     Vehicle { name: format!("{} {}", brand, name) }
+///   }
+///
+///   mixin acme {
+///     brand = "Acme".to_string(),
+///   }
+/// });
+///
+/// fn main () {
+///   let (vehicle, builder) = create_inspect!(Vehicle, :acme);
+///   assert_eq!(vehicle.name, "Acme car");
+///   assert_eq!(builder.brand, "Acme");
+///   assert_eq!(builder.name, "car");
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+/// [`build!()`]: macro.build.html
+#[macro_export]
+macro_rules! create_inspect {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_inspect!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate an instance of a factory as a `Cow`.
+///
+/// Accepts the same arguments as [`create!()`], and returns the instance in
+/// `Cow::Owned`, for APIs taking a `Cow<'_, T>` which are given either
+/// borrowed or owned values. The type must implement `Clone`, as `Cow`
+/// requires it.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// use std::borrow::Cow;
+///
+/// #[derive(Clone)]
+/// pub struct Config {
+///   retries: u8,
+/// }
+///
+/// factori!(Config, {
+///   default {
+///     retries = 3,
+///   }
+///
+///   mixin patient {
+///     retries = 10,
+///   }
+/// });
+///
+/// fn retries(config: Cow<'static, Config>) -> u8 {
+///   config.retries
+/// }
+///
+/// fn main () {
+///   let config = create_cow!(Config, :patient);
+///   assert!(matches!(config, Cow::Owned(_)));
+///   assert_eq!(retries(config), 10);
+///   assert_eq!(retries(create_cow!(Config, retries: 1)), 1);
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! create_cow {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_cow!([$crate] $($input)*);
+  }
+}
+
+/// A macro to get a closure which instantiates a factory, for benchmarks.
+///
+/// Accepts the same arguments as [`create!()`], and returns an
+/// `impl Fn() -> T` creating a new instance with them each time it's called,
+/// which can be passed to a benchmark harness such as criterion's `b.iter`.
+/// The default values, such as ids taken from a counter, and the transient
+/// fields are evaluated anew on each call, as in separate calls to
+/// [`create!()`].
+///
+/// The closure takes the variables used in the values by move. Values which
+/// aren't `Copy` need to be cloned in the values, e.g. `name: name.clone()`,
+/// for the closure to be callable more than once.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///     electric = false,
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+/// });
+///
+/// fn main () {
+///   let create_bike = factori_bench!(Vehicle, :bike);
+///
+///   let start = std::time::Instant::now();
+///   for _ in 0..1000 {
+///     let bike = std::hint::black_box(create_bike());
+///     assert_eq!(bike.number_wheels, 2);
+///   }
+///   println!("1000 bikes in {:?}", start.elapsed());
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! factori_bench {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::factori_bench!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate a factory's defaults without naming its type.
+///
+/// The type is inferred from the context, so it can only be used where the
+/// type is already known, such as a binding with a type annotation or the
+/// return value of a function. When the type can't be inferred, the compiler
+/// will ask for a type annotation.
+///
+/// Unlike [`create!()`], it doesn't accept mixins or overrides: it always
+/// returns the factory's defaults.
+///
+/// In the `default` block of another factory, it instantiates the field from
+/// the factory of the field's type. The type is that of the struct's field
+/// without a `builder` block, or the type given in the `default` block with
+/// one. The field's type must have a factory defined with [`factori!()`],
+/// rather than in a [`factories!()`] group, which has no default factory.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///   }
+/// });
+///
+/// pub struct Garage {
+///   vehicle: Vehicle,
+/// }
+///
+/// factori!(Garage, {
+///   default {
+///     vehicle = create_default!(),
+///   }
+/// });
+///
+/// fn make() -> Vehicle {
+///   create_default!()
+/// }
+///
+/// fn main () {
+///   let vehicle: Vehicle = create_default!();
+///   assert_eq!(vehicle.number_wheels, 4);
+///   assert_eq!(make().number_wheels, 4);
+///   assert_eq!(create!(Garage).vehicle.number_wheels, 4);
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+/// [`factori!()`]: macro.factori.html
+/// [`factories!()`]: macro.factories.html
+#[macro_export]
+macro_rules! create_default {
+  () => {
+    $crate::FactoriDefault::factori_default()
+  };
+}
+
+/// A macro to instantiate an associated object in a factory's defaults.
+///
+/// Accepts the same arguments as [`create!()`] and is meant to be used as a
+/// value in the `default { }` block of a factory.
+///
+/// In factories with a `builder { }` block, the association is created when
+/// building, after the other fields and the transient fields have their
+/// values. This means that its overrides can refer to them, such as to pass a
+/// transient value down to the associated object. It is also not created at
+/// all when the field is overridden.
+///
+/// In factories without a `builder { }` block it is the same as [`create!()`].
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct User {
+///   name: String,
+/// }
+///
+/// factori!(User, {
+///   default {
+///     name = "Richard".to_string(),
+///   }
+/// });
+///
+/// pub struct Post {
+///   title: String,
+///   owner: User,
+/// }
+///
+/// factori!(Post, {
+///   default {
+///     title: String = "Hello".to_string(),
+///     owner: User = association!(User, name: owner_name.clone()),
+///   }
+///
+///   transient {
+///     owner_name: String = "Tom".to_string(),
+///   }
+///
+///   builder {
+///     Post { title, owner }
+///   }
+/// });
+///
+/// fn main () {
+///   let post = create!(Post, owner_name: "John".to_string());
+///   assert_eq!(post.owner.name, "John");
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! association {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate an instance of a factory with a seeded random
+/// number generator.
+///
+/// Takes the type, a `u64` seed and then everything that [`create!()`]
+/// supports. While the factory runs, [`rng()`] is seeded with the seed, so
+/// randomized values are the same for every call with the same seed. This
+/// makes snapshot tests of randomized data stable.
+///
+/// Calls to [`create!()`] and other macros use an unseeded generator, which
+/// isn't affected by `create_with_seed!()`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct User {
+///   id: u64,
+/// }
+///
+/// factori!(User, {
+///   default {
+///     id = factori_imp::rng().next_u64(),
+///   }
+/// });
+///
+/// fn main () {
+///   let user = create_with_seed!(User, 1234);
+///   let same_user = create_with_seed!(User, 1234);
+///   assert_eq!(user.id, same_user.id);
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+/// [`rng()`]: rng/fn.rng.html
+#[macro_export]
+macro_rules! create_with_seed {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_with_seed!([$crate] $($input)*);
+  }
+}
+
+/// A macro to get a shared instance of a factory.
+///
+/// Accepts the same arguments as [`create!()`], but only builds the factory
+/// the first time it's called for a type with these arguments. It then
+/// returns a clone of the same `Arc` on every such call. This is useful for expensive fixtures that are
+/// never modified.
+///
+/// The instance is shared by the calls made on the same thread, each thread
+/// builds its own. As the tests of a test binary run on their own threads,
+/// they don't share instances with each other.
+///
+/// Calls with the same mixins and fields share an instance, and calls with
+/// other ones get an instance of their own. They are compared as written in
+/// the call, so `url: url` shares the instance built the first time whatever
+/// the value of `url` is then. Use [`reset_shared!()`] to remove the
+/// instances of a type, so that the next calls build new ones.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Config {
+///   url: &'static str,
+/// }
+///
+/// factori!(Config, {
+///   default {
+///     url = "http://localhost",
+///   }
+/// });
+///
+/// fn main () {
+///   let config = create_shared!(Config);
+///   let same_config = create_shared!(Config);
+///   assert!(std::sync::Arc::ptr_eq(&config, &same_config));
+///
+///   let other_config = create_shared!(Config, url: "http://example.com");
+///   assert_eq!(other_config.url, "http://example.com");
+///
+///   reset_shared!(Config);
+///   let new_config = create_shared!(Config);
+///   assert!(!std::sync::Arc::ptr_eq(&config, &new_config));
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+/// [`reset_shared!()`]: macro.reset_shared.html
+#[macro_export]
+macro_rules! create_shared {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_shared!([$crate] $($input)*);
+  }
+}
+
+/// A macro to remove the shared instances of a factory on the current thread.
+///
+/// The next calls to [`create_shared!()`] for the type build new instances.
+///
+/// [`create_shared!()`]: macro.create_shared.html
+#[macro_export]
+macro_rules! reset_shared {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::reset_shared!([$crate] $($input)*);
+  }
+}
+
+/// A macro to reset the state kept by the factories between tests.
+///
+/// It resets the state of the current thread: it removes every instance
+/// shared by [`create_shared!()`], as [`reset_shared!()`] does for a single
+/// type, takes the objects created out of the registry with the `registry`
+/// feature, as [`factori_drain!()`] does, and runs the hooks registered with
+/// [`reset::register()`]. The tests running in parallel on other threads are
+/// left alone.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Config {
+///   url: &'static str,
+/// }
+///
+/// factori!(Config, {
+///   default {
+///     url = "http://localhost",
+///   }
+/// });
+///
+/// fn main () {
+///   let config = create_shared!(Config);
+///
+///   factori_reset_all!();
+///   let new_config = create_shared!(Config);
+///   assert!(!std::sync::Arc::ptr_eq(&config, &new_config));
+/// }
+/// ```
+///
+/// [`create_shared!()`]: macro.create_shared.html
+/// [`reset_shared!()`]: macro.reset_shared.html
+/// [`factori_drain!()`]: macro.factori_drain.html
+/// [`reset::register()`]: reset/fn.register.html
+#[macro_export]
+macro_rules! factori_reset_all {
+  () => {
+    $crate::reset::reset_all()
+  };
+}
+
+/// A macro to repeat a value a number of times in a factory's defaults.
+///
+/// `repeat!(value, count)` evaluates `value` `count` times and collects the
+/// results, typically into a `Vec`. As the value is evaluated for each
+/// element, `repeat!(association!(Seat), 4)` creates four distinct seats.
+///
+/// Like [`association!()`], in factories with a `builder { }` block it is
+/// evaluated when building, so both the value and the count can refer to the
+/// other fields and the transient fields.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Seat {
+///   number: u8,
+/// }
+///
+/// factori!(Seat, {
+///   default {
+///     number = 1,
+///   }
+/// });
+///
+/// pub struct Vehicle {
+///   seats: Vec<Seat>,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     seats: Vec<Seat> = repeat!(association!(Seat), number_seats),
+///   }
+///
+///   transient {
+///     number_seats: usize = 4,
+///   }
+///
+///   builder {
+///     Vehicle { seats }
+///   }
+/// });
+///
+/// fn main () {
+///   assert_eq!(create!(Vehicle).seats.len(), 4);
+///   assert_eq!(create!(Vehicle, number_seats: 2).seats.len(), 2);
+/// }
+/// ```
+///
+/// [`association!()`]: macro.association.html
+#[macro_export]
+macro_rules! repeat {
+  ($value:expr, $count:expr $(,)?) => {
+    (0..$count).map(|_| $value).collect()
+  };
+}
+
+/// A macro to instantiate many associated objects in a factory's defaults.
+///
+/// Accepts the same arguments as [`create_vec!()`], e.g.
+/// `has_many!(Seat, 4, heated: true)`, and is meant to be used as a value in
+/// the `default { }` block of a factory. It is a shorthand for
+/// `repeat!(association!(Seat, heated: true), 4)`, named after FactoryBot's
+/// `has_many`.
+///
+/// Like [`association!()`], in factories with a `builder { }` block it is
+/// evaluated when building, so the count and the overrides can refer to the
+/// other fields and the transient fields. The objects aren't created at all
+/// when the field is overridden, e.g. with another `has_many!()`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Seat {
+///   heated: bool,
+/// }
+///
+/// factori!(Seat, {
+///   default {
+///     heated = false,
+///   }
+/// });
+///
+/// pub struct Vehicle {
+///   seats: Vec<Seat>,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     seats: Vec<Seat> = has_many!(Seat, number_seats, heated: heated_seats),
+///   }
+///
+///   transient {
+///     number_seats: usize = 4,
+///     heated_seats: bool = false,
+///   }
+///
+///   builder {
+///     Vehicle { seats }
+///   }
+/// });
+///
+/// fn main () {
+///   assert_eq!(create!(Vehicle).seats.len(), 4);
+///   assert_eq!(create!(Vehicle, number_seats: 2).seats.len(), 2);
+///   assert_eq!(create!(Vehicle, seats: has_many!(Seat, 5)).seats.len(), 5);
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+/// [`association!()`]: macro.association.html
+#[macro_export]
+macro_rules! has_many {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_vec!([$crate] $($input)*);
+  }
+}
+
+/// A macro to evaluate a default value only once, for every instance of a
+/// factory.
+///
+/// It is meant to be used as a value in the `default { }` block of a
+/// factory, e.g. `base_url: String = once!(load_config().base_url)`, for
+/// values which are expensive to get, such as from a configuration file. The
+/// value is evaluated the first time it's needed and kept in a static, and
+/// every instance gets a clone of it.
+///
+/// The type of the field must be given, and must be `Clone` and `Send`.
+/// It can't be used in generic factories, and it's an error anywhere
+/// else than in the `default` block.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// static LOADS: AtomicU32 = AtomicU32::new(0);
+///
+/// fn load_base_url() -> String {
+///   LOADS.fetch_add(1, Ordering::SeqCst);
+///   "http://localhost:8080".to_string()
+/// }
+///
+/// pub struct Client {
+///   base_url: String,
+/// }
+///
+/// factori!(Client, {
+///   default {
+///     base_url: String = once!(load_base_url()),
+///   }
+/// });
+///
+/// fn main () {
+///   assert_eq!(create!(Client).base_url, "http://localhost:8080");
+///   assert_eq!(create!(Client).base_url, "http://localhost:8080");
+///   assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+/// }
+/// ```
+///
+/// Without the type of the field, it fails to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Client {
+///   timeout: u32,
+/// }
+///
+/// factori!(Client, {
+///   default {
+///     // error: Type must be specified when using `once!()`.
+///     timeout = once!(30),
+///   }
+/// });
+/// #
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! once {
+  ($($input:tt)*) => {
+    compile_error!("`once!()` can only be used as a value in the `default` block of a factory")
+  };
+}
+
+/// A macro to clone a value for every instance of a factory.
+///
+/// The values of the `default { }` block are evaluated for every instance,
+/// so a value which is shared by the instances, such as a static, can't be
+/// moved into them. `clone!(value)` clones it instead, e.g.
+/// `name = clone!(DEFAULT_NAME)`. The value is borrowed and cloned with
+/// `.clone()`, so a static initialized lazily, e.g. with `OnceLock`, is
+/// cloned through the reference to it.
+///
+/// It can be used anywhere else as well, e.g. in mixins or in the values
+/// passed to [`create!()`].
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// use std::borrow::Cow;
+///
+/// static DEFAULT_NAME: Cow<'static, str> = Cow::Borrowed("Lamp");
+///
+/// pub struct Product {
+///   name: Cow<'static, str>,
+/// }
+///
+/// factori!(Product, {
+///   default {
+///     name = clone!(DEFAULT_NAME),
+///   }
+/// });
+///
+/// fn main () {
+///   assert_eq!(create!(Product).name, "Lamp");
+///   assert_eq!(create!(Product).name, "Lamp");
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! clone {
+  ($value:expr $(,)?) => {
+    ($value).clone()
+  };
+}
+
+/// A macro to instantiate multiple instances of a factory.
+///
+/// Supports everything that [`create!()`] supports but additionally takes a
+/// count to create a vec of count instances.
+///
+/// Count can be any expression that evaluates into a number, including a
+/// block. It can also be a closure without arguments, which is called once to
+/// get the count. A negative literal count is a compile error, while other
+/// expressions evaluating to a negative number create an empty vec, as the
+/// range up to them is empty.
+///
+/// A closure can be passed last, which is called once with a mutable
+/// reference to the created vec, e.g. to link its elements together.
+///
+/// Instead of a count, a list can be passed with the arguments of
+/// [`create!()`] for each element in parentheses, e.g.
+/// `create_vec!(Vehicle, [(:bike), (:bike), (), ()])`. The vec has as many
+/// elements as the list.
+///
+/// Also instead of a count, the values of some fields can be given for each
+/// element with `=`, e.g. `create_vec!(Vehicle, registration = ["A", "B"])`,
+/// followed by the other arguments of [`create!()`]. The values can be any
+/// expression which can be iterated over, such as an array or a `Vec`, and
+/// the vec has as many elements as there are values. All of the fields given
+/// this way must have the same number of values: it's a compile error for
+/// arrays, and a panic otherwise.
+///
+/// As a value in the `default { }` block of a factory with a `builder { }`
+/// block, it is evaluated when building, like [`has_many!()`], so the count
+/// can be a transient field, e.g. `create_vec!(Seat, number_seats)`.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// struct Vehicle {
+///     registration: &'static str,
+///     number_wheels: u8,
+///     number_seats: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///     default {
+///         registration = "",
+///         number_wheels = 4,
+///         number_seats = 5,
+///     }
+/// });
+///
+/// fn main () {
+///     let many_vehicles = create_vec!(Vehicle, 2+5, number_wheels: 2);
+///     assert_eq!(many_vehicles.len(), 7);
+///
+///     let lazily_counted = create_vec!(Vehicle, || many_vehicles.len() - 4);
+///     assert_eq!(lazily_counted.len(), 3);
+///
+///     let registered = create_vec!(Vehicle, 3, |vehicles| {
+///         vehicles.iter_mut().for_each(|vehicle| vehicle.registration = "REG");
+///     });
+///     assert!(registered.iter().all(|vehicle| vehicle.registration == "REG"));
+///
+///     let mixed = create_vec!(Vehicle, [(number_wheels: 2), (number_wheels: 2), (), ()]);
+///     assert_eq!(mixed.len(), 4);
+///     assert_eq!(mixed[1].number_wheels, 2);
+///     assert_eq!(mixed[2].number_wheels, 4);
+///
+///     let registered = create_vec!(Vehicle, registration = ["A", "B", "C"], number_wheels: 2);
+///     assert_eq!(registered.len(), 3);
+///     assert_eq!(registered[1].registration, "B");
+///     assert_eq!(registered[1].number_wheels, 2);
+/// }
+/// ```
+///
+/// A negative count fails to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// struct Vehicle {
+///     number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///     default {
+///         number_wheels = 4,
+///     }
+/// });
+///
+/// fn main () {
+///     // error: the count can't be negative
+///     let vehicles = create_vec!(Vehicle, -3);
+/// }
+/// ```
+///
+/// [`has_many!()`]: macro.has_many.html
+#[macro_export]
+macro_rules! create_vec {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_vec!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate many objects from a factory with a
+/// `builder async` block.
+///
+/// Accepts the same arguments as [`create_vec!()`], but returns a future of
+/// the vec instead. The elements are built one after the other, in order, so
+/// that they are created in the same order as with [`create_vec!()`]. The
+/// closure passed last, if any, is called once they are all built.
+///
+/// It is only available with the `async` feature, which needs Rust 1.75.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// # struct Noop;
+/// # impl std::task::Wake for Noop {
+/// #   fn wake(self: std::sync::Arc<Self>) {}
+/// # }
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #   let mut future = Box::pin(future);
+/// #   let waker = std::task::Waker::from(std::sync::Arc::new(Noop));
+/// #   let mut context = std::task::Context::from_waker(&waker);
+/// #   loop {
+/// #     if let std::task::Poll::Ready(value) = future.as_mut().poll(&mut context) {
+/// #       return value;
+/// #     }
+/// #   }
+/// # }
+/// pub struct Client {
+///   url: String,
+/// }
+///
+/// factori!(Client, {
+///   default {
+///     url: String = "http://localhost".to_string(),
+///   }
+///
+///   builder async {
+///     Client { url }
+///   }
+/// });
+///
+/// fn main() {
+///   block_on(async {
+///     let clients = create_vec_async!(Client, 3).await;
+///     assert_eq!(clients.len(), 3);
+///
+///     let clients = create_vec_async!(Client, [(), (url: "http://example.com".to_string())]).await;
+///     assert_eq!(clients[1].url, "http://example.com");
+///   });
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+#[cfg(feature = "async")]
+#[macro_export]
+macro_rules! create_vec_async {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_vec_async!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple instances of a factory into an existing
+/// vec.
+///
+/// Takes a `&mut Vec` first, followed by the same arguments as
+/// [`create_vec!()`] except for the closure over the vec. The instances are
+/// pushed at the end of the vec, so that a vec can be reused, e.g. across
+/// the iterations of a benchmark, rather than allocating a new one.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// struct Vehicle {
+///     number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///     default {
+///         number_wheels = 4,
+///     }
+///
+///     mixin bike {
+///         number_wheels = 2,
+///     }
+/// });
+///
+/// fn main () {
+///     let mut vehicles = Vec::with_capacity(100);
+///     for _ in 0..3 {
+///         vehicles.clear();
+///         create_into_vec!(&mut vehicles, Vehicle, 100, :bike);
+///         assert_eq!(vehicles.len(), 100);
+///     }
+///
+///     create_into_vec!(&mut vehicles, Vehicle, [(), (number_wheels: 3)]);
+///     assert_eq!(vehicles.len(), 102);
+///     assert_eq!(vehicles[101].number_wheels, 3);
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+#[macro_export]
+macro_rules! create_into_vec {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_into_vec!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple instances of a factory into any
+/// collection.
+///
+/// Takes the type of the collection first, which implements `FromIterator`,
+/// such as `VecDeque<_>` or `HashSet<_>`, followed by the same arguments as
+/// [`create_vec!()`] except for the closure over the vec. The element type can
+/// be left as `_`, as it's the factory's type. A `HashSet` needs the type to
+/// implement `Hash` and `Eq`, and instances which are equal are only kept
+/// once.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// use std::collections::{HashSet, VecDeque};
+///
+/// #[derive(PartialEq, Eq, Hash)]
+/// struct Vehicle {
+///     number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///     default {
+///         number_wheels = 4,
+///     }
+///
+///     mixin bike {
+///         number_wheels = 2,
+///     }
+/// });
+///
+/// fn main () {
+///     let queue = create_collection!(VecDeque<_>, Vehicle, 3, :bike);
+///     assert_eq!(queue.len(), 3);
+///
+///     let set = create_collection!(HashSet<_>, Vehicle, [(), (:bike), (number_wheels: 2)]);
+///     assert_eq!(set.len(), 2);
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+#[macro_export]
+macro_rules! create_collection {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_collection!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple instances of a factory whose values of a
+/// field are all different.
+///
+/// Takes the type, the count and `key = field`, followed by the same
+/// arguments as [`create!()`]. Instances whose field has the same value as
+/// one of the instances before them are dropped, and more are created in
+/// their place, which is meant for factories with random or otherwise
+/// colliding values. The field's type must implement `Clone`, `Eq` and
+/// `Hash`. The count is a `usize`.
+///
+/// To avoid looping forever when there aren't enough distinct values, it
+/// panics after ten times as many attempts as the count.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// static NEXT: AtomicU32 = AtomicU32::new(0);
+///
+/// struct User {
+///     email: String,
+/// }
+///
+/// factori!(User, {
+///     default {
+///         // Only 5 distinct values, every other one repeating the last
+///         email = format!("user{}@example.com", NEXT.fetch_add(1, Ordering::SeqCst) / 2),
+///     }
+/// });
+///
+/// fn main () {
+///     let users = create_vec_unique!(User, 5, key = email);
+///     assert_eq!(users.len(), 5);
+///     assert_eq!(users[4].email, "user4@example.com");
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! create_vec_unique {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_vec_unique!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple copies of a single instance of a factory.
+///
+/// Takes the same arguments as [`create_vec!()`], but only runs the factory
+/// once and then clones the result, so the factory's type must implement
+/// `Clone`.
+///
+/// This is cheaper than [`create_vec!()`] when the defaults or the `builder`
+/// block are expensive. However, as the factory only runs once, any side
+/// effects in it (such as incrementing a counter to give each instance a
+/// unique value) happen once rather than once per element. Use
+/// [`create_vec!()`] when every element needs to be built independently.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// #[derive(Clone)]
+/// struct Vehicle {
+///     number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///     default {
+///         number_wheels = 4,
+///     }
+///
+///     mixin bike {
+///         number_wheels = 2,
+///     }
+/// });
+///
+/// fn main () {
+///     let bikes = create_vec_clone!(Vehicle, 1000, :bike);
+///     assert_eq!(bikes.len(), 1000);
+///     assert_eq!(bikes[999].number_wheels, 2);
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+#[macro_export]
+macro_rules! create_vec_clone {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_vec_clone!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple instances of a factory from clones of a
+/// single builder.
+///
+/// Takes the same arguments as [`create_vec!()`], but only evaluates the
+/// defaults, mixins and overrides once, and then builds each element from a
+/// clone of the resulting builder. Unlike [`create_vec_clone!()`], the
+/// `builder` block still runs once per element, and the factory's type
+/// doesn't need to be `Clone`.
+///
+/// The builder is only `Clone` when the factory is defined with
+/// `#[factori(clone_builder)]`, which requires every field and transient
+/// field to be `Clone`. Without a `builder` block, the builder is the type
+/// itself, which then has to be `Clone`. As with [`create_vec_clone!()`],
+/// side effects in the default values happen once rather than once per
+/// element.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///     id: usize,
+///     number_wheels: u8,
+/// }
+///
+/// factori!(#[factori(clone_builder)] Vehicle, {
+///     default {
+///         number_wheels: u8 = 4,
+///     }
+///
+///     builder {
+///         static IDS: std::sync::atomic::AtomicUsize =
+///             std::sync::atomic::AtomicUsize::new(0);
+///         let id = IDS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+///         Vehicle { id, number_wheels }
+///     }
+///
+///     mixin bike {
+///         number_wheels = 2,
+///     }
+/// });
+///
+/// fn main () {
+///     let bikes = create_vec_from_builder!(Vehicle, 3, :bike);
+///     assert_eq!(bikes.len(), 3);
+///     assert_eq!(bikes[2].number_wheels, 2);
+///     assert_ne!(bikes[0].id, bikes[1].id);
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+/// [`create_vec_clone!()`]: macro.create_vec_clone.html
+#[macro_export]
+macro_rules! create_vec_from_builder {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_vec_from_builder!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple instances of a factory and map each of
+/// them.
+///
+/// Takes the same arguments as [`create_vec!()`], followed by a closure which
+/// is called with each instance. Returns a `Vec` of what the closure returns.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// struct Vehicle {
+///     number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///     default {
+///         number_wheels = 4,
+///     }
+///
+///     mixin bike {
+///         number_wheels = 2,
+///     }
+/// });
+///
+/// fn main () {
+///     let wheels: Vec<u8> = create_mapped!(Vehicle, 3, :bike, |vehicle| vehicle.number_wheels);
+///     assert_eq!(wheels, [2, 2, 2]);
+/// }
+/// ```
+///
+/// [`create_vec!()`]: macro.create_vec.html
+#[macro_export]
+macro_rules! create_mapped {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_mapped!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate multiple instances of a factory in a `HashMap`,
+/// keyed by one of their fields.
+///
+/// Takes the type, the count and `key = field`, followed by everything that
+/// [`create!()`] supports. The key is cloned from the field of every instance,
+/// so its type must implement `Clone`, `Hash` and `Eq`, and the field must be
+/// accessible where the macro is used.
+///
+/// Instances with the same key replace each other, so the key should be
+/// unique, e.g. a field using a sequence.
+///
+/// # Example
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+///
+/// struct User {
+///     id: u32,
+///     admin: bool,
+/// }
+///
+/// factori!(User, {
+///     default {
+///         id = NEXT_ID.fetch_add(1, Ordering::Relaxed),
+///         admin = false,
+///     }
+/// });
+///
+/// fn main () {
+///     let admins = create_hashmap!(User, 3, key = id, admin: true);
+///     assert_eq!(admins.len(), 3);
+///     assert!(admins.values().all(|user| user.admin));
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! create_hashmap {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_hashmap!([$crate] $($input)*);
+  }
+}
+
+/// A macro to instantiate objects of different factories as trait objects.
+///
+/// Takes the type of the elements, e.g. `dyn Trait`, and a list of the
+/// elements. Each element is either the type of a factory, or the arguments
+/// of [`create!()`] in parentheses. The elements are created in order and
+/// boxed into a `Vec<Box<dyn Trait>>`, so the type of each factory must
+/// implement the trait.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// trait Shape {
+///   fn area(&self) -> u32;
+/// }
+///
+/// pub struct Square {
+///   side: u32,
+/// }
+///
+/// impl Shape for Square {
+///   fn area(&self) -> u32 {
+///     self.side * self.side
+///   }
+/// }
+///
+/// pub struct Rectangle {
+///   width: u32,
+///   height: u32,
+/// }
+///
+/// impl Shape for Rectangle {
+///   fn area(&self) -> u32 {
+///     self.width * self.height
+///   }
+/// }
+///
+/// factori!(Square, {
+///   default {
+///     side = 1,
+///   }
+/// });
+///
+/// factori!(Rectangle, {
+///   default {
+///     width = 2,
+///     height = 3,
+///   }
+///
+///   mixin wide {
+///     width = 10,
+///   }
+/// });
+///
+/// fn main () {
+///   let shapes = create_dyn_vec!(dyn Shape, [Square, (Square, side: 2), (Rectangle, :wide)]);
+///   let areas: Vec<_> = shapes.iter().map(|shape| shape.area()).collect();
+///   assert_eq!(areas, [1, 4, 30]);
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! create_dyn_vec {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_dyn_vec!([$crate] $($input)*);
+  }
+}
+
+#[doc(hidden)]
+pub use factori_imp_impl;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_json;
+
+/// A macro to define a factory for a type.
+///
+/// The macro accepts:
+///
+///  - The type to be constructed by the factory.
+///
+///    This can be a path such as `other_crate::Vehicle`, in which case the
+///    factory is named after the last segment and used as
+///    `create!(Vehicle)`. Factories for types from other crates must have a
+///    `builder { }` block, see below.
+///  - A `default { }` block.
+///
+///    This provides default values for all fields in the struct. It is the
+///    only block that is required, so the smallest factory is
+///    `factori!(Vehicle, { default { ... } })`. With a `builder { }` block,
+///    it can be empty, e.g. when the builder only uses transient fields or
+///    constants.
+///  - A optional `transient { }` block.
+///
+///    This allows using values that are not part of the type in the builder
+///    block, these values can also be set in mixins, see more below. Their
+///    values can be left out to require them to be set.
+///  - Zero or more `mixin name { }` blocks.
+///
+///    These provide values to override the default values of one or more
+///    fields. They are typically used to define groups of values which allow
+///    you to quickly create test objects which are in certain states.
+///
+///    Multiple mixin blocks can set the same attributes and the precedence is
+///    determined by the order that they are included in calls to [`create!()`].
+///
+///    Mixins can only set fields of the `default { }` and `transient { }`
+///    blocks, so a typo in a mixin is an error where the factory is defined:
+///
+///    ```compile_fail
+///    # #[macro_use] extern crate factori_imp;
+///    #
+///    struct Vehicle {
+///      color: &'static str,
+///    }
+///
+///    factori!(Vehicle, {
+///      default {
+///        color = "blue",
+///      }
+///
+///      mixin red {
+///        // error: `colour` is not a field of the default {} or transient {}
+///        // block, so mixin `red` can't set it
+///        colour = "red",
+///      }
+///    });
+///    #
+///    # fn main() {}
+///    ```
+///
+/// Fields in any of these blocks can be preceded by outer attributes, such as
+/// `#[cfg(feature = "electric")]`. They are forwarded to the generated fields,
+/// so a field which only exists under some configuration can be gated in the
+/// factory the same way as it is in the struct.
+///
+/// [`create!()`]: macro.create.html
+/// [`factories!()`]: macro.factories.html
+///
+/// ## Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// struct Order {
+///   id: u64,
+///   shipped: bool,
+/// }
+///
+/// factori!(Order, {
+///   default {
+///     id = 1,
+///     shipped = false,
+///   }
+///
+///   mixin shipped {
+///     shipped = true,
+///   }
+/// });
+///
+/// fn main() {
+///   let order = create!(Order, :shipped);
+/// }
+/// ```
+///
+/// ## Renaming fields
+///
+/// A field of the `default { }` block can be named differently than the
+/// struct's field with `as`, such as when the struct's field is a keyword. The
+/// factory's name is used in mixins and in calls to [`create!()`]. Types
+/// must be specified when renaming fields.
+///
+/// Renaming isn't needed to use keywords as names though: raw identifiers
+/// such as `r#type` work everywhere a field or mixin name is expected. The
+/// names of mixins are listed without the `r#` prefix by `FactoriMixins`.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Token {
+///   r#type: u8,
+/// }
+///
+/// factori!(Token, {
+///   default {
+///     kind as r#type: u8 = 1,
+///   }
+/// });
+///
+/// fn main() {
+///   let token = create!(Token, kind: 2);
+///   assert_eq!(token.r#type, 2);
+/// }
+/// ```
+///
+/// ## Generic types
+///
+/// A factory can be generic over a single type parameter, written with its
+/// bounds in place of the type's argument, e.g. `Wrapper<T: Default>`. The
+/// bounds are required to tell the parameter apart from a concrete type,
+/// `T: Sized` can be used if there's nothing else to require. The type
+/// parameter is inferred where the factory is instantiated.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Wrapper<T> {
+///   inner: T,
+/// }
+///
+/// factori!(Wrapper<T: Default>, {
+///   default {
+///     inner = Default::default(),
+///   }
+/// });
+///
+/// fn main() {
+///   let number: Wrapper<i32> = create!(Wrapper);
+///   assert_eq!(number.inner, 0);
+///
+///   let text = create!(Wrapper, inner: "text");
+///   assert_eq!(text.inner, "text");
+/// }
+/// ```
+///
+/// ## Const generics
+///
+/// Types with const generics get a factory per instantiation, e.g.
+/// `Buffer<16>`. The instantiation is then passed to [`create!()`] and the
+/// other macros, as in `create!(Buffer<16>)`.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Buffer<const N: usize> {
+///   data: [u8; N],
+/// }
+///
+/// factori!(Buffer<16>, {
+///   default {
+///     data = [0u8; 16],
+///   }
+/// });
+///
+/// factori!(Buffer<4>, {
+///   default {
+///     data = [1u8; 4],
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(Buffer<16>).data, [0u8; 16]);
+///   assert_eq!(create!(Buffer<4>).data, [1u8; 4]);
+/// }
+/// ```
+///
+/// ## Evaluation order
+///
+/// The default values are evaluated in the order of the `default` block,
+/// whatever the order of the struct's fields, followed by those of the
+/// `transient` block. The values of mixins are evaluated after all of them,
+/// and `lazy` fields last, when the instance is built.
+///
+/// ## Lazy fields
+///
+/// The default value of a field marked `lazy` is only evaluated if neither
+/// a mixin nor [`create!()`] sets the field, which avoids running expensive
+/// or side-effecting defaults needlessly. Types must be specified when a
+/// field is `lazy`.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Session {
+///   token: String,
+/// }
+///
+/// fn expensive_token() -> String {
+///   panic!("only called if the token isn't set");
+/// }
+///
+/// factori!(Session, {
+///   default {
+///     lazy token: String = expensive_token(),
+///   }
+/// });
+///
+/// fn main() {
+///   let session = create!(Session, token: "abc".to_string());
+///   assert_eq!(session.token, "abc");
+/// }
+/// ```
+///
+/// ## Partial fields
+///
+/// The fields of a `partial { }` block have no default, for fields which the
+/// code under test sets later. They are left unset in the builder until a
+/// mixin, [`create!()`] or the builder's setter gives them a value, and
+/// building it while one of them is still unset panics with the name of the
+/// field. As with `lazy` fields, the types of all of the fields must be
+/// specified.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct User {
+///   name: &'static str,
+///   email: String,
+/// }
+///
+/// factori!(User, {
+///   default {
+///     name: &'static str = "Alice",
+///   }
+///
+///   partial {
+///     email: String,
+///   }
+/// });
+///
+/// fn main() {
+///   let builder = build!(User);
+///   let user = builder.email("alice@example.com".to_string()).build();
+///   assert_eq!(user.email, "alice@example.com");
+///
+///   let user = create!(User, email: "bob@example.com".to_string());
+///   assert_eq!(user.email, "bob@example.com");
+/// }
+/// ```
+///
+/// ```should_panic
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// # pub struct User {
+/// #   name: &'static str,
+/// #   email: String,
+/// # }
+/// #
+/// # factori!(User, {
+/// #   default {
+/// #     name: &'static str = "Alice",
+/// #   }
+/// #
+/// #   partial {
+/// #     email: String,
+/// #   }
+/// # });
+/// #
+/// # fn main() {
+/// // panics: the partial field `email` isn't set, it must be set by a mixin,
+/// // in `create!` or with the builder's setter
+/// let user = create!(User);
+/// # }
+/// ```
+///
+/// ## Mapped fields
+///
+/// A field of the `default` block can be followed by `map` and a closure,
+/// which is applied to the field's final value when the instance is built,
+/// whether it comes from the default, a mixin or [`create!()`]. This
+/// normalizes the values given for the field. Types must be specified when a
+/// field has a `map`, and the builders returned by [`build!()`] have the
+/// values before they are mapped.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct User {
+///   email: String,
+/// }
+///
+/// factori!(User, {
+///   default {
+///     email: String = "user@example.com".to_string() map |email| email.trim().to_lowercase(),
+///   }
+///
+///   mixin admin {
+///     email = "ADMIN@example.com".to_string(),
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(User, :admin).email, "admin@example.com");
+///   assert_eq!(create!(User, email: " Bob@Example.com ".to_string()).email, "bob@example.com");
+/// }
+/// ```
+///
+/// ## Fields which can't be overridden
+///
+/// A field marked `#[factori(skip)]` can't be set in [`create!()`] and the
+/// other macros, which is a compile error. This keeps the invariants of the
+/// factory, e.g. for a field computed from the others in the `builder` block.
+/// The mixins of the factory can still set it.
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Document {
+///   body: String,
+///   length: usize,
+/// }
+///
+/// factori!(Document, {
+///   default {
+///     body: String = "Hello".to_string(),
+///     #[factori(skip)]
+///     length: usize = 0,
+///   }
+///
+///   builder {
+///     let length = body.len();
+///     Document { body, length }
+///   }
+/// });
+///
+/// fn main() {
+///   // error: `length` is marked `#[factori(skip)]` in the factory, so it can't be overridden
+///   let document = create!(Document, length: 3);
+/// }
+/// ```
+///
+/// ## Constructing complex types
+///
+/// Under the hood, the example above constructs `Vehicle` using the struct
+/// literal syntax, passing the values defined in the `default` and `mixin`
+/// blocks.
+///
+/// This isn't always possible, such as for types which can't be constructed
+/// with struct literal syntax (enums and tuple structs) or types with private
+/// fields. For these more complex types, a `builder` block can be provided to
+/// tell `factori!()` how to turn the fields in the `default` and `mixin`
+/// blocks into the factory's type.
+///
+/// Structs marked `#[non_exhaustive]` can be constructed with struct literal
+/// syntax in the crate that defines them, so they don't need a `builder`
+/// block there. From other crates, the factory needs a `builder` block
+/// calling one of the type's constructors.
+///
+/// When a `builder` block is provided, the fields in `default` define an
+/// anonymous, temporary struct that is used during factory construction. To
+/// achieve this, the types of fields must be provided inside the `default`
+/// block.
+///
+/// When using `transient` { } block you must also use `builder` block so
+/// that you can change how the type and values are created based on the
+/// transient fields, otherwise it is a compile error:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Order(u64);
+///
+/// factori!(Order, {
+///   default {
+///     id: u64 = 1,
+///   }
+///
+///   // error: transient {} requires a builder {} block
+///   transient {
+///     base_id: u64 = 0,
+///   }
+/// });
+/// #
+/// # fn main() {}
+/// ```
+///
+/// Mixins can set transient fields as well as the other fields, even in the
+/// same mixin. Both kinds of fields follow the same precedence: each mixin
+/// overrides the values set by the mixins before it, and the values passed
+/// to [`create!()`] override all of them. The `builder` block only runs once
+/// at the end, with the resulting values.
+///
+/// A transient field can be required by leaving out its value, e.g.
+/// `tenant_id: u64`. It must then be set in each call to [`create!()`] or by
+/// one of its mixins which isn't conditional, otherwise it is a compile
+/// error. [`create_default!()`] and the other ways to build the factory which
+/// don't take fields panic instead.
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Document {
+///   tenant_id: u64,
+/// }
+///
+/// factori!(Document, {
+///   default {
+///     title: &'static str = "Untitled",
+///   }
+///
+///   transient {
+///     tenant: u64,
+///   }
+///
+///   builder {
+///     Document { tenant_id: tenant }
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(Document, tenant: 1).tenant_id, 1);
+///
+///   // error: the transient field `tenant` is required, it must be set by a
+///   // mixin or in `create!`
+///   create!(Document);
+/// }
+/// ```
+///
+/// A mixin can also `reset` a field, which sets it back to the value of the
+/// `default` or `transient` block, evaluated again. This follows the same
+/// precedence, so it undoes the mixins before it but not the ones after it:
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///     electric = false,
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+///
+///   mixin stock {
+///     reset number_wheels,
+///     reset electric,
+///   }
+/// });
+///
+/// fn main() {
+///   let vehicle = create!(Vehicle, :bike, :stock);
+///   assert_eq!(vehicle.number_wheels, 4);
+///
+///   let vehicle = create!(Vehicle, :stock, :bike);
+///   assert_eq!(vehicle.number_wheels, 2);
+/// }
+/// ```
+///
+/// ## Computed bindings
+///
+/// A `computed` block holds `let` statements which run before the `builder`
+/// block, after the fields and the transient fields are bound, so that the
+/// values they compute can be used in it. Unlike transient fields, they
+/// can't be set by mixins or [`create!()`]. A `computed` block requires a
+/// `builder` block.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Room {
+///   area: u32,
+///   description: String,
+/// }
+///
+/// factori!(Room, {
+///   default {
+///     width: u32 = 3,
+///     length: u32 = 4,
+///   }
+///
+///   computed {
+///     let area = width * length;
+///   }
+///
+///   builder {
+///     Room {
+///       area,
+///       description: format!("{}m², {}x{}", area, width, length),
+///     }
+///   }
+/// });
+///
+/// fn main() {
+///   let room = create!(Room, width: 5);
+///   assert_eq!(room.area, 20);
+///   assert_eq!(room.description, "20m², 5x4");
+/// }
+/// ```
+///
+/// ## Parameterized mixins
+///
+/// A mixin can take parameters, which are in scope in the values of that
+/// mixin only. Its arguments are passed in parentheses in `create!`, and it
+/// follows the same precedence as other mixins. Parameterized mixins can't
+/// be applied by name with [`FactoriMixins`], as there's no value for their
+/// parameters:
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///     electric: bool = false,
+///   }
+///
+///   mixin wheels(n: u8) {
+///     number_wheels = n,
+///   }
+///
+///   mixin truck(axles: u8, electric: bool) {
+///     number_wheels = axles * 4,
+///     electric = electric,
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(Vehicle, :wheels(3)).number_wheels, 3);
+///
+///   let vehicle = create!(Vehicle, :truck(3, true), :wheels(10));
+///   assert_eq!(vehicle.number_wheels, 10);
+///   assert!(vehicle.electric);
+/// }
+/// ```
+///
+/// ## Mixin groups
+///
+/// Mixins which can't apply together, such as the states of a state machine,
+/// can be put in a `mixin_group`. Applying two different mixins of the same
+/// group in `create!` is then a compile error, rather than one silently
+/// replacing the other. Conditional mixins might not be applied, so they
+/// aren't checked. [`FactoriMixins::factori_with_mixins`] returns `None` for
+/// such mixins instead.
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// pub enum State {
+///   Pending,
+///   Shipped,
+///   Cancelled,
+/// }
+///
+/// pub struct Order {
+///   state: State,
+///   tracking: Option<&'static str>,
+/// }
+///
+/// factori!(Order, {
+///   default {
+///     state = State::Pending,
+///     tracking = None,
+///   }
+///
+///   mixin_group state {
+///     mixin shipped {
+///       state = State::Shipped,
+///       tracking = Some("1Z999"),
+///     }
+///
+///     mixin cancelled {
+///       state = State::Cancelled,
+///     }
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(Order, :shipped).state, State::Shipped);
+///
+///   // error: the mixins `shipped` and `cancelled` are in the same
+///   // `mixin_group`, only one of them can be applied
+///   create!(Order, :shipped, :cancelled);
+/// }
+/// ```
+///
+/// ## Weak mixins
+///
+/// A `weak` mixin only sets the fields which no mixin applied before it
+/// sets, and keeps their values otherwise. This includes other weak mixins,
+/// so the first one to set a field wins. The mixins after it and the values
+/// passed to [`create!()`] still override it. Conditional mixins count when
+/// they're applied.
+///
+/// The factory keeps track of the applied mixins to do this, so the types of
+/// the fields must be given in the `default` block, as with a `builder`
+/// block.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   color: &'static str,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///     color: &'static str = "white",
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+///
+///   mixin weak truck {
+///     number_wheels = 6,
+///     color = "red",
+///   }
+/// });
+///
+/// fn main() {
+///   let vehicle = create!(Vehicle, :bike, :truck);
+///   assert_eq!(vehicle.number_wheels, 2);
+///   assert_eq!(vehicle.color, "red");
+///
+///   let vehicle = create!(Vehicle, :truck, :bike);
+///   assert_eq!(vehicle.number_wheels, 2);
+/// }
+/// ```
+///
+/// ## Shared mixins
+///
+/// Mixins defined with [`shared_mixins!()`] can be added to several
+/// factories with `use_mixins name, other_name;`, in any of their blocks'
+/// places. Each factory gets its own copy of the mixin, as if it was written
+/// in its definition, so the fields it sets must be fields of each of them.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// shared_mixins! {
+///   archived {
+///     archived = true,
+///   }
+/// }
+///
+/// pub struct Post {
+///   title: &'static str,
+///   archived: bool,
+/// }
+///
+/// factori!(Post, {
+///   use_mixins archived;
+///
+///   default {
+///     title = "Hello",
+///     archived = false,
+///   }
+/// });
+///
+/// pub struct Comment {
+///   archived: bool,
+/// }
+///
+/// factori!(Comment, {
+///   default {
+///     archived = false,
+///   }
+///
+///   use_mixins archived;
+/// });
+///
+/// fn main() {
+///   assert!(create!(Post, :archived).archived);
+///   assert!(create!(Comment, :archived).archived);
+/// }
+/// ```
+///
+/// ## Mixin aliases
+///
+/// A mixin can have other names after `alias`, so that a renamed mixin can
+/// still be applied with its old name. The aliases are the same mixin, they
+/// can be applied together even in a `mixin_group`, and
+/// [`FactoriMixins::factori_with_mixins`] accepts them too, although
+/// [`FactoriMixins::factori_mixins`] only lists the mixin's name. An alias
+/// which is also the name or the alias of another mixin is a compile error.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///     electric = false,
+///   }
+///
+///   mixin electric alias ev, battery {
+///     electric = true,
+///   }
+/// });
+///
+/// fn main() {
+///   assert!(create!(Vehicle, :electric).electric);
+///   assert!(create!(Vehicle, :ev).electric);
+///   assert!(create!(Vehicle, :battery).electric);
+/// }
+/// ```
+///
+/// ## Exhaustive defaults
+///
+/// Without a `builder` block, a field missing from the `default` block is a
+/// compile error, as the struct literal built from it needs every field. With
+/// a `builder` block, the `default` block can leave out fields which the
+/// block sets itself. `#[factori(exhaustive)]` before the type requires the
+/// `default` block to have every field of the struct anyway, so that a field
+/// added to the struct later can't be forgotten in the factory. The `default`
+/// block then can't have fields which aren't in the struct, the other inputs
+/// of the `builder` block go in the `transient` block:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
+///
+/// // error[E0027]: pattern does not mention field `electric`
+/// factori!(#[factori(exhaustive)] Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///   }
+///
+///   builder {
+///     let electric = false;
+///     Vehicle { number_wheels, electric }
+///   }
+/// });
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ## Deriving traits on the builder
+///
+/// `#[factori(builder_derive(...))]` before the type derives the given traits
+/// on the builder struct of a factory with a `builder` block, e.g. to compare
+/// the builders returned by [`build!()`] when testing the factory itself. The
+/// derived traits cover the transient fields, as well as the names of the
+/// mixins which were applied, and require every field and transient field to
+/// implement them.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+/// }
+///
+/// factori!(#[factori(builder_derive(Debug, PartialEq))] Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///   }
+///
+///   transient {
+///     electric: bool = false,
+///   }
+///
+///   builder {
+///     Vehicle { number_wheels }
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(build!(Vehicle), build!(Vehicle));
+///   assert_ne!(build!(Vehicle), build!(Vehicle, electric: true));
+/// }
+/// ```
+///
+/// ## Deriving traits on the mixins enum
+///
+/// The mixins of a factory are the variants of an enum, named e.g.
+/// `_Factori_Mixins_Vehicle`. `#[factori(mixins_derive(...))]` before the
+/// type derives the given traits on it, such as `strum::EnumIter` to iterate
+/// over the mixins for a test matrix, or `strum::EnumString` to parse their
+/// names. The crate of such derives is then a dependency of the tests, not
+/// of factori-imp. The enum already implements `Debug`, which writes the
+/// mixin's name, and the parameters of parameterized mixins must implement
+/// the derived traits too.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// use std::collections::HashSet;
+///
+/// pub struct Vehicle {
+///   number_wheels: u8,
+/// }
+///
+/// factori!(#[factori(mixins_derive(Clone, Copy, PartialEq, Eq, Hash))] Vehicle, {
+///   default {
+///     number_wheels = 4,
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+/// });
+///
+/// fn main() {
+///   let mixins: HashSet<_> = [_Factori_Mixins_Vehicle::bike, _Factori_Mixins_Vehicle::bike]
+///     .into_iter()
+///     .collect();
+///   assert_eq!(mixins.len(), 1);
+/// }
+/// ```
+///
+/// ## Metadata
+///
+/// A `meta { }` block attaches metadata to a factory, e.g. for tooling which
+/// groups the factories of a test suite. Its values must be string or integer
+/// literals, and each becomes an associated const of the type named after the
+/// key, e.g. `Vehicle::FACTORI_META_CATEGORY` for `category`. Strings are
+/// `&'static str`, and integers have the type of their suffix, or `i64`
+/// without one. The consts are defined on the type, so it must be defined in
+/// the same crate, and the factories of [`factories!()`] can't have a `meta`
+/// block.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct User {
+///   name: &'static str,
+/// }
+///
+/// factori!(User, {
+///   default {
+///     name = "Alice",
+///   }
+///
+///   meta {
+///     category = "auth",
+///     priority = 2u8,
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(User::FACTORI_META_CATEGORY, "auth");
+///   assert_eq!(User::FACTORI_META_PRIORITY, 2);
+/// }
+/// ```
+///
+/// ## Allowing lints
+///
+/// The code of the values and blocks of a factory ends up in the generated
+/// items, so lints denied in the crate can be triggered by it.
+/// `#[factori(allow(...))]` before the type adds `#[allow(...)]` with the
+/// given lints to each of the items generated for the factory.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+/// }
+///
+/// factori!(#[factori(allow(clippy::let_and_return))] Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///   }
+///
+///   builder {
+///     let vehicle = Vehicle { number_wheels };
+///     vehicle
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(Vehicle).number_wheels, 4);
+/// }
+/// ```
+///
+/// ## Fallible builders
+///
+/// A `try_builder` block can be used instead of the `builder` block when
+/// constructing the type can fail. It takes the error type in parentheses,
+/// and `?` can be used inside of it like in a function returning that error.
+///
+/// [`try_create!()`] returns the `Result` of the block, while [`create!()`]
+/// and the other macros panic with the error, which must implement `Debug`.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Port(u16);
+///
+/// factori!(Port, {
+///   default {
+///     raw: String = "8080".to_string(),
+///   }
+///
+///   try_builder(std::num::ParseIntError) {
+///     Port(raw.parse()?)
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(create!(Port).0, 8080);
+///   assert!(try_create!(Port, raw: "http".to_string()).is_err());
+/// }
+/// ```
+///
+/// ## Validation
+///
+/// A `validate { }` block holds a closure which is called with a reference
+/// to the builder before building, and returns a `Result<(), String>`. The
+/// builder is the type itself for factories without a `builder` block. When
+/// it returns an error, [`try_create!()`] returns a [`FactoriError`] with the
+/// factory's name and the message, while [`create!()`] and the other macros
+/// panic with it. It can't be combined with a `try_builder` block, which can
+/// return its own errors, or a `builder async` block.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   number_seats: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///     number_seats = 5,
+///   }
+///
+///   validate {
+///     |vehicle| match vehicle.number_wheels {
+///       0 => Err("a vehicle needs wheels".to_string()),
+///       _ => Ok(()),
+///     }
+///   }
+/// });
+///
+/// fn main() {
+///   assert!(try_create!(Vehicle).is_ok());
+///
+///   let error = try_create!(Vehicle, number_wheels: 0).err().unwrap();
+///   assert_eq!(error.to_string(), "invalid `Vehicle`: a vehicle needs wheels");
+/// }
+/// ```
+///
+/// [`FactoriError`]: struct.FactoriError.html
+///
+/// ## Async builders
+///
+/// With the `async` feature, which needs Rust 1.75, a `builder async` block
+/// can be used instead of the `builder` block when constructing the type
+/// needs to `.await`, such as to connect a client. Its factory is used with
+/// [`create_async!()`] and [`create_vec_async!()`], which return futures,
+/// and whose docs have examples. Only the block is async: the defaults,
+/// mixins and overrides are applied as usual.
+///
+/// The other macros, such as [`create!()`], can't build such a factory, and
+/// neither can [`FactoriDefault`] or [`FactoriMixins`], which aren't
+/// implemented for its type. Its builder implements [`AsyncBuilder`] instead
+/// of [`Builder`].
+///
+/// The names of the mixins that were applied are available in the `builder`
+/// block as `__mixins`, a `&[&'static str]` in the order they were applied.
+/// This allows a mixin to select how the type is constructed, such as which
+/// enum variant to use, without a transient field for it.
+///
+/// ```
+/// #  #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct Order(u64, bool);
+///
+/// factori!(Order, {
+///   default {
+///     id: u64 = 1,
+///     shipped: bool = false,
+///   }
+///
+///   transient {
+///     base_id: u64 = 0,
+///   }
+///
+///   builder {
+///     // All fields from default { } and transient { } are in scope here
+///     // with their values. We construct a tuple struct here, but we could
+///     // easily call a method like Order::new().
+///     let id = base_id + id;
+///     Order(id, shipped)
+///   }
+///
+///   mixin shipped {
+///     shipped = true,
+///   }
+/// });
+///
+/// fn main() {
+///   let order = create!(Order, :shipped, id: 2);
+///   let another_order = create!(Order, id: 5, base_id: 200);
+///   assert_eq!(another_order.0, 205);
+/// }
+/// ```
+#[macro_export]
+macro_rules! factori {
   // We define a simple macro so that the documentation doesn't state this
   // is a re-export from factori-impl. This also allows us to write docs here.
   ($($input:tt)*) => {
-    $crate::factori_imp_impl::define!($($input)*);
+    $crate::factori_imp_impl::define!([$crate] $($input)*);
   }
 }
 
-#[doc(hidden)]
+/// A macro to define a factory in a function.
+///
+/// Takes the same arguments as [`factori!()`], but the types it generates
+/// are private rather than `pub`. A type defined in a function can't be
+/// part of a public type, so this lets a test define a throwaway factory for
+/// a type of its own. [`create!()`] and the other macros work the same with
+/// it, in the same function.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// fn main() {
+///   struct Vehicle {
+///     number_wheels: u8,
+///   }
+///
+///   factori_local!(Vehicle, {
+///     default {
+///       number_wheels: u8 = 4,
+///     }
+///
+///     builder {
+///       let number_wheels = number_wheels * 2;
+///       Vehicle { number_wheels }
+///     }
+///   });
+///
+///   assert_eq!(create!(Vehicle).number_wheels, 8);
+/// }
+/// ```
+///
+/// [`factori!()`]: macro.factori.html
+/// [`create!()`]: macro.create.html
+#[macro_export]
+macro_rules! factori_local {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::define_local!([$crate] $($input)*);
+  }
+}
+
+/// A macro to define mixins which several factories can use.
+///
+/// Takes mixins as they are written in [`factori!()`], without the `mixin`
+/// keyword, e.g. `archived { archived = true }`, `weak name { ... }` or
+/// `wheels(n: u8) { ... }`. A factory adds them with `use_mixins archived;`
+/// in its definition, as in the example of [`factori!()`].
+///
+/// Each mixin is a macro named `__factori_mixin_` followed by the name of the
+/// mixin, so like a `macro_rules!` macro, they must be defined before the
+/// factories using them, in the same module or in a parent module.
+///
+/// [`factori!()`]: macro.factori.html
+#[macro_export]
+macro_rules! shared_mixins {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::shared_mixins!([$crate] $($input)*);
+  }
+}
+
+/// Macro used to define several named factories for a single type.
+///
+/// Each named factory takes the same blocks as [`factori!()`], and is
+/// instantiated by passing its name instead of the type to [`create!()`] and
+/// the other `create*` macros.
+///
+/// Each named factory has its own builder struct, so the types of the fields
+/// in its `default` block must be specified. Named factories aren't used by
+/// [`create_default!()`] and `FactoriMixins`, as they can't be picked from the
+/// type.
+///
+/// Mixins defined in the group, outside of the named factories, are shared by
+/// all of them, as if they were defined in each one. A mixin can't share its
+/// name with a mixin of the group.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// pub struct User {
+///   name: String,
+///   role: &'static str,
+///   archived: bool,
+/// }
+///
+/// factories!(User, {
+///   mixin archived {
+///     archived = true,
+///   }
+///
+///   admin {
+///     default {
+///       name: String = "Root".to_string(),
+///       role: &'static str = "admin",
+///       archived: bool = false,
+///     }
+///   }
+///
+///   guest {
+///     default {
+///       name: String = "Anonymous".to_string(),
+///       role: &'static str = "guest",
+///       archived: bool = false,
+///     }
+///
+///     mixin named {
+///       name = "Bob".to_string(),
+///     }
+///   }
+/// });
+///
+/// fn main() {
+///   let admin = create!(admin);
+///   assert_eq!(admin.role, "admin");
+///
+///   let guest = create!(guest, :named, :archived);
+///   assert_eq!(guest.role, "guest");
+///   assert_eq!(guest.name, "Bob");
+///   assert!(guest.archived);
+/// }
+/// ```
+///
+/// [`factori!()`]: macro.factori.html
+/// [`create!()`]: macro.create.html
+/// [`create_default!()`]: macro.create_default.html
+#[macro_export]
+macro_rules! factories {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::factories!([$crate] $($input)*);
+  }
+}
+
+/// Turns a factory's builder into the factory's type.
+///
+/// This is implemented for the builder of every factory and is what
+/// [`create!()`] uses under the hood. It is only needed directly to build the
+/// value returned by [`build!()`]. The builder structs generated for the
+/// factories with a `builder` block, or with fields which need one, also have
+/// an inherent `build()` method, so importing the trait to call it on them is
+/// unnecessary, and the import is reported as unused.
+///
+/// [`create!()`]: macro.create.html
+/// [`build!()`]: macro.build.html
 pub trait Builder {
+  /// The type constructed by the factory.
   type Ty;
 
+  /// Builds the factory's type, running its `builder` block if it has one.
+  ///
+  /// Panics if the factory's `try_builder` block fails.
   fn build(self) -> Self::Ty;
 }
 
+/// Turns a factory's builder into the factory's type, if its `try_builder`
+/// block succeeds.
+///
+/// This is implemented for the builder of every factory and is what
+/// [`try_create!()`] uses under the hood. The error is the one given to the
+/// factory's `try_builder` block, [`FactoriError`] for factories with a
+/// `validate` block, or [`Infallible`] for other factories.
+///
+/// [`try_create!()`]: macro.try_create.html
+/// [`FactoriError`]: struct.FactoriError.html
+/// [`Infallible`]: https://doc.rust-lang.org/std/convert/enum.Infallible.html
+pub trait TryBuilder: Builder {
+  /// The error returned when the factory fails to build.
+  type Error;
+
+  /// Builds the factory's type, running its `try_builder` or `builder`
+  /// block if it has one.
+  fn try_build(self) -> Result<Self::Ty, Self::Error>;
+}
+
+/// The error of a factory whose `validate` block rejected the builder.
+///
+/// It is returned by [`try_create!()`] for factories with a `validate` block,
+/// while [`create!()`] panics with it.
+///
+/// [`try_create!()`]: macro.try_create.html
+/// [`create!()`]: macro.create.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactoriError {
+  factory: &'static str,
+  message: String,
+}
+
+impl FactoriError {
+  /// Creates the error of the named factory with the message returned by its
+  /// `validate` block.
+  pub fn new(factory: &'static str, message: String) -> Self {
+    FactoriError { factory, message }
+  }
+
+  /// The name of the factory which failed, e.g. `Vehicle`.
+  pub fn factory(&self) -> &'static str {
+    self.factory
+  }
+
+  /// The message returned by the factory's `validate` block.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl std::fmt::Display for FactoriError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "invalid `{}`: {}", self.factory, self.message)
+  }
+}
+
+impl std::error::Error for FactoriError {}
+
+/// Turns the builder of a factory with a `builder async` block into a future
+/// of the factory's type.
+///
+/// This is implemented for the builders of those factories instead of
+/// [`Builder`], and is what [`create_async!()`] uses under the hood. It is
+/// only available with the `async` feature, which needs Rust 1.75.
+///
+/// [`create_async!()`]: macro.create_async.html
+#[cfg(feature = "async")]
+pub trait AsyncBuilder {
+  /// The type constructed by the factory.
+  type Ty;
+
+  /// Builds the factory's type, running its `builder async` block.
+  fn build(self) -> impl std::future::Future<Output = Self::Ty>;
+}
+
+/// Instantiates a type from its factory's defaults.
+///
+/// This is implemented by [`factori!()`] for the type of every factory, with
+/// the factory's builder as the type parameter. It is what
+/// [`create_default!()`] uses to find the factory from the inferred type.
+///
+/// [`factori!()`]: macro.factori.html
+/// [`create_default!()`]: macro.create_default.html
+pub trait FactoriDefault<B> {
+  /// Builds the type from the defaults of its factory.
+  fn factori_default() -> Self;
+
+  /// Describes the defaults of its factory, pretty-printed with `Debug`.
+  /// This helps to find out why a test's expectation no longer matches the
+  /// factory. It is only available for types which implement `Debug`.
+  ///
+  /// ```
+  /// # #[macro_use] extern crate factori_imp;
+  /// #
+  /// use factori_imp::FactoriDefault;
+  ///
+  /// #[derive(Debug)]
+  /// pub struct Vehicle {
+  ///   number_wheels: u8,
+  /// }
+  ///
+  /// factori!(Vehicle, {
+  ///   default {
+  ///     number_wheels = 4,
+  ///   }
+  /// });
+  ///
+  /// fn main() {
+  ///   let debug = Vehicle::factori_default_debug();
+  ///   assert_eq!(debug, "Vehicle {\n    number_wheels: 4,\n}");
+  /// }
+  /// ```
+  fn factori_default_debug() -> String
+  where
+    Self: Sized + std::fmt::Debug,
+  {
+    format!("{:#?}", Self::factori_default())
+  }
+}
+
+/// Lists and applies a factory's mixins at runtime.
+///
+/// This is implemented by [`factori!()`] for the type of every factory, with
+/// the factory's builder as the type parameter. It is useful to build a test
+/// matrix over every mixin of a factory.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// use factori_imp::FactoriMixins;
+///
+/// pub struct Vehicle {
+///   number_wheels: u8,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+///
+///   mixin trike {
+///     number_wheels = 3,
+///   }
+/// });
+///
+/// fn main() {
+///   assert_eq!(Vehicle::factori_mixins(), &["bike", "trike"]);
+///
+///   for name in Vehicle::factori_mixins() {
+///     let vehicle = Vehicle::factori_with_mixins(&[name]).unwrap();
+///     assert!(vehicle.number_wheels < 4);
+///   }
+/// }
+/// ```
+///
+/// [`factori!()`]: macro.factori.html
+pub trait FactoriMixins<B>: Sized {
+  /// The names of the factory's mixins, in the order they are defined.
+  fn factori_mixins() -> &'static [&'static str];
+
+  /// Builds the type from its factory with the named mixins applied in order,
+  /// as [`create!()`] would. Returns `None` if any name isn't a mixin of the
+  /// factory.
+  ///
+  /// [`create!()`]: macro.create.html
+  fn factori_with_mixins(names: &[&str]) -> Option<Self>;
+}
+
+/// Starts a factory's builder without the macros.
+///
+/// This is implemented by [`factori!()`] for the type of every factory, with
+/// the factory's builder as the type parameter. The builder starts with the
+/// factory's defaults.
+///
+/// When the factory has a `builder` block, its builder has a setter for each
+/// field of the `default` and `transient` blocks, which takes the value and
+/// returns the builder, and a `build()` method, which is the same as
+/// [`Builder::build()`] without having to import it. Without one, the builder
+/// is the type itself, whose fields are set directly.
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// use factori_imp::{Builder, FactoriBuilder};
+///
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   name: String,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels: u8 = 4,
+///   }
+///
+///   transient {
+///     brand: &'static str = "Generic",
+///   }
+///
+///   builder {
+///     Vehicle { number_wheels, name: format!("{} {}", brand, number_wheels) }
+///   }
+/// });
+///
+/// fn main() {
+///   let vehicles: Vec<Vehicle> = (1..=3)
+///     .map(|i| Vehicle::factori_builder().number_wheels(i).brand("Acme"))
+///     .map(Builder::build)
+///     .collect();
+///
+///   assert_eq!(vehicles[2].number_wheels, 3);
+///   assert_eq!(vehicles[2].name, "Acme 3");
+///
+///   let bike = Vehicle::factori_builder().number_wheels(2).build();
+///   assert_eq!(bike.name, "Generic 2");
+/// }
+/// ```
+///
+/// [`factori!()`]: macro.factori.html
+/// [`Builder::build()`]: trait.Builder.html#tymethod.build
+pub trait FactoriBuilder<B> {
+  /// The factory's builder with its defaults.
+  fn factori_builder() -> B;
+}
+
+/// A macro to define a factory and print the code generated for it.
+///
+/// Accepts the same input as [`factori!()`] and defines the same factory, but
+/// also prints the generated code when compiling. This helps to understand
+/// why a factory doesn't compile without having to install [`cargo-expand`].
+///
+/// It is only available with the `debug` feature, so that it doesn't end up
+/// being used in normal builds.
+///
+/// [`factori!()`]: macro.factori.html
+/// [`cargo-expand`]: https://github.com/dtolnay/cargo-expand
+#[cfg(feature = "debug")]
+#[macro_export]
+macro_rules! debug_factori {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::debug_define!([$crate] $($input)*);
+  }
+}
+
+/// A macro to take the objects created on the current thread out of the
+/// registry.
+///
+/// Returns a `Vec<Box<dyn Any>>` of everything created since the last call,
+/// which can be downcast back into the created types. See the [`registry`]
+/// module for an example.
+///
+/// It is only available with the `registry` feature.
+///
+/// [`registry`]: registry/index.html
+#[cfg(feature = "registry")]
+#[macro_export]
+macro_rules! factori_drain {
+  () => {
+    $crate::registry::drain()
+  };
+}
+
+/// A macro to instantiate an instance of a factory serialized to JSON.
+///
+/// Accepts the same arguments as [`create!()`], and returns the instance
+/// serialized with `serde_json::to_string`, e.g. to compare it to a golden
+/// file. The type must implement `serde::Serialize`. It panics if the
+/// instance can't be serialized.
+///
+/// It is only available with the `serde` feature.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// #[derive(serde::Serialize)]
+/// pub struct Vehicle {
+///   number_wheels: u8,
+///   electric: bool,
+/// }
+///
+/// factori!(Vehicle, {
+///   default {
+///     number_wheels = 4,
+///     electric = false,
+///   }
+///
+///   mixin bike {
+///     number_wheels = 2,
+///   }
+/// });
+///
+/// fn main() {
+///   let json = create_json!(Vehicle, :bike);
+///   assert_eq!(json, r#"{"number_wheels":2,"electric":false}"#);
+/// }
+/// ```
+///
+/// [`create!()`]: macro.create.html
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! create_json {
+  ($($input:tt)*) => {
+    $crate::factori_imp_impl::create_json!([$crate] $($input)*);
+  }
+}
+
 #[doc(hidden)]
 pub trait Default {
   fn default() -> Self;
@@ -0,0 +1,92 @@
+//! A thread-local registry of the objects created by the factories.
+//!
+//! It is only available with the `registry` feature. When enabled, every
+//! object created with [`create!()`] (and the other `create*` macros) is
+//! cloned into the registry, so that integration tests can tear down
+//! everything they created with [`factori_drain!()`]. Only the objects built
+//! by a factory are registered, e.g. [`create_vec_clone!()`] registers the one
+//! object it clones.
+//!
+//! The objects are stored as `Box<dyn Any>`, so only the types which
+//! implement `Clone` and are `'static` are registered, the others are
+//! skipped. They can be retrieved by downcasting:
+//!
+//! ```
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! #[derive(Clone)]
+//! pub struct Vehicle {
+//!   number_wheels: u8,
+//! }
+//!
+//! factori!(Vehicle, {
+//!   default {
+//!     number_wheels = 4,
+//!   }
+//! });
+//!
+//! fn main() {
+//!   create!(Vehicle, number_wheels: 2);
+//!
+//!   let vehicles: Vec<Vehicle> = factori_drain!()
+//!     .into_iter()
+//!     .filter_map(|created| created.downcast::<Vehicle>().ok())
+//!     .map(|vehicle| *vehicle)
+//!     .collect();
+//!
+//!   assert_eq!(vehicles.len(), 1);
+//!   assert_eq!(vehicles[0].number_wheels, 2);
+//!   assert!(factori_drain!().is_empty());
+//! }
+//! ```
+//!
+//! [`create!()`]: ../macro.create.html
+//! [`factori_drain!()`]: ../macro.factori_drain.html
+//! [`create_vec_clone!()`]: ../macro.create_vec_clone.html
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+  static CREATED: RefCell<Vec<Box<dyn Any>>> = const { RefCell::new(Vec::new()) };
+  static RESET_REGISTERED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Wraps a created object to pick how to register it, depending on whether
+/// it implements `Clone`: `(&&Register(&value)).register()` finds
+/// [`RegisterClone`] first, and falls back to [`RegisterSkip`].
+#[doc(hidden)]
+pub struct Register<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait RegisterClone {
+  fn register(&self);
+}
+
+impl<T: Any + Clone> RegisterClone for &Register<'_, T> {
+  fn register(&self) {
+    if !RESET_REGISTERED.with(|registered| registered.replace(true)) {
+      crate::reset::register(|| {
+        drain();
+      });
+    }
+
+    let value = self.0.clone();
+    CREATED.with(|created| created.borrow_mut().push(Box::new(value)));
+  }
+}
+
+#[doc(hidden)]
+pub trait RegisterSkip {
+  fn register(&self);
+}
+
+impl<T> RegisterSkip for Register<'_, T> {
+  fn register(&self) {}
+}
+
+/// Returns the objects created on this thread since the last call, in the
+/// order they were created, and clears the registry.
+pub fn drain() -> Vec<Box<dyn Any>> {
+  CREATED.with(|created| created.borrow_mut().drain(..).collect())
+}
@@ -0,0 +1,68 @@
+//! The hooks run by [`factori_reset_all!()`].
+//!
+//! [`factori_reset_all!()`] runs the hooks registered on the current thread.
+//! The instances shared by [`create_shared!()`] and, with the `registry`
+//! feature, the objects created register their own hook. State of your own,
+//! such as a counter used in the defaults of a factory, can be reset along
+//! with them by registering a hook:
+//!
+//! ```
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//!
+//! static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+//!
+//! pub struct User {
+//!   id: u32,
+//! }
+//!
+//! factori!(User, {
+//!   default {
+//!     id = NEXT_ID.fetch_add(1, Ordering::SeqCst),
+//!   }
+//! });
+//!
+//! fn main() {
+//!   factori_imp::reset::register(|| NEXT_ID.store(1, Ordering::SeqCst));
+//!
+//!   create!(User);
+//!   assert_eq!(create!(User).id, 2);
+//!
+//!   factori_reset_all!();
+//!   assert_eq!(create!(User).id, 1);
+//! }
+//! ```
+//!
+//! [`factori_reset_all!()`]: ../macro.factori_reset_all.html
+//! [`create_shared!()`]: ../macro.create_shared.html
+
+use std::cell::RefCell;
+
+thread_local! {
+  static HOOKS: RefCell<Vec<fn()>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Registers a hook to run on every call to [`factori_reset_all!()`] on the
+/// current thread, in the order they are registered. A hook registered twice
+/// runs twice.
+///
+/// The stateful features of the factories register their own hook the first
+/// time they are used on a thread.
+///
+/// [`factori_reset_all!()`]: ../macro.factori_reset_all.html
+pub fn register(hook: fn()) {
+  HOOKS.with(|hooks| hooks.borrow_mut().push(hook));
+}
+
+/// Resets the state of the factories, see [`factori_reset_all!()`].
+///
+/// [`factori_reset_all!()`]: ../macro.factori_reset_all.html
+#[doc(hidden)]
+pub fn reset_all() {
+  // The hooks are copied out, as they might register other hooks.
+  let hooks = HOOKS.with(|hooks| hooks.borrow().clone());
+  for hook in hooks {
+    hook();
+  }
+}
@@ -0,0 +1,115 @@
+//! A small random number generator for randomized default values.
+//!
+//! It is not suitable for anything but test data: it is a thread-local
+//! SplitMix64 generator, seeded randomly unless it is used inside of
+//! [`create_with_seed!()`].
+//!
+//! [`create_with_seed!()`]: ../macro.create_with_seed.html
+
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::Range;
+
+thread_local! {
+  static STATE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Returns a handle to the thread-local random number generator.
+///
+/// It is meant to be used in the values of factories, which makes them
+/// reproducible with [`create_with_seed!()`].
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate factori_imp;
+/// #
+/// pub struct User {
+///   age: u64,
+/// }
+///
+/// factori!(User, {
+///   default {
+///     age = factori_imp::rng().gen_range(18..100),
+///   }
+/// });
+///
+/// fn main() {
+///   let user = create!(User);
+///   assert!(user.age >= 18 && user.age < 100);
+/// }
+/// ```
+///
+/// [`create_with_seed!()`]: ../macro.create_with_seed.html
+pub fn rng() -> Rng {
+  Rng { _private: () }
+}
+
+/// A handle to the thread-local random number generator, see [`rng()`].
+///
+/// [`rng()`]: fn.rng.html
+pub struct Rng {
+  _private: (),
+}
+
+impl Rng {
+  /// Returns a random `u64`.
+  pub fn next_u64(&mut self) -> u64 {
+    STATE.with(|state| {
+      let mut seed = state.get().unwrap_or_else(random_seed);
+      let value = split_mix(&mut seed);
+      state.set(Some(seed));
+      value
+    })
+  }
+
+  /// Returns a random number in the range. Panics if the range is empty.
+  pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+    assert!(range.start < range.end, "cannot sample an empty range");
+    range.start + self.next_u64() % (range.end - range.start)
+  }
+
+  /// Returns a random `bool`.
+  pub fn gen_bool(&mut self) -> bool {
+    self.next_u64() & 1 == 1
+  }
+
+  /// Returns a random element of the slice. Panics if the slice is empty.
+  pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    let index = self.gen_range(0..items.len() as u64);
+    &items[index as usize]
+  }
+}
+
+/// Seeds the thread-local random number generator until it is dropped, when
+/// the previous state is restored. Used by create_with_seed!().
+#[doc(hidden)]
+pub struct SeedGuard {
+  previous: Option<u64>,
+}
+
+impl SeedGuard {
+  pub fn new(seed: u64) -> Self {
+    let previous = STATE.with(|state| state.replace(Some(seed)));
+    SeedGuard { previous }
+  }
+}
+
+impl Drop for SeedGuard {
+  fn drop(&mut self) {
+    STATE.with(|state| state.set(self.previous));
+  }
+}
+
+fn random_seed() -> u64 {
+  RandomState::new().build_hasher().finish()
+}
+
+fn split_mix(state: &mut u64) -> u64 {
+  *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+  let mut z = *state;
+  z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  z ^ (z >> 31)
+}
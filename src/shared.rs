@@ -0,0 +1,79 @@
+//! Storage for the instances shared by [`create_shared!()`].
+//!
+//! Each thread has its own instances, so that tests running in parallel don't
+//! share them, nor reset them for each other.
+//!
+//! [`create_shared!()`]: ../macro.create_shared.html
+
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+
+/// A shared instance, with its type and the arguments of the
+/// [`create_shared!()`] call which created it
+///
+/// [`create_shared!()`]: ../macro.create_shared.html
+type Instance = (TypeId, &'static str, Arc<dyn Any + Send + Sync>);
+
+thread_local! {
+  static INSTANCES: RefCell<Vec<Instance>> = const { RefCell::new(Vec::new()) };
+  static RESET_REGISTERED: Cell<bool> = const { Cell::new(false) };
+}
+
+fn get<T: Any + Send + Sync>(key: &str) -> Option<Arc<T>> {
+  INSTANCES.with(|instances| {
+    instances
+      .borrow()
+      .iter()
+      .find(|(type_id, instance_key, _)| *type_id == TypeId::of::<T>() && *instance_key == key)
+      .map(|(_, _, instance)| {
+        Arc::clone(instance)
+          .downcast()
+          .expect("instances are stored by their TypeId")
+      })
+  })
+}
+
+/// Returns the shared instance of `T` for the key, creating it if there isn't
+/// one.
+#[doc(hidden)]
+pub fn get_or_insert_with<T, F>(key: &'static str, create: F) -> Arc<T>
+where
+  T: Any + Send + Sync,
+  F: FnOnce() -> T,
+{
+  if let Some(instance) = get(key) {
+    return instance;
+  }
+
+  if !RESET_REGISTERED.with(|registered| registered.replace(true)) {
+    crate::reset::register(reset_all);
+  }
+
+  // The instances aren't borrowed while creating, as the factory might use
+  // other shared instances.
+  let created = Arc::new(create());
+  INSTANCES.with(|instances| {
+    instances
+      .borrow_mut()
+      .push((TypeId::of::<T>(), key, created.clone()))
+  });
+  created
+}
+
+/// Removes the shared instances of `T`, whatever their key.
+#[doc(hidden)]
+pub fn reset<T: Any>() {
+  INSTANCES.with(|instances| {
+    instances
+      .borrow_mut()
+      .retain(|(type_id, _, _)| *type_id != TypeId::of::<T>())
+  });
+}
+
+/// Removes every shared instance of this thread.
+fn reset_all() {
+  // The instances are dropped once taken out, as dropping them might use
+  // other shared instances.
+  INSTANCES.with(RefCell::take);
+}
@@ -0,0 +1,111 @@
+//! Timing of the objects created by the factories.
+//!
+//! It is only available with the `trace` feature. When enabled, every object
+//! created with [`create!()`] (and the other `create*` macros) is timed, from
+//! evaluating its default values to building it, and reported with the name
+//! of its factory. Without the feature, no code is generated for it.
+//!
+//! The reports are sent to a [`Sink`], which prints them to stderr by
+//! default, e.g. `factori: created Vehicle in 1.2ms`. [`set_sink()`]
+//! replaces it for every thread, e.g. with a function forwarding them to
+//! `log` or `tracing`, or collecting them for a test:
+//!
+//! ```
+//! # #[macro_use] extern crate factori_imp;
+//! #
+//! use std::time::Duration;
+//!
+//! pub struct Vehicle {
+//!   number_wheels: u8,
+//! }
+//!
+//! factori!(Vehicle, {
+//!   default {
+//!     number_wheels = 4,
+//!   }
+//! });
+//!
+//! fn report(factory: &str, elapsed: Duration) {
+//!   println!("{} took {:?}", factory, elapsed);
+//! }
+//!
+//! fn main() {
+//!   factori_imp::trace::set_sink(report);
+//!   create!(Vehicle);
+//! }
+//! ```
+//!
+//! To switch the reports off, e.g. for the tests of a crate which enables
+//! the feature for its benchmarks, set the [`Off`] sink with
+//! `factori_imp::trace::set_sink(factori_imp::trace::Off)`. Leaving the
+//! feature disabled removes the timing altogether.
+//!
+//! [`create!()`]: ../macro.create.html
+//! [`Sink`]: trait.Sink.html
+//! [`set_sink()`]: fn.set_sink.html
+//! [`Off`]: struct.Off.html
+
+use std::sync::{PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+/// Receives the reports of the created objects.
+///
+/// It is implemented for functions and closures taking the name of the
+/// factory and the time it took to create an object.
+pub trait Sink: Send + Sync {
+  fn report(&self, factory: &str, elapsed: Duration);
+}
+
+impl<F: Fn(&str, Duration) + Send + Sync> Sink for F {
+  fn report(&self, factory: &str, elapsed: Duration) {
+    self(factory, elapsed)
+  }
+}
+
+/// The default sink, which prints the reports to stderr.
+pub struct Stderr;
+
+impl Sink for Stderr {
+  fn report(&self, factory: &str, elapsed: Duration) {
+    eprintln!("factori: created {} in {:?}", factory, elapsed);
+  }
+}
+
+/// A sink which drops the reports, to switch them off.
+pub struct Off;
+
+impl Sink for Off {
+  fn report(&self, _factory: &str, _elapsed: Duration) {}
+}
+
+static SINK: RwLock<Option<Box<dyn Sink>>> = RwLock::new(None);
+
+/// Replaces the sink of the reports, for every thread.
+pub fn set_sink(sink: impl Sink + 'static) {
+  *SINK.write().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(sink));
+}
+
+/// Times the creation of an object, which is reported when it's finished.
+/// Nothing is reported if the factory panics.
+#[doc(hidden)]
+pub struct Span {
+  factory: &'static str,
+  start: Instant,
+}
+
+impl Span {
+  pub fn new(factory: &'static str) -> Self {
+    Span {
+      factory,
+      start: Instant::now(),
+    }
+  }
+
+  pub fn finish(self) {
+    let elapsed = self.start.elapsed();
+    match &*SINK.read().unwrap_or_else(PoisonError::into_inner) {
+      Some(sink) => sink.report(self.factory, elapsed),
+      None => Stderr.report(self.factory, elapsed),
+    }
+  }
+}
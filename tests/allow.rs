@@ -0,0 +1,31 @@
+#![deny(clippy::let_and_return)]
+
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+}
+
+// Without the allow, clippy denies the builder's `let` followed by a return
+// of the binding.
+factori!(#[factori(allow(clippy::let_and_return))] Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+  }
+
+  builder {
+    let vehicle = Vehicle { number_wheels };
+    vehicle
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[test]
+fn allowed_lints_in_builder() {
+  assert_eq!(create!(Vehicle).number_wheels, 4);
+  assert_eq!(create!(Vehicle, :bike).number_wheels, 2);
+}
@@ -0,0 +1,71 @@
+#[macro_use]
+extern crate factori_imp;
+
+#[derive(Debug, PartialEq)]
+pub enum Shape {
+  Circle(u8),
+  Square(u8),
+}
+
+pub struct Item {
+  shape: Shape,
+}
+
+factori!(Item, {
+  default {
+    size: u8 = 1,
+  }
+
+  builder {
+    let shape = if __mixins.contains(&"square") {
+      Shape::Square(size)
+    } else {
+      Shape::Circle(size)
+    };
+
+    Item { shape }
+  }
+
+  mixin square {}
+
+  mixin big {
+    size = 10,
+  }
+});
+
+pub struct Recorder {
+  mixins: Vec<&'static str>,
+}
+
+factori!(Recorder, {
+  default {}
+
+  builder {
+    Recorder { mixins: __mixins.to_vec() }
+  }
+
+  mixin first {}
+
+  mixin second {}
+});
+
+#[test]
+fn no_mixins_applied() {
+  let item = create!(Item);
+  assert_eq!(item.shape, Shape::Circle(1));
+}
+
+#[test]
+fn variant_selected_by_mixin() {
+  let item = create!(Item, :square);
+  assert_eq!(item.shape, Shape::Square(1));
+
+  let item = create!(Item, :big, :square);
+  assert_eq!(item.shape, Shape::Square(10));
+}
+
+#[test]
+fn lists_mixins_in_order() {
+  let recorder = create!(Recorder, :second, :first, :second);
+  assert_eq!(recorder.mixins, vec!["second", "first", "second"]);
+}
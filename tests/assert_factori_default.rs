@@ -0,0 +1,56 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  name: String,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+    name = "car".to_string(),
+  }
+});
+
+pub struct User {
+  name: String,
+}
+
+factori!(User, {
+  default {
+    name: String = "Alice".to_string(),
+  }
+
+  transient {
+    upcased: bool = true,
+  }
+
+  builder {
+    let name = if upcased { name.to_uppercase() } else { name };
+    User { name }
+  }
+});
+
+#[test]
+fn passes() {
+  assert_factori_default!(Vehicle, number_wheels == 4);
+}
+
+#[test]
+fn several_fields() {
+  assert_factori_default!(Vehicle, number_wheels == 4, electric == false, name == "car",);
+}
+
+#[test]
+fn runs_the_builder() {
+  assert_factori_default!(User, name == "ALICE");
+}
+
+#[test]
+#[should_panic(expected = "the default of `number_wheels` in the factory of `Vehicle` changed")]
+fn fails_when_changed() {
+  assert_factori_default!(Vehicle, electric == false, number_wheels == 2);
+}
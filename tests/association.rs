@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static USERS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn count_user() {
+  USERS.with(|users| users.set(users.get() + 1));
+}
+
+fn users() -> u32 {
+  USERS.with(|users| users.get())
+}
+
+pub struct User {
+  name: String,
+  admin: bool,
+}
+
+factori!(User, {
+  default {
+    name: String = "Richard".to_string(),
+    admin: bool = false,
+  }
+
+  builder {
+    count_user();
+    User { name, admin }
+  }
+
+  mixin admin {
+    admin = true,
+  }
+});
+
+pub struct Post {
+  title: String,
+  owner: User,
+}
+
+factori!(Post, {
+  default {
+    title: String = "Hello".to_string(),
+    owner: User = association!(User, :admin, name: format!("{} ({})", owner_name, title)),
+  }
+
+  transient {
+    owner_name: String = "Tom".to_string(),
+  }
+
+  builder {
+    Post { title, owner }
+  }
+
+  mixin anonymous {
+    owner = create!(User, name: "Anonymous".to_string()),
+  }
+});
+
+pub struct Comment {
+  author: User,
+}
+
+factori!(Comment, {
+  default {
+    author = association!(User),
+  }
+});
+
+#[test]
+fn creates_association() {
+  let post = create!(Post);
+  assert_eq!(post.title, "Hello");
+  assert_eq!(post.owner.name, "Tom (Hello)");
+  assert!(post.owner.admin);
+}
+
+#[test]
+fn refers_to_transient_fields() {
+  let post = create!(Post, owner_name: "John".to_string());
+  assert_eq!(post.owner.name, "John (Hello)");
+}
+
+#[test]
+fn refers_to_default_fields() {
+  let post = create!(Post, title: "Bye".to_string());
+  assert_eq!(post.owner.name, "Tom (Bye)");
+}
+
+#[test]
+fn overridden_association_is_not_created() {
+  let before = users();
+  let post = create!(Post, owner: User { name: "Jane".to_string(), admin: false });
+  assert_eq!(users(), before);
+  assert_eq!(post.owner.name, "Jane");
+  assert!(!post.owner.admin);
+}
+
+#[test]
+fn overridden_by_mixin() {
+  let post = create!(Post, :anonymous);
+  assert_eq!(post.owner.name, "Anonymous");
+}
+
+#[test]
+fn without_builder_block() {
+  let comment = create!(Comment);
+  assert_eq!(comment.author.name, "Richard");
+}
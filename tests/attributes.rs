@@ -0,0 +1,133 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  #[cfg(not(test))]
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    // Comments are fine as well.
+    number_wheels = 4,
+    #[cfg(not(test))]
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+    #[cfg(not(test))]
+    electric = true,
+  }
+});
+
+pub struct User {
+  name: String,
+  #[cfg(not(test))]
+  admin: bool,
+}
+
+factori!(User, {
+  default {
+    name: String = "Richard".to_string(),
+    #[cfg(not(test))]
+    admin: bool = false,
+  }
+
+  transient {
+    #[cfg(test)]
+    upcased: bool = false,
+    #[cfg(not(test))]
+    downcased: bool = false,
+  }
+
+  builder {
+    let name = if upcased { name.to_uppercase() } else { name };
+    User { name }
+  }
+});
+
+#[test]
+fn cfg_gated_default_field() {
+  let vehicle = create!(Vehicle);
+  assert_eq!(vehicle.number_wheels, 4);
+}
+
+#[test]
+fn cfg_gated_mixin_field() {
+  let bike = create!(Vehicle, :bike);
+  assert_eq!(bike.number_wheels, 2);
+}
+
+#[test]
+fn cfg_gated_builder_fields() {
+  let user = create!(User, upcased: true);
+  assert_eq!(user.name, "RICHARD");
+}
+
+#[derive(serde::Serialize)]
+pub struct Profile {
+  bio: &'static str,
+  #[serde(rename = "years")]
+  age: u8,
+}
+
+factori!(Profile, {
+  default {
+    /// The text shown on the profile.
+    bio = "",
+    #[serde(rename = "years")]
+    age = 30,
+  }
+
+  mixin old {
+    /// Old enough to retire.
+    age = 80,
+  }
+});
+
+pub struct Account {
+  name: &'static str,
+  balance: u32,
+}
+
+factori!(#[factori(builder_derive(serde::Serialize))] Account, {
+  default {
+    /// The name shown to other users.
+    name: &'static str = "Ann",
+    #[serde(rename = "total")]
+    balance: u32 = 0,
+  }
+
+  transient {
+    /// Doubles the balance when building.
+    doubled: bool = false,
+  }
+
+  builder {
+    let balance = if doubled { balance * 2 } else { balance };
+    Account { name, balance }
+  }
+
+  mixin rich {
+    /// A balance few users have.
+    balance = 1000,
+  }
+});
+
+#[test]
+fn doc_comments_and_other_attributes() {
+  let profile = create!(Profile, :old);
+  assert_eq!(profile.age, 80);
+  assert_eq!(
+    serde_json::to_string(&profile).unwrap(),
+    r#"{"bio":"","years":80}"#
+  );
+
+  let account = create!(Account, :rich, doubled: true);
+  assert_eq!(account.name, "Ann");
+  assert_eq!(account.balance, 2000);
+  let json = serde_json::to_string(&build!(Account, :rich)).unwrap();
+  assert!(json.contains(r#""total":1000"#));
+}
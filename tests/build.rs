@@ -0,0 +1,74 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+pub struct User {
+  name: String,
+}
+
+factori!(User, {
+  default {
+    name: String = "Richard".to_string()
+  }
+
+  transient {
+    upcased: bool = false
+  }
+
+  mixin upcased {
+    upcased = true
+  }
+
+  builder {
+    let name = if upcased { name.to_uppercase() } else { name };
+    User { name }
+  }
+});
+
+#[test]
+fn without_builder_block_is_the_type() {
+  let vehicle: Vehicle = build!(Vehicle, :bike, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn exposes_fields_before_building() {
+  let builder = build!(User, :upcased, name: "John".into());
+  assert_eq!(builder.name, "John");
+  assert!(builder.upcased);
+}
+
+#[test]
+fn can_be_built_later() {
+  let builder = build!(User, :upcased);
+  let user = builder.build();
+  assert_eq!(user.name, "RICHARD");
+}
+
+fn greet(user: impl Into<User>) -> String {
+  format!("Hello {}", user.into().name)
+}
+
+#[test]
+fn converts_into_the_type() {
+  assert_eq!(greet(build!(User, :upcased)), "Hello RICHARD");
+
+  let user: User = build!(User, name: "John".into()).into();
+  assert_eq!(user.name, "John");
+}
@@ -0,0 +1,41 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+}
+
+factori!(#[factori(builder_derive(Debug, PartialEq))] Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+  }
+
+  transient {
+    electric: bool = false,
+  }
+
+  builder {
+    let _ = electric;
+    Vehicle { number_wheels }
+  }
+
+  mixin electric {
+    electric = true,
+  }
+});
+
+#[test]
+fn builders_with_the_same_values_are_equal() {
+  assert_eq!(build!(Vehicle), build!(Vehicle));
+  assert_eq!(build!(Vehicle, number_wheels: 2), build!(Vehicle, number_wheels: 2));
+}
+
+#[test]
+fn builders_with_different_transients_are_not_equal() {
+  assert_ne!(build!(Vehicle), build!(Vehicle, electric: true));
+}
+
+#[test]
+fn builders_with_different_mixins_are_not_equal() {
+  assert_ne!(build!(Vehicle, :electric), build!(Vehicle, electric: true));
+}
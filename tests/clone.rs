@@ -0,0 +1,41 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::borrow::Cow;
+
+static DEFAULT_NAME: Cow<'static, str> = Cow::Borrowed("Lamp");
+
+static FEATURED: &[&str] = &["featured"];
+
+pub struct Product {
+  name: Cow<'static, str>,
+  tags: Vec<String>,
+}
+
+factori!(Product, {
+  default {
+    name = clone!(DEFAULT_NAME),
+    tags = vec!["new".to_string()],
+  }
+
+  mixin featured {
+    tags = FEATURED.iter().map(|tag| tag.to_string()).collect(),
+  }
+});
+
+#[test]
+fn clones_the_default_for_every_instance() {
+  let products = create_vec!(Product, 3);
+  assert!(products.iter().all(|product| product.name == "Lamp"));
+  assert_eq!(create!(Product).name, DEFAULT_NAME);
+}
+
+#[test]
+fn mixins_and_overrides_replace_the_clone() {
+  assert_eq!(create!(Product, :featured).tags, ["featured"]);
+
+  let tags = vec!["old".to_string()];
+  let product = create!(Product, tags: clone!(tags));
+  assert_eq!(product.tags, tags);
+  assert_eq!(product.name, "Lamp");
+}
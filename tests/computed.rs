@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Rectangle {
+  area: u32,
+  perimeter: u32,
+  label: String,
+}
+
+factori!(Rectangle, {
+  default {
+    width: u32 = 3,
+    height: u32 = 4,
+  }
+
+  transient {
+    unit: &'static str = "cm",
+  }
+
+  computed {
+    let area = width * height;
+    let perimeter = 2 * (width + height);
+  }
+
+  mixin square {
+    height = 3,
+  }
+
+  builder {
+    Rectangle {
+      area,
+      perimeter,
+      label: format!("{}{}²", area, unit),
+    }
+  }
+});
+
+#[test]
+fn builder_uses_computed_bindings() {
+  let rectangle = create!(Rectangle);
+  assert_eq!(rectangle.area, 12);
+  assert_eq!(rectangle.perimeter, 14);
+  assert_eq!(rectangle.label, "12cm²");
+}
+
+#[test]
+fn computed_from_overridden_fields() {
+  let rectangle = create!(Rectangle, :square, width: 5, unit: "m");
+  assert_eq!(rectangle.area, 15);
+  assert_eq!(rectangle.perimeter, 16);
+  assert_eq!(rectangle.label, "15m²");
+}
+
+#[test]
+fn computed_in_builder_setters() {
+  let rectangle = build!(Rectangle).width(10).build();
+  assert_eq!(rectangle.area, 40);
+}
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  mixin trike {
+    number_wheels = 3,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+});
+
+#[test]
+fn applied_when_true() {
+  let is_ev = true;
+  let vehicle = create!(Vehicle, :electric if is_ev);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn skipped_when_false() {
+  let is_ev = false;
+  let vehicle = create!(Vehicle, :bike, :electric if is_ev);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(!vehicle.electric);
+}
+
+#[test]
+fn applied_in_order() {
+  let vehicle = create!(Vehicle, :bike if 1 + 1 == 2, :trike);
+  assert_eq!(vehicle.number_wheels, 3);
+
+  let vehicle = create!(Vehicle, :trike, :bike if true, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, :trike if false, :bike if false);
+  assert_eq!(vehicle.number_wheels, 4);
+}
+
+#[test]
+fn with_create_vec() {
+  let vehicles = create_vec!(Vehicle, 2, :bike if true);
+  assert!(vehicles.iter().all(|vehicle| vehicle.number_wheels == 2));
+}
@@ -0,0 +1,57 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Buffer<const N: usize> {
+  data: [u8; N],
+  len: usize,
+}
+
+factori!(Buffer<16>, {
+  default {
+    data = [0u8; 16],
+    len = 0,
+  }
+
+  mixin full {
+    data = [1u8; 16],
+    len = 16,
+  }
+});
+
+factori!(Buffer<4>, {
+  default {
+    data = [7u8; 4],
+    len = 4,
+  }
+});
+
+#[test]
+fn creates_the_instantiation() {
+  let buffer = create!(Buffer<16>);
+
+  assert_eq!(buffer.data, [0u8; 16]);
+  assert_eq!(buffer.len, 0);
+}
+
+#[test]
+fn works_with_mixins_and_overrides() {
+  let buffer = create!(Buffer<16>, :full, len: 8);
+
+  assert_eq!(buffer.data, [1u8; 16]);
+  assert_eq!(buffer.len, 8);
+}
+
+#[test]
+fn each_instantiation_has_its_factory() {
+  let buffers = create_vec!(Buffer<4>, 2);
+
+  assert_eq!(buffers.len(), 2);
+  assert_eq!(buffers[0].data, [7u8; 4]);
+}
+
+#[test]
+fn works_with_create_default() {
+  let buffer: Buffer<4> = create_default!();
+
+  assert_eq!(buffer.len, 4);
+}
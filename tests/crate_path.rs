@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate factori_imp as factori;
+
+mod shadowed {
+  // The generated code must not resolve to a module with the crate's name.
+  #[allow(dead_code)]
+  mod factori_imp {}
+
+  pub struct Vehicle {
+    pub number_wheels: u8,
+  }
+
+  factori!(Vehicle, {
+    default {
+      number_wheels = 4,
+    }
+
+    mixin bike {
+      number_wheels = 2,
+    }
+  });
+
+  pub struct User {
+    pub name: String,
+  }
+
+  factori!(User, {
+    default {
+      name: String = "Alice".to_string(),
+    }
+
+    transient {
+      upcased: bool = false,
+    }
+
+    builder {
+      let name = if upcased { name.to_uppercase() } else { name };
+      User { name }
+    }
+  });
+
+  #[test]
+  fn create_macros() {
+    assert_eq!(create!(Vehicle, :bike).number_wheels, 2);
+    assert_eq!(create!(User, upcased: true).name, "ALICE");
+    assert_eq!(create_vec!(Vehicle, 2).len(), 2);
+    assert_eq!(create_hashmap!(Vehicle, 2, key = number_wheels).len(), 1);
+    assert!(try_create!(User).is_ok());
+    assert_eq!(create_with_seed!(Vehicle, 1).number_wheels, 4);
+
+    let vehicle: Vehicle = create_default!();
+    assert_eq!(vehicle.number_wheels, 4);
+  }
+
+  #[test]
+  fn shared() {
+    assert_eq!(create_shared!(User).name, "Alice");
+    reset_shared!(User);
+  }
+}
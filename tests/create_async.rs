@@ -0,0 +1,121 @@
+#![cfg(feature = "async")]
+// The `async` feature needs Rust 1.75, above the minimum supported version.
+#![allow(clippy::incompatible_msrv)]
+
+#[macro_use]
+extern crate factori_imp;
+
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct Noop;
+
+impl Wake for Noop {
+  fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+  let mut future = Box::pin(future);
+  let waker = Waker::from(Arc::new(Noop));
+  let mut context = Context::from_waker(&waker);
+  loop {
+    if let Poll::Ready(value) = future.as_mut().poll(&mut context) {
+      return value;
+    }
+  }
+}
+
+/// Returns `Pending` once before the value, like a future waiting on I/O.
+async fn connect(url: String) -> String {
+  let mut pending = true;
+  std::future::poll_fn(|_| {
+    if pending {
+      pending = false;
+      Poll::Pending
+    } else {
+      Poll::Ready(())
+    }
+  })
+  .await;
+  url
+}
+
+pub struct Client {
+  url: String,
+  retries: u8,
+}
+
+factori!(Client, {
+  default {
+    url: String = "http://localhost".to_string(),
+    retries: u8 = 3,
+  }
+
+  transient {
+    secure: bool = false,
+  }
+
+  builder async {
+    let url = if secure { url.replace("http:", "https:") } else { url };
+    Client { url: connect(url).await, retries }
+  }
+
+  mixin secure {
+    secure = true,
+  }
+});
+
+#[test]
+fn create_async() {
+  let client = block_on(create_async!(Client));
+
+  assert_eq!(client.url, "http://localhost");
+  assert_eq!(client.retries, 3);
+}
+
+#[test]
+fn mixins_and_overrides() {
+  let client = block_on(create_async!(Client, :secure, retries: 0));
+
+  assert_eq!(client.url, "https://localhost");
+  assert_eq!(client.retries, 0);
+}
+
+#[test]
+fn overrides_are_evaluated_right_away() {
+  let mut url = "http://example.com".to_string();
+  let future = create_async!(Client, url: url.clone());
+  url.clear();
+
+  assert_eq!(block_on(future).url, "http://example.com");
+}
+
+#[test]
+fn builder_and_setters() {
+  use factori_imp::{AsyncBuilder, FactoriBuilder};
+
+  let client = block_on(AsyncBuilder::build(build!(Client, retries: 1)));
+  assert_eq!(client.retries, 1);
+
+  let client = block_on(Client::factori_builder().retries(2).secure(true).build());
+  assert_eq!(client.retries, 2);
+  assert_eq!(client.url, "https://localhost");
+}
+
+#[test]
+fn create_vec_async() {
+  let clients = block_on(create_vec_async!(Client, 3, :secure));
+  assert_eq!(clients.len(), 3);
+  assert!(clients.iter().all(|client| client.url == "https://localhost"));
+
+  let clients = block_on(create_vec_async!(Client, [(:secure), (retries: 0)]));
+  assert_eq!(clients[0].url, "https://localhost");
+  assert_eq!(clients[1].retries, 0);
+
+  let clients = block_on(create_vec_async!(Client, retries = [1, 2], |clients| {
+    clients.reverse();
+  }));
+  assert_eq!(clients[0].retries, 2);
+  assert_eq!(clients[1].retries, 1);
+}
@@ -0,0 +1,48 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[test]
+fn collects_into_vecdeque() {
+  let mut queue = create_collection!(VecDeque<_>, Vehicle, 3, :bike);
+  assert_eq!(queue.len(), 3);
+  assert_eq!(queue.pop_front().unwrap().number_wheels, 2);
+}
+
+#[test]
+fn collects_into_hashset() {
+  let set = create_collection!(HashSet<Vehicle>, Vehicle, 3);
+  assert_eq!(set.len(), 1);
+
+  let set = create_collection!(HashSet<_>, Vehicle, [(), (:bike), (electric: true)]);
+  assert_eq!(set.len(), 3);
+  assert!(set.contains(&Vehicle {
+    number_wheels: 2,
+    electric: false,
+  }));
+}
+
+#[test]
+fn collects_with_zipped_values() {
+  let set = create_collection!(BTreeSet<_>, Vehicle, number_wheels = [3, 1, 2]);
+  let wheels: Vec<_> = set.iter().map(|vehicle| vehicle.number_wheels).collect();
+  assert_eq!(wheels, vec![1, 2, 3]);
+}
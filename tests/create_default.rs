@@ -0,0 +1,124 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+});
+
+pub struct User {
+  name: String,
+}
+
+factori!(User, {
+  default {
+    name: String = "Richard".to_string()
+  }
+
+  transient {
+    upcased: bool = true
+  }
+
+  builder {
+    let name = if upcased { name.to_uppercase() } else { name };
+    User { name }
+  }
+});
+
+fn make_vehicle() -> Vehicle {
+  create_default!()
+}
+
+#[test]
+fn infers_from_return_type() {
+  let vehicle = make_vehicle();
+  assert_eq!(vehicle.number_wheels, 4);
+  assert!(!vehicle.electric);
+}
+
+#[test]
+fn infers_from_binding() {
+  let vehicle: Vehicle = create_default!();
+  assert_eq!(vehicle.number_wheels, 4);
+}
+
+#[test]
+fn runs_the_builder() {
+  let user: User = create_default!();
+  assert_eq!(user.name, "RICHARD");
+}
+
+pub struct Owner {
+  vehicle: Vehicle,
+  user: User,
+}
+
+factori!(Owner, {
+  default {
+    vehicle = create_default!(),
+    user = create_default!(),
+  }
+});
+
+pub struct Fleet {
+  vehicles: Vec<Vehicle>,
+}
+
+factori!(Fleet, {
+  default {
+    lead: Vehicle = create_default!(),
+  }
+
+  builder {
+    Fleet { vehicles: vec![lead] }
+  }
+});
+
+#[test]
+fn infers_from_struct_field() {
+  let owner = create!(Owner);
+  assert_eq!(owner.vehicle.number_wheels, 4);
+  assert_eq!(owner.user.name, "RICHARD");
+}
+
+#[test]
+fn infers_from_default_block_type() {
+  let fleet = create!(Fleet);
+  assert_eq!(fleet.vehicles[0].number_wheels, 4);
+}
+
+#[test]
+fn overrides_replace_it() {
+  let owner = create!(Owner, vehicle: create!(Vehicle, number_wheels: 2));
+  assert_eq!(owner.vehicle.number_wheels, 2);
+}
+
+#[derive(Debug)]
+pub struct Passenger {
+  pub name: &'static str,
+  pub seat: u8,
+}
+
+factori!(Passenger, {
+  default {
+    name = "Michael",
+    seat = 1,
+  }
+});
+
+#[test]
+fn describes_the_defaults() {
+  use factori_imp::FactoriDefault;
+
+  assert_eq!(
+    Passenger::factori_default_debug(),
+    "Passenger {\n    name: \"Michael\",\n    seat: 1,\n}"
+  );
+}
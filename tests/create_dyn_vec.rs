@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::fmt::Debug;
+
+trait Describe {
+  fn describe(&self) -> String;
+}
+
+pub struct Vehicle {
+  number_wheels: u8,
+}
+
+impl Describe for Vehicle {
+  fn describe(&self) -> String {
+    format!("vehicle with {} wheels", self.number_wheels)
+  }
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[derive(Debug)]
+pub struct Passenger {
+  name: &'static str,
+}
+
+impl Describe for Passenger {
+  fn describe(&self) -> String {
+    format!("passenger {}", self.name)
+  }
+}
+
+factori!(Passenger, {
+  default {
+    name = "Michael",
+  }
+});
+
+mod fleet {
+  #[derive(Debug)]
+  pub struct Truck {
+    pub axles: u8,
+  }
+
+  factori!(in factories; Truck, {
+    default {
+      axles = 2,
+    }
+  });
+}
+
+impl Describe for fleet::Truck {
+  fn describe(&self) -> String {
+    format!("truck with {} axles", self.axles)
+  }
+}
+
+#[test]
+fn different_factories() {
+  let items = create_dyn_vec!(dyn Describe, [Vehicle, Passenger, Vehicle]);
+  let descriptions: Vec<_> = items.iter().map(|item| item.describe()).collect();
+
+  assert_eq!(
+    descriptions,
+    ["vehicle with 4 wheels", "passenger Michael", "vehicle with 4 wheels"]
+  );
+}
+
+#[test]
+fn arguments_per_element() {
+  let items = create_dyn_vec!(dyn Describe, [
+    (Vehicle, :bike),
+    (Passenger, name: "Ann"),
+    (Vehicle, number_wheels: 3),
+  ]);
+  let descriptions: Vec<_> = items.iter().map(|item| item.describe()).collect();
+
+  assert_eq!(
+    descriptions,
+    ["vehicle with 2 wheels", "passenger Ann", "vehicle with 3 wheels"]
+  );
+}
+
+#[test]
+fn other_trait_objects() {
+  let items = create_dyn_vec!(dyn Debug + Send, [Passenger, (fleet::factories::Truck, axles: 3)]);
+
+  assert_eq!(format!("{:?}", items[1]), "Truck { axles: 3 }");
+}
+
+#[test]
+fn empty() {
+  let items = create_dyn_vec!(dyn Describe, []);
+
+  assert!(items.is_empty());
+}
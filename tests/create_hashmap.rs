@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static NEXT_ID: Cell<u32> = const { Cell::new(1) };
+}
+
+fn next_id() -> u32 {
+  NEXT_ID.with(|id| id.replace(id.get() + 1))
+}
+
+pub struct User {
+  id: u32,
+  name: String,
+  admin: bool,
+}
+
+factori!(User, {
+  default {
+    id = next_id(),
+    name = format!("user{}", next_id()),
+    admin = false,
+  }
+
+  mixin admin {
+    admin = true,
+  }
+});
+
+#[test]
+fn keyed_by_a_field() {
+  let users = create_hashmap!(User, 3, key = id);
+
+  assert_eq!(users.len(), 3);
+  users.iter().for_each(|(id, user)| {
+    assert_eq!(*id, user.id);
+  });
+}
+
+#[test]
+fn keyed_by_a_cloned_field() {
+  let users = create_hashmap!(User, 2, key = name, :admin);
+
+  assert_eq!(users.len(), 2);
+  users.iter().for_each(|(name, user)| {
+    assert_eq!(*name, user.name);
+    assert!(user.admin);
+  });
+}
+
+#[test]
+fn same_keys_replace_each_other() {
+  let users = create_hashmap!(User, 3, key = admin, name: "same".to_string());
+
+  assert_eq!(users.len(), 1);
+  assert_eq!(users[&false].name, "same");
+}
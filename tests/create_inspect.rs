@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  name: String,
+}
+
+factori!(#[factori(clone_builder)] Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    name: String = "car".to_string(),
+  }
+
+  transient {
+    doubled: bool = false,
+  }
+
+  builder {
+    let number_wheels = if doubled { number_wheels * 2 } else { number_wheels };
+    Vehicle { number_wheels, name }
+  }
+
+  mixin bike {
+    number_wheels = 2,
+    name = "bike".to_string(),
+  }
+
+  mixin doubled {
+    doubled = true,
+  }
+});
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Passenger {
+  name: &'static str,
+}
+
+factori!(Passenger, {
+  default {
+    name = "Michael",
+  }
+});
+
+#[test]
+fn returns_the_builder_before_building() {
+  let (vehicle, builder) = create_inspect!(Vehicle, :bike, :doubled);
+
+  assert_eq!(vehicle.number_wheels, 4);
+  assert_eq!(builder.number_wheels, 2);
+  assert!(builder.doubled);
+  assert_eq!(builder.name, vehicle.name);
+}
+
+#[test]
+fn overrides() {
+  let (vehicle, builder) = create_inspect!(Vehicle, number_wheels: 3, doubled: true);
+
+  assert_eq!(vehicle.number_wheels, 6);
+  assert_eq!(builder.number_wheels, 3);
+}
+
+#[test]
+fn builder_can_be_built_again() {
+  let (vehicle, builder) = create_inspect!(Vehicle, :doubled);
+  let again = factori_imp::Builder::build(builder);
+
+  assert_eq!(vehicle.number_wheels, again.number_wheels);
+}
+
+#[test]
+fn without_builder_block() {
+  let (passenger, builder) = create_inspect!(Passenger, name: "Ann");
+
+  assert_eq!(passenger, builder);
+}
@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Address {
+  city: String,
+}
+
+pub struct User {
+  name: String,
+  age: u64,
+  nickname: Option<String>,
+  address: Address,
+}
+
+factori!(User, {
+  default {
+    name = "Alice".to_string(),
+    age = 30,
+    nickname = None,
+    address = Address { city: "Paris".to_string() },
+  }
+
+  mixin bob {
+    name = "Bob".to_string(),
+  }
+});
+
+#[test]
+fn str_to_string() {
+  let user = create_into!(User, name: "John");
+  assert_eq!(user.name, "John");
+}
+
+#[test]
+fn values_of_the_field_type() {
+  let user = create_into!(User, name: String::from("John"), age: 40);
+  assert_eq!(user.name, "John");
+  assert_eq!(user.age, 40);
+}
+
+#[test]
+fn widening() {
+  let age: u8 = 40;
+  let user = create_into!(User, age: age);
+  assert_eq!(user.age, 40);
+}
+
+#[test]
+fn option() {
+  let user = create_into!(User, nickname: "Johnny".to_string());
+  assert_eq!(user.nickname.as_deref(), Some("Johnny"));
+}
+
+#[test]
+fn mixins() {
+  let user = create_into!(User, :bob, age: 20u8);
+  assert_eq!(user.name, "Bob");
+  assert_eq!(user.age, 20);
+}
+
+#[test]
+fn refers_to_default() {
+  let user = create_into!(User, name: default + " Smith");
+  assert_eq!(user.name, "Alice Smith");
+}
+
+#[test]
+fn dotted_paths_as_is() {
+  let user = create_into!(User, name: "John", address.city: "Berlin".to_string());
+  assert_eq!(user.name, "John");
+  assert_eq!(user.address.city, "Berlin");
+}
+
+#[test]
+fn number_literals_as_is() {
+  let user = create_into!(User, age: 40);
+  assert_eq!(user.age, 40);
+}
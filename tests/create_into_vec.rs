@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static BUILDS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn count_build() -> u32 {
+  BUILDS.with(|builds| {
+    builds.set(builds.get() + 1);
+    builds.get()
+  })
+}
+
+pub struct Vehicle {
+  id: u32,
+  number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+  }
+
+  transient {
+    double_wheels: bool = false,
+  }
+
+  builder {
+    let number_wheels = if double_wheels { number_wheels * 2 } else { number_wheels };
+    Vehicle { id: count_build(), number_wheels }
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[test]
+fn pushes_into_the_vec() {
+  let mut vehicles = vec![create!(Vehicle)];
+  create_into_vec!(&mut vehicles, Vehicle, 3, :bike);
+
+  assert_eq!(vehicles.len(), 4);
+  assert_eq!(vehicles[0].number_wheels, 4);
+  assert!(vehicles[1..].iter().all(|vehicle| vehicle.number_wheels == 2));
+}
+
+#[test]
+fn reuses_the_allocation() {
+  let mut vehicles = Vec::with_capacity(10);
+  let pointer = vehicles.as_ptr();
+
+  for _ in 0..3 {
+    vehicles.clear();
+    create_into_vec!(&mut vehicles, Vehicle, 10);
+  }
+
+  assert_eq!(vehicles.len(), 10);
+  assert_eq!(vehicles.as_ptr(), pointer);
+}
+
+#[test]
+fn builds_every_element() {
+  let mut vehicles = Vec::new();
+  create_into_vec!(&mut vehicles, Vehicle, 3, double_wheels: true);
+
+  assert_eq!(vehicles[1].id, vehicles[0].id + 1);
+  assert_eq!(vehicles[2].id, vehicles[0].id + 2);
+  assert!(vehicles.iter().all(|vehicle| vehicle.number_wheels == 8));
+}
+
+#[test]
+fn through_a_reference() {
+  fn fill(vehicles: &mut Vec<Vehicle>) {
+    create_into_vec!(vehicles, Vehicle, 2);
+    create_into_vec!(vehicles, Vehicle, 1, :bike);
+  }
+
+  let mut vehicles = Vec::new();
+  fill(&mut vehicles);
+  assert_eq!(vehicles.len(), 3);
+  assert_eq!(vehicles[2].number_wheels, 2);
+}
+
+#[test]
+fn with_a_list_of_elements() {
+  let mut vehicles = Vec::new();
+  create_into_vec!(&mut vehicles, Vehicle, [(:bike), (number_wheels: 3)]);
+
+  assert_eq!(vehicles.len(), 2);
+  assert_eq!(vehicles[1].number_wheels, 3);
+}
+
+#[test]
+fn with_values_per_element() {
+  let mut vehicles = Vec::new();
+  create_into_vec!(&mut vehicles, Vehicle, number_wheels = [1, 2], double_wheels: true);
+
+  assert_eq!(vehicles.len(), 2);
+  assert_eq!(vehicles[1].number_wheels, 4);
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "serde")]
+
+#[macro_use]
+extern crate factori_imp;
+
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  tags: Vec<&'static str>,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+    tags = vec![],
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[test]
+fn serializes_the_default() {
+  assert_eq!(
+    create_json!(Vehicle),
+    r#"{"number_wheels":4,"electric":false,"tags":[]}"#
+  );
+}
+
+#[test]
+fn with_mixins_and_fields() {
+  let json = create_json!(Vehicle, :bike, electric: true, tags: vec!["city"]);
+
+  assert_eq!(
+    json,
+    r#"{"number_wheels":2,"electric":true,"tags":["city"]}"#
+  );
+}
@@ -0,0 +1,45 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+fn describe(vehicle: &Vehicle) -> String {
+  let kind = if vehicle.electric { "electric" } else { "fuel" };
+  format!("{} wheels, {}", vehicle.number_wheels, kind)
+}
+
+#[test]
+fn maps_each_instance() {
+  let descriptions: Vec<String> = create_mapped!(Vehicle, 2, |vehicle| describe(&vehicle));
+
+  assert_eq!(descriptions, ["4 wheels, fuel", "4 wheels, fuel"]);
+}
+
+#[test]
+fn works_with_mixins_and_overrides() {
+  let descriptions = create_mapped!(Vehicle, 3, :bike, electric: true, |vehicle| describe(&vehicle));
+
+  assert_eq!(descriptions.len(), 3);
+  assert!(descriptions.iter().all(|description| description == "2 wheels, electric"));
+}
+
+#[test]
+fn works_with_a_list_of_elements() {
+  let wheels = create_mapped!(Vehicle, [(:bike), ()], |vehicle| vehicle.number_wheels);
+
+  assert_eq!(wheels, [2, 4]);
+}
@@ -0,0 +1,88 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::sync::Arc;
+
+pub struct Config {
+  url: &'static str,
+}
+
+factori!(Config, {
+  default {
+    url = "http://localhost",
+  }
+});
+
+pub struct Server {
+  config: Arc<Config>,
+}
+
+factori!(Server, {
+  default {
+    config = create_shared!(Config),
+  }
+});
+
+pub struct Database {
+  name: &'static str,
+}
+
+factori!(Database, {
+  default {
+    name = "test",
+  }
+
+  mixin archive {
+    name = "archive",
+  }
+});
+
+#[test]
+fn builds_once() {
+  let config = create_shared!(Config);
+  let same_config = create_shared!(Config);
+
+  assert!(Arc::ptr_eq(&config, &same_config));
+  assert_eq!(config.url, "http://localhost");
+}
+
+#[test]
+fn shared_per_thread() {
+  let config = create_shared!(Config);
+  let other_config = std::thread::spawn(|| create_shared!(Config))
+    .join()
+    .unwrap();
+
+  assert!(!Arc::ptr_eq(&config, &other_config));
+  assert!(Arc::ptr_eq(&config, &create_shared!(Config)));
+}
+
+#[test]
+fn used_in_other_factories() {
+  let server = create!(Server);
+
+  assert!(Arc::ptr_eq(&server.config, &create_shared!(Config)));
+}
+
+#[test]
+fn an_instance_per_override() {
+  let first = create_shared!(Database, name: "first");
+  let second = create_shared!(Database, name: "second");
+
+  assert_eq!(first.name, "first");
+  assert_eq!(second.name, "second");
+  assert!(Arc::ptr_eq(&first, &create_shared!(Database, name: "first")));
+  assert!(Arc::ptr_eq(&second, &create_shared!(Database, name: "second")));
+  assert!(!Arc::ptr_eq(&first, &create_shared!(Database)));
+  assert_eq!(create_shared!(Database, :archive).name, "archive");
+}
+
+#[test]
+fn reset() {
+  let database = create_shared!(Database);
+  let first = create_shared!(Database, name: "first");
+
+  reset_shared!(Database);
+  assert!(!Arc::ptr_eq(&database, &create_shared!(Database)));
+  assert!(!Arc::ptr_eq(&first, &create_shared!(Database, name: "first")));
+}
@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+struct Garage {
+  vehicles: Vec<Vehicle>,
+}
+
+#[test]
+fn create_pushes_into_vec() {
+  let mut garage = Garage { vehicles: Vec::new() };
+  create!(Vehicle => garage.vehicles, :bike);
+  create!(Vehicle => garage.vehicles, electric: true);
+
+  assert_eq!(
+    garage.vehicles,
+    vec![
+      Vehicle { number_wheels: 2, electric: false },
+      Vehicle { number_wheels: 4, electric: true },
+    ]
+  );
+}
+
+#[test]
+fn create_inserts_into_hashset() {
+  let mut vehicles = HashSet::new();
+  create!(Vehicle => vehicles);
+  create!(Vehicle => vehicles);
+  create!(Vehicle => vehicles, :bike);
+
+  assert_eq!(vehicles.len(), 2);
+  assert!(vehicles.contains(&Vehicle { number_wheels: 2, electric: false }));
+}
+
+#[test]
+fn create_into_target_is_unit() {
+  let mut vehicles = Vec::new();
+  let () = create!(Vehicle => vehicles);
+  assert_eq!(vehicles.len(), 1);
+}
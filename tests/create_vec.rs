@@ -80,3 +80,153 @@ fn works_with_all_of_them() {
     assert!(vehicle.electric);
   });
 }
+
+#[test]
+fn works_with_a_block_count() {
+  let vehicles = create_vec!(Vehicle, { let sizes = [1, 2, 3]; sizes.len() }, :bike);
+
+  assert_eq!(vehicles.len(), 3);
+}
+
+#[test]
+fn works_with_a_closure_count() {
+  let calls = std::cell::Cell::new(0);
+  let vehicles = create_vec!(Vehicle, || { calls.set(calls.get() + 1); 2 }, :trike);
+
+  assert_eq!(vehicles.len(), 2);
+  assert_eq!(calls.get(), 1);
+  vehicles.iter().for_each(|vehicle| {
+    assert_eq!(vehicle.number_wheels, 3);
+  });
+}
+
+#[test]
+fn works_with_a_closure_over_the_vec() {
+  let vehicles = create_vec!(Vehicle, 3, :bike, |vehicles: &mut Vec<Vehicle>| {
+    for (i, vehicle) in vehicles.iter_mut().enumerate() {
+      vehicle.number_wheels += i as u8;
+    }
+  });
+
+  let wheels: Vec<_> = vehicles.iter().map(|vehicle| vehicle.number_wheels).collect();
+  assert_eq!(wheels, [2, 3, 4]);
+}
+
+#[test]
+fn works_with_a_closure_over_the_vec_after_fields() {
+  let vehicles = create_vec!(Vehicle, 2, electric: true, |vehicles| {
+    vehicles.push(create!(Vehicle));
+  });
+
+  assert_eq!(vehicles.len(), 3);
+  assert!(vehicles[0].electric);
+  assert!(!vehicles[2].electric);
+}
+
+#[test]
+fn works_with_a_list_of_elements() {
+  let vehicles = create_vec!(Vehicle, [(:bike), (:bike, electric: true), (), (:trike)]);
+
+  let wheels: Vec<_> = vehicles.iter().map(|vehicle| vehicle.number_wheels).collect();
+  assert_eq!(wheels, [2, 2, 4, 3]);
+  assert!(vehicles[1].electric);
+  assert!(!vehicles[0].electric);
+}
+
+#[test]
+fn works_with_an_empty_list() {
+  let vehicles: Vec<Vehicle> = create_vec!(Vehicle, []);
+
+  assert!(vehicles.is_empty());
+}
+
+#[test]
+fn works_with_a_list_and_a_closure_over_the_vec() {
+  let vehicles = create_vec!(Vehicle, [(:bike), (double_wheels: true)], |vehicles| {
+    vehicles.reverse();
+  });
+
+  let wheels: Vec<_> = vehicles.iter().map(|vehicle| vehicle.number_wheels).collect();
+  assert_eq!(wheels, [8, 2]);
+}
+
+#[test]
+fn works_with_values_per_element() {
+  let vehicles = create_vec!(Vehicle, number_wheels = [1, 2, 3]);
+
+  assert_eq!(vehicles.len(), 3);
+  assert_eq!(vehicles[0].number_wheels, 1);
+  assert_eq!(vehicles[2].number_wheels, 3);
+}
+
+#[test]
+fn works_with_several_values_per_element_and_the_rest() {
+  let electric = vec![true, false];
+  let vehicles = create_vec!(
+    Vehicle,
+    number_wheels = [2, 3],
+    electric = electric,
+    double_wheels: true,
+    |vehicles| vehicles.reverse()
+  );
+
+  assert_eq!(vehicles.len(), 2);
+  assert_eq!(vehicles[0].number_wheels, 6);
+  assert!(!vehicles[0].electric);
+  assert_eq!(vehicles[1].number_wheels, 4);
+  assert!(vehicles[1].electric);
+}
+
+#[test]
+fn works_with_mixins_after_values_per_element() {
+  let vehicles = create_vec!(Vehicle, electric = (0..4).map(|i| i % 2 == 0), :bike);
+
+  assert_eq!(vehicles.len(), 4);
+  assert!(vehicles.iter().all(|vehicle| vehicle.number_wheels == 2));
+  assert!(vehicles[2].electric);
+  assert!(!vehicles[3].electric);
+}
+
+#[test]
+fn works_with_no_values_per_element() {
+  let vehicles = create_vec!(Vehicle, number_wheels = Vec::<u8>::new());
+
+  assert!(vehicles.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "must have the same length as those of `number_wheels`")]
+fn panics_with_values_of_different_lengths() {
+  let electric = vec![true];
+  let _ = create_vec!(Vehicle, number_wheels = [2, 3], electric = electric);
+}
+
+const BATCH_SIZE: usize = 3;
+static STATIC_BATCH_SIZE: usize = 2;
+
+mod sizes {
+  pub const SMALL: u8 = 1;
+}
+
+struct Batch;
+
+impl Batch {
+  const SIZE: usize = 4;
+}
+
+#[test]
+fn works_with_const_count() {
+  let vehicles = create_vec!(Vehicle, BATCH_SIZE);
+  assert_eq!(vehicles.len(), BATCH_SIZE);
+
+  let vehicles = create_vec!(Vehicle, BATCH_SIZE, :bike, electric: true);
+  assert_eq!(vehicles.len(), 3);
+  assert!(vehicles.iter().all(|v| v.number_wheels == 2 && v.electric));
+}
+
+#[test]
+fn works_with_static_and_path_counts() {
+  assert_eq!(create_vec!(Vehicle, STATIC_BATCH_SIZE).len(), 2);
+  assert_eq!(create_vec!(Vehicle, sizes::SMALL).len(), 1);
+  assert_eq!(create_vec!(Vehicle, Batch::SIZE, :trike).len(), 4);
+}
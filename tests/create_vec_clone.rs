@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static BUILDS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn count_build() -> u32 {
+  BUILDS.with(|builds| {
+    builds.set(builds.get() + 1);
+    builds.get()
+  })
+}
+
+#[derive(Clone)]
+pub struct Vehicle {
+  id: u32,
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    id: u32 = 0,
+    number_wheels: u8 = 4,
+    electric: bool = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  builder {
+    let id = count_build();
+    Vehicle { id, number_wheels, electric }
+  }
+});
+
+#[test]
+fn can_create_many() {
+  let vehicles = create_vec_clone!(Vehicle, 5);
+
+  assert_eq!(vehicles.len(), 5);
+}
+
+#[test]
+fn builds_only_once() {
+  let vehicles = create_vec_clone!(Vehicle, 3);
+
+  vehicles.iter().for_each(|vehicle| {
+    assert_eq!(vehicle.id, vehicles[0].id);
+  });
+}
+
+#[test]
+fn works_with_mixins_and_overrides() {
+  let vehicles = create_vec_clone!(Vehicle, 3, :bike, electric: true);
+
+  vehicles.iter().for_each(|vehicle| {
+    assert_eq!(vehicle.number_wheels, 2);
+    assert!(vehicle.electric);
+  });
+}
+
+#[test]
+fn works_with_a_closure_over_the_vec() {
+  let vehicles = create_vec_clone!(Vehicle, 3, |vehicles| {
+    vehicles.iter_mut().for_each(|vehicle| vehicle.electric = true);
+  });
+
+  assert_eq!(vehicles.len(), 3);
+  assert!(vehicles.iter().all(|vehicle| vehicle.electric));
+}
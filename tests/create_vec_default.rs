@@ -0,0 +1,65 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static CREATED: Cell<u32> = const { Cell::new(0) };
+}
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = {
+      CREATED.with(|created| created.set(created.get() + 1));
+      4
+    },
+  }
+});
+
+pub struct Garage {
+  pub vehicles: Vec<Vehicle>,
+}
+
+factori!(Garage, {
+  default {
+    vehicles: Vec<Vehicle> = create_vec!(Vehicle, vehicle_count, number_wheels: wheels),
+  }
+
+  transient {
+    vehicle_count: usize = 3,
+    wheels: u8 = 4,
+  }
+
+  builder {
+    Garage { vehicles }
+  }
+
+  mixin big {
+    vehicle_count = 10,
+  }
+});
+
+#[test]
+fn count_from_transient() {
+  assert_eq!(create!(Garage).vehicles.len(), 3);
+  assert_eq!(create!(Garage, vehicle_count: 5).vehicles.len(), 5);
+  assert_eq!(create!(Garage, :big).vehicles.len(), 10);
+}
+
+#[test]
+fn overrides_from_transient() {
+  let garage = create!(Garage, vehicle_count: 2, wheels: 2);
+  assert!(garage.vehicles.iter().all(|v| v.number_wheels == 2));
+}
+
+#[test]
+fn not_created_when_overridden() {
+  let before = CREATED.with(Cell::get);
+  let garage = create!(Garage, vehicles: Vec::new());
+  assert!(garage.vehicles.is_empty());
+  assert_eq!(CREATED.with(Cell::get), before);
+}
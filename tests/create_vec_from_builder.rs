@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static BUILDS: Cell<u32> = const { Cell::new(0) };
+  static DEFAULTS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn count(counter: &'static std::thread::LocalKey<Cell<u32>>) -> u32 {
+  counter.with(|counter| {
+    counter.set(counter.get() + 1);
+    counter.get()
+  })
+}
+
+// Not `Clone`, only its builder is.
+pub struct Vehicle {
+  id: u32,
+  number_wheels: u8,
+  name: String,
+}
+
+factori!(#[factori(clone_builder)] Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    name: String = format!("vehicle {}", count(&DEFAULTS)),
+  }
+
+  transient {
+    brand: String = "Generic".to_string(),
+  }
+
+  builder {
+    let id = count(&BUILDS);
+    Vehicle { id, number_wheels, name: format!("{} {}", brand, name) }
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Passenger {
+  name: &'static str,
+}
+
+factori!(#[factori(clone_builder)] Passenger, {
+  default {
+    name = "Michael",
+  }
+});
+
+#[test]
+fn can_create_many() {
+  let vehicles = create_vec_from_builder!(Vehicle, 5);
+
+  assert_eq!(vehicles.len(), 5);
+}
+
+#[test]
+fn evaluates_defaults_once() {
+  let before = DEFAULTS.with(Cell::get);
+  let vehicles = create_vec_from_builder!(Vehicle, 3);
+
+  assert_eq!(DEFAULTS.with(Cell::get), before + 1);
+  assert_eq!(vehicles[0].name, vehicles[2].name);
+}
+
+#[test]
+fn builds_every_element() {
+  let vehicles = create_vec_from_builder!(Vehicle, 3);
+
+  assert_eq!(vehicles[1].id, vehicles[0].id + 1);
+  assert_eq!(vehicles[2].id, vehicles[0].id + 2);
+}
+
+#[test]
+fn mixins_and_overrides() {
+  let vehicles = create_vec_from_builder!(Vehicle, 2, :bike, brand: "Acme".to_string());
+
+  assert!(vehicles.iter().all(|vehicle| vehicle.number_wheels == 2));
+  assert!(vehicles.iter().all(|vehicle| vehicle.name.starts_with("Acme ")));
+}
+
+#[test]
+fn without_builder_block() {
+  let passengers = create_vec_from_builder!(Passenger, 2, name: "Ann");
+
+  assert_eq!(passengers, vec![Passenger { name: "Ann" }; 2]);
+}
+
+#[test]
+fn closure() {
+  let vehicles = create_vec_from_builder!(Vehicle, 2, |vehicles| {
+    vehicles[1].number_wheels = 3;
+  });
+
+  assert_eq!(vehicles[0].number_wheels, 4);
+  assert_eq!(vehicles[1].number_wheels, 3);
+}
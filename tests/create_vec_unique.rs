@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static NEXT: Cell<u32> = const { Cell::new(0) };
+}
+
+fn next_id() -> u32 {
+  NEXT.with(|next| {
+    let id = next.get();
+    next.set(id + 1);
+    id
+  })
+}
+
+pub struct User {
+  email: String,
+  admin: bool,
+}
+
+factori!(User, {
+  default {
+    email = format!("user{}@example.com", next_id() % 3),
+    admin = false,
+  }
+
+  mixin admin {
+    admin = true,
+  }
+});
+
+#[test]
+fn keeps_distinct_keys() {
+  let users = create_vec_unique!(User, 3, key = email, :admin);
+  assert_eq!(users.len(), 3);
+  assert!(users.iter().all(|user| user.admin));
+
+  let mut emails: Vec<_> = users.iter().map(|user| user.email.as_str()).collect();
+  emails.sort_unstable();
+  assert_eq!(
+    emails,
+    [
+      "user0@example.com",
+      "user1@example.com",
+      "user2@example.com"
+    ]
+  );
+}
+
+#[test]
+fn zero_count() {
+  assert!(create_vec_unique!(User, 0, key = email).is_empty());
+}
+
+#[test]
+#[should_panic(
+  expected = "create_vec_unique! gave up after 40 attempts, with 3 of the 4 `User` having distinct `email` values"
+)]
+fn gives_up_without_enough_keys() {
+  create_vec_unique!(User, 4, key = email);
+}
+
+#[test]
+#[should_panic(expected = "with 1 of the 2 `User` having distinct `email` values")]
+fn duplicate_overrides() {
+  create_vec_unique!(User, 2, key = email, email: "admin@example.com".to_string());
+}
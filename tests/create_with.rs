@@ -0,0 +1,53 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+  pub color: String,
+}
+
+impl Vehicle {
+  fn paint(&mut self, color: &str) {
+    self.color = color.to_string();
+  }
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    color = String::new(),
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[test]
+fn with_closure_changes_the_instance() {
+  let vehicle = create!(Vehicle, :bike; with |v| {
+    v.paint("red");
+  });
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(vehicle.color, "red");
+}
+
+#[test]
+fn with_closure_after_overrides() {
+  let vehicle = create!(Vehicle, number_wheels: 3; with |v| v.number_wheels += 1);
+  assert_eq!(vehicle.number_wheels, 4);
+}
+
+#[test]
+fn with_closure_without_arguments() {
+  let color = "blue";
+  let vehicle = create!(Vehicle; with move |v| v.paint(color));
+  assert_eq!(vehicle.color, "blue");
+}
+
+#[test]
+fn with_closure_and_target() {
+  let mut vehicles = Vec::new();
+  create!(Vehicle => vehicles, :bike; with |v| v.paint("green"));
+  assert_eq!(vehicles[0].color, "green");
+}
@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct User {
+  id: u64,
+  admin: bool,
+}
+
+factori!(User, {
+  default {
+    id = factori_imp::rng().next_u64(),
+    admin = factori_imp::rng().gen_bool(),
+  }
+
+  mixin admin {
+    admin = true,
+  }
+});
+
+#[test]
+fn same_seed_same_values() {
+  let user = create_with_seed!(User, 1234);
+  let same_user = create_with_seed!(User, 1234);
+
+  assert_eq!(user.id, same_user.id);
+  assert_eq!(user.admin, same_user.admin);
+}
+
+#[test]
+fn different_seed_different_values() {
+  let user = create_with_seed!(User, 1234);
+  let other_user = create_with_seed!(User, 4321);
+
+  assert_ne!(user.id, other_user.id);
+}
+
+#[test]
+fn unaffected_by_unseeded_calls() {
+  let user = create_with_seed!(User, 1);
+  create!(User);
+  let same_user = create_with_seed!(User, 1);
+
+  assert_eq!(user.id, same_user.id);
+}
+
+#[test]
+fn does_not_seed_later_calls() {
+  create_with_seed!(User, 1);
+  let user = create!(User);
+  create_with_seed!(User, 1);
+  let other_user = create!(User);
+
+  assert_ne!(user.id, other_user.id);
+}
+
+#[test]
+fn works_with_mixins_and_overrides() {
+  let user = create_with_seed!(User, 1234, :admin);
+  assert!(user.admin);
+
+  let user = create_with_seed!(User, 1234, id: 5);
+  assert_eq!(user.id, 5);
+}
+
+#[test]
+fn gen_range_stays_in_range() {
+  let mut rng = factori_imp::rng();
+  for _ in 0..100 {
+    let value = rng.gen_range(10..20);
+    assert!((10..20).contains(&value));
+  }
+  assert_eq!(*rng.choose(&["only"]), "only");
+}
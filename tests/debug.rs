@@ -0,0 +1,20 @@
+#![cfg(feature = "debug")]
+
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+}
+
+debug_factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+  }
+});
+
+#[test]
+fn defines_the_factory() {
+  let vehicle = create!(Vehicle);
+  assert_eq!(vehicle.number_wheels, 4);
+}
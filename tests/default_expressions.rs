@@ -0,0 +1,109 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq)]
+pub struct Status(u8);
+
+impl Status {
+  pub const DEFAULT: Status = Status(1);
+
+  pub fn new<T: Into<u8>>(value: T) -> Self {
+    Status(value.into())
+  }
+}
+
+pub struct Wrapper<T>(T);
+
+impl<T: Default> Wrapper<T> {
+  pub fn make() -> T {
+    T::default()
+  }
+}
+
+pub struct Account {
+  status: Status,
+  capacity: usize,
+  scores: Vec<u8>,
+  size: usize,
+  limit: u8,
+  names: HashMap<u8, &'static str>,
+}
+
+factori!(Account, {
+  default {
+    status = Status::DEFAULT,
+    capacity = Wrapper::<usize>::make(),
+    scores = Vec::<u8>::with_capacity(2),
+    size = std::mem::size_of::<u32>(),
+    limit = <u8 as Default>::default(),
+    names = <HashMap<u8, &'static str>>::new(),
+  }
+
+  mixin active {
+    status = Status::new::<u8>(2),
+    scores = std::iter::repeat(7).take(3).collect::<Vec<_>>(),
+  }
+
+  mixin limited {
+    limit = <u8>::MAX,
+  }
+});
+
+pub struct User {
+  status: Status,
+  capacity: usize,
+}
+
+factori!(User, {
+  default {
+    status: Status = Status::DEFAULT,
+    capacity: usize = Wrapper::<usize>::make(),
+  }
+
+  transient {
+    extra: Vec<usize> = Vec::<usize>::new(),
+  }
+
+  builder {
+    User { status, capacity: capacity + extra.len() }
+  }
+});
+
+#[test]
+fn associated_constants_and_turbofish() {
+  let account = create!(Account);
+
+  assert_eq!(account.status, Status::DEFAULT);
+  assert_eq!(account.capacity, 0);
+  assert!(account.scores.is_empty());
+  assert_eq!(account.size, 4);
+  assert_eq!(account.limit, 0);
+  assert!(account.names.is_empty());
+}
+
+#[test]
+fn in_mixins() {
+  let account = create!(Account, :active, :limited);
+
+  assert_eq!(account.status, Status(2));
+  assert_eq!(account.scores, vec![7, 7, 7]);
+  assert_eq!(account.limit, u8::MAX);
+}
+
+#[test]
+fn in_overrides() {
+  let account = create!(Account, capacity: <usize as From<u8>>::from(3), status: Status::new::<u8>(5));
+
+  assert_eq!(account.capacity, 3);
+  assert_eq!(account.status, Status(5));
+}
+
+#[test]
+fn with_types_and_builder() {
+  let user = create!(User, extra: Vec::<usize>::from([1, 2]));
+
+  assert_eq!(user.status, Status::DEFAULT);
+  assert_eq!(user.capacity, 2);
+}
@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+  pub electric: bool,
+}
+
+factori!(Vehicle, {
+  default {}
+
+  transient {
+    wheels: u8 = 4,
+  }
+
+  builder {
+    Vehicle { number_wheels: wheels, electric: false }
+  }
+
+  mixin bike {
+    wheels = 2,
+  }
+});
+
+pub struct Clock {
+  pub hour: u8,
+}
+
+factori!(Clock, {
+  default {}
+
+  builder {
+    Clock { hour: 12 }
+  }
+});
+
+#[test]
+fn builder_from_transients_only() {
+  assert_eq!(create!(Vehicle).number_wheels, 4);
+  assert_eq!(create!(Vehicle, :bike).number_wheels, 2);
+  assert_eq!(create!(Vehicle, wheels: 6).number_wheels, 6);
+  assert!(!create!(Vehicle).electric);
+}
+
+#[test]
+fn builder_from_constants_only() {
+  assert_eq!(create!(Clock).hour, 12);
+  assert_eq!(create_vec!(Clock, 2).len(), 2);
+}
@@ -0,0 +1,83 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::RefCell;
+
+thread_local! {
+  static ORDER: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record<T>(name: &'static str, value: T) -> T {
+  ORDER.with(|order| order.borrow_mut().push(name));
+  value
+}
+
+fn take_order() -> Vec<&'static str> {
+  ORDER.with(|order| order.borrow_mut().drain(..).collect())
+}
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+  pub number_seats: u8,
+  pub electric: bool,
+}
+
+// The fields are deliberately in a different order than the struct's.
+factori!(Vehicle, {
+  default {
+    electric = record("electric", false),
+    number_seats = record("number_seats", 5),
+    number_wheels = record("number_wheels", 4),
+  }
+
+  mixin bike {
+    number_wheels = record("bike", 2),
+  }
+});
+
+pub struct User {
+  pub name: String,
+  pub age: u8,
+  pub avatar: String,
+}
+
+factori!(User, {
+  default {
+    lazy avatar: String = record("avatar", String::new()),
+    age: u8 = record("age", 30),
+    name: String = record("name", String::new()),
+  }
+
+  transient {
+    admin: bool = record("admin", false),
+  }
+
+  builder {
+    let _ = admin;
+    User { name, age, avatar }
+  }
+});
+
+#[test]
+fn defaults_follow_the_default_block() {
+  take_order();
+  create!(Vehicle);
+  assert_eq!(take_order(), vec!["electric", "number_seats", "number_wheels"]);
+}
+
+#[test]
+fn mixins_follow_the_defaults() {
+  take_order();
+  create!(Vehicle, :bike);
+  assert_eq!(
+    take_order(),
+    vec!["electric", "number_seats", "number_wheels", "bike"]
+  );
+}
+
+#[test]
+fn transient_and_lazy_fields_come_after_the_defaults() {
+  take_order();
+  create!(User);
+  assert_eq!(take_order(), vec!["age", "name", "admin", "avatar"]);
+}
@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(#[factori(exhaustive)] Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    electric: bool = false,
+  }
+
+  transient {
+    double_wheels: bool = false,
+  }
+
+  builder {
+    let number_wheels = if double_wheels { number_wheels * 2 } else { number_wheels };
+    Vehicle { number_wheels, electric }
+  }
+});
+
+pub struct Wrapper<T> {
+  inner: T,
+}
+
+factori!(#[factori(exhaustive)] Wrapper<T: Default>, {
+  default {
+    inner: T = Default::default(),
+  }
+
+  builder {
+    Wrapper { inner }
+  }
+});
+
+pub struct Seat {
+  kind: u8,
+}
+
+factori!(#[factori(exhaustive, prefix = "_SeatFactory")] Seat, {
+  default {
+    number as kind: u8 = 1,
+  }
+});
+
+#[test]
+fn builds_with_a_builder() {
+  let vehicle = create!(Vehicle, double_wheels: true);
+
+  assert_eq!(vehicle.number_wheels, 8);
+  assert!(!vehicle.electric);
+}
+
+#[test]
+fn works_with_generic_factories() {
+  let wrapper: Wrapper<u8> = create!(Wrapper);
+
+  assert_eq!(wrapper.inner, 0);
+}
+
+#[test]
+fn works_with_renamed_fields() {
+  let seat = create!(#[factori(prefix = "_SeatFactory")] Seat, number: 2);
+
+  assert_eq!(seat.kind, 2);
+}
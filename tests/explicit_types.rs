@@ -0,0 +1,86 @@
+#[macro_use]
+extern crate factori_imp;
+
+mod factories {
+  pub struct Vehicle {
+    pub number_wheels: u8,
+    pub electric: bool,
+  }
+
+  factori!(Vehicle, {
+    default {
+      number_wheels = 4,
+      electric = false,
+    }
+
+    mixin bike {
+      number_wheels = 2,
+    }
+
+    mixin electric {
+      electric = true,
+    }
+  });
+
+  pub struct Order(pub u64);
+
+  factori!(Order, {
+    default {
+      id: u64 = 1,
+    }
+
+    transient {
+      base_id: u64 = 100,
+    }
+
+    mixin imported {
+      base_id = 1000,
+    }
+
+    builder {
+      Order(base_id + id)
+    }
+  });
+}
+
+mod reexports {
+  pub use crate::factories::{
+    _Factori_Builder_Order as OrderBuilder, _Factori_Builder_Vehicle as VehicleBuilder,
+    _Factori_Mixins_Order as OrderMixins, _Factori_Mixins_Vehicle as VehicleMixins,
+  };
+}
+
+use reexports::{OrderBuilder, OrderMixins, VehicleBuilder, VehicleMixins};
+
+#[test]
+fn explicit_builder() {
+  let vehicle = create!(@builder VehicleBuilder);
+  assert_eq!(vehicle.number_wheels, 4);
+
+  let vehicle = create!(@builder VehicleBuilder, number_wheels: 6);
+  assert_eq!(vehicle.number_wheels, 6);
+}
+
+#[test]
+fn explicit_builder_and_mixins() {
+  let vehicle = create!(@builder VehicleBuilder, @mixins VehicleMixins, :bike, :electric);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(
+    @builder reexports::VehicleBuilder,
+    @mixins reexports::VehicleMixins,
+    :bike,
+    number_wheels: default + 1
+  );
+  assert_eq!(vehicle.number_wheels, 3);
+}
+
+#[test]
+fn explicit_builder_struct() {
+  let order = create!(@builder OrderBuilder, @mixins OrderMixins, :imported, id: 5);
+  assert_eq!(order.0, 1005);
+
+  let order = create!(@builder OrderBuilder, base_id: 0);
+  assert_eq!(order.0, 1);
+}
@@ -0,0 +1,85 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  name: String,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    name = "car".to_string(),
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+fn compute() -> String {
+  "truck".to_string()
+}
+
+#[test]
+fn block() {
+  let vehicle = create!(Vehicle, name: {
+    let name = compute();
+    name.to_uppercase()
+  });
+  assert_eq!(vehicle.name, "TRUCK");
+}
+
+#[test]
+fn block_followed_by_fields() {
+  let vehicle = create!(Vehicle, :bike, name: { compute() }, number_wheels: { 6 });
+  assert_eq!(vehicle.name, "truck");
+  assert_eq!(vehicle.number_wheels, 6);
+}
+
+#[test]
+fn block_with_method_call() {
+  let vehicle = create!(Vehicle, name: { compute() }.to_uppercase());
+  assert_eq!(vehicle.name, "TRUCK");
+}
+
+#[test]
+fn if_else() {
+  let big = true;
+  let vehicle = create!(Vehicle, number_wheels: if big { 18 } else { 4 }, name: compute());
+  assert_eq!(vehicle.number_wheels, 18);
+  assert_eq!(vehicle.name, "truck");
+}
+
+#[test]
+fn match_arms() {
+  let kind = "bike";
+  let vehicle = create!(Vehicle,
+    number_wheels: match kind {
+      "bike" => 2,
+      "trike" => 3,
+      _ => 4,
+    },
+    name: kind.to_string(),
+  );
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(vehicle.name, "bike");
+}
+
+#[test]
+fn refers_to_the_default() {
+  let vehicle = create!(Vehicle, number_wheels: {
+    let wheels = default;
+    if wheels > 2 { wheels * 2 } else { wheels }
+  });
+  assert_eq!(vehicle.number_wheels, 8);
+}
+
+#[test]
+fn in_create_vec() {
+  let vehicles = create_vec!(Vehicle, 2, name: match 1 { 1 => compute(), _ => String::new() }, |vehicles| {
+    vehicles[0].number_wheels = 1;
+  });
+  assert_eq!(vehicles[0].number_wheels, 1);
+  assert_eq!(vehicles[1].name, "truck");
+}
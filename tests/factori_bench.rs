@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+pub struct Vehicle {
+  pub id: u32,
+  pub name: String,
+  pub number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    id = NEXT_ID.fetch_add(1, Ordering::SeqCst),
+    name: String = "car".to_string(),
+    number_wheels = 4,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[test]
+fn bench_closure_creates_a_new_instance_each_call() {
+  let create_bike = factori_bench!(Vehicle, :bike);
+
+  let first = create_bike();
+  let second = create_bike();
+  assert_eq!(first.number_wheels, 2);
+  assert_eq!(second.number_wheels, 2);
+  assert_ne!(first.id, second.id);
+}
+
+#[test]
+fn bench_closure_captures_values() {
+  let name = "van".to_string();
+  let create_van = factori_bench!(Vehicle, name: name.clone());
+
+  assert_eq!(create_van().name, "van");
+  assert_eq!(create_van().name, "van");
+}
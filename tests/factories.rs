@@ -0,0 +1,89 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct User {
+  name: String,
+  role: &'static str,
+  archived: bool,
+}
+
+factori!(User, {
+  default {
+    name = "Alice".to_string(),
+    role = "member",
+    archived = false,
+  }
+});
+
+factories!(User, {
+  mixin archived {
+    archived = true,
+  }
+
+  admin {
+    default {
+      name: String = "Root".to_string(),
+      role: &'static str = "admin",
+      archived: bool = false,
+    }
+  }
+
+  guest {
+    default {
+      name: String = "Anonymous".to_string(),
+      role: &'static str = "guest",
+      archived: bool = false,
+    }
+
+    transient {
+      upcased: bool = false,
+    }
+
+    builder {
+      let name = if upcased { name.to_uppercase() } else { name };
+      User { name, role, archived }
+    }
+
+    mixin named {
+      name = "Bob".to_string(),
+    }
+  }
+});
+
+#[test]
+fn named_factories() {
+  let admin = create!(admin);
+  assert_eq!(admin.name, "Root");
+  assert_eq!(admin.role, "admin");
+
+  let guest = create!(guest, upcased: true);
+  assert_eq!(guest.name, "ANONYMOUS");
+  assert_eq!(guest.role, "guest");
+  assert!(!guest.archived);
+}
+
+#[test]
+fn type_factory_alongside_named_factories() {
+  let user = create!(User);
+  assert_eq!(user.role, "member");
+
+  let user: User = create_default!();
+  assert_eq!(user.name, "Alice");
+}
+
+#[test]
+fn shared_mixins() {
+  let admin = create!(admin, :archived);
+  assert!(admin.archived);
+
+  let guest = create!(guest, :named, :archived);
+  assert_eq!(guest.name, "Bob");
+  assert!(guest.archived);
+}
+
+#[test]
+fn create_vec_of_named_factory() {
+  let guests = create_vec!(guest, 3, :named);
+  assert_eq!(guests.len(), 3);
+  assert!(guests.iter().all(|guest| guest.name == "Bob"));
+}
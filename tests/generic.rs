@@ -0,0 +1,88 @@
+#[macro_use]
+extern crate factori_imp;
+
+#[derive(Debug, PartialEq)]
+pub struct Wrapper<T> {
+  inner: T,
+  label: &'static str,
+}
+
+factori!(Wrapper<T: Default>, {
+  default {
+    inner = Default::default(),
+    label = "wrapper",
+  }
+
+  mixin labelled {
+    label = "labelled",
+  }
+});
+
+pub struct Pair<T> {
+  first: T,
+  second: T,
+  count: u8,
+}
+
+factori!(Pair<T: Default + Clone>, {
+  default {
+    first: T = Default::default(),
+    second: T = Default::default(),
+    count: u8 = 2,
+  }
+
+  transient {
+    same: Option<T> = None,
+  }
+
+  builder {
+    match same {
+      Some(value) => Pair { first: value.clone(), second: value, count },
+      None => Pair { first, second, count },
+    }
+  }
+
+  mixin single {
+    count = 1,
+  }
+});
+
+#[test]
+fn inferred_from_the_context() {
+  let wrapper: Wrapper<i32> = create!(Wrapper);
+  assert_eq!(wrapper, Wrapper { inner: 0, label: "wrapper" });
+
+  let wrapper: Wrapper<String> = create!(Wrapper, :labelled);
+  assert_eq!(wrapper.inner, "");
+  assert_eq!(wrapper.label, "labelled");
+}
+
+#[test]
+fn inferred_from_the_overrides() {
+  let wrapper = create!(Wrapper, inner: "given".to_string());
+  assert_eq!(wrapper.inner, "given");
+
+  let wrapper: Wrapper<i64> = create!(Wrapper, inner: default + 1);
+  assert_eq!(wrapper.inner, 1);
+}
+
+#[test]
+fn with_builder() {
+  let pair: Pair<String> = create!(Pair, :single, same: Some("both".to_string()));
+  assert_eq!(pair.first, "both");
+  assert_eq!(pair.second, "both");
+  assert_eq!(pair.count, 1);
+
+  let pair = create!(Pair, first: 1u8);
+  assert_eq!(pair.second, 0);
+  assert_eq!(pair.count, 2);
+}
+
+#[test]
+fn create_default_and_create_vec() {
+  let wrapper: Wrapper<u8> = create_default!();
+  assert_eq!(wrapper.inner, 0);
+
+  let pairs: Vec<Pair<i32>> = create_vec!(Pair, 2);
+  assert_eq!(pairs.len(), 2);
+}
@@ -0,0 +1,127 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static CREATED_LEAVES: Cell<usize> = const { Cell::new(0) };
+}
+
+fn created_leaves() -> usize {
+  CREATED_LEAVES.with(Cell::get)
+}
+
+pub struct Leaf {
+  color: &'static str,
+}
+
+factori!(Leaf, {
+  default {
+    color: &'static str = { CREATED_LEAVES.with(|count| count.set(count.get() + 1)); "green" },
+  }
+
+  mixin autumn {
+    color = "red",
+  }
+});
+
+pub struct Branch {
+  leaves: Vec<Leaf>,
+}
+
+factori!(Branch, {
+  default {
+    leaves: Vec<Leaf> = has_many!(Leaf, number_leaves),
+  }
+
+  transient {
+    number_leaves: usize = 3,
+  }
+
+  builder {
+    Branch { leaves }
+  }
+});
+
+pub struct Tree {
+  branches: Vec<Branch>,
+}
+
+factori!(Tree, {
+  default {
+    branches: Vec<Branch> = has_many!(Branch, 2, number_leaves: leaves_per_branch),
+  }
+
+  transient {
+    leaves_per_branch: usize = 3,
+  }
+
+  builder {
+    Tree { branches }
+  }
+});
+
+pub struct Garden {
+  trees: Vec<Tree>,
+}
+
+factori!(Garden, {
+  default {
+    trees = has_many!(Tree, 2),
+  }
+});
+
+#[test]
+fn creates_the_default_count() {
+  let branch = create!(Branch);
+
+  assert_eq!(branch.leaves.len(), 3);
+}
+
+#[test]
+fn count_can_refer_to_transient_fields() {
+  let branch = create!(Branch, number_leaves: 5);
+
+  assert_eq!(branch.leaves.len(), 5);
+}
+
+#[test]
+fn creates_nested_children() {
+  let tree = create!(Tree, leaves_per_branch: 4);
+
+  assert_eq!(tree.branches.len(), 2);
+  tree.branches.iter().for_each(|branch| {
+    assert_eq!(branch.leaves.len(), 4);
+  });
+}
+
+#[test]
+fn works_without_builder() {
+  let garden = create!(Garden);
+
+  assert_eq!(garden.trees.len(), 2);
+  let leaves: usize = garden
+    .trees
+    .iter()
+    .flat_map(|tree| &tree.branches)
+    .map(|branch| branch.leaves.len())
+    .sum();
+  assert_eq!(leaves, 2 * 2 * 3);
+}
+
+#[test]
+fn can_be_overridden_with_another_count() {
+  let branch = create!(Branch, leaves: has_many!(Leaf, 5, :autumn));
+
+  assert_eq!(branch.leaves.len(), 5);
+  assert!(branch.leaves.iter().all(|leaf| leaf.color == "red"));
+}
+
+#[test]
+fn is_not_created_when_overridden() {
+  let before = created_leaves();
+  let branch = create!(Branch, leaves: vec![]);
+
+  assert!(branch.leaves.is_empty());
+  assert_eq!(created_leaves(), before);
+}
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  color: &'static str,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+    color = "white",
+  }
+
+  mixin bike {
+    number_wheels = 2,
+    color = "black",
+  }
+
+  mixin electric {
+    electric = true,
+    color = "green",
+  }
+});
+
+#[test]
+fn override_before_mixin() {
+  let vehicle = create!(Vehicle, number_wheels: 3, :bike);
+  assert_eq!(vehicle.number_wheels, 3);
+  assert_eq!(vehicle.color, "black");
+}
+
+#[test]
+fn mixins_between_overrides() {
+  let vehicle = create!(Vehicle, :bike, color: "red", :electric, number_wheels: 1);
+  assert_eq!(vehicle.number_wheels, 1);
+  assert!(vehicle.electric);
+  assert_eq!(vehicle.color, "red");
+}
+
+#[test]
+fn mixins_keep_their_order() {
+  assert_eq!(
+    create!(Vehicle, :electric, electric: false, :bike).color,
+    "black"
+  );
+  assert_eq!(
+    create!(Vehicle, :bike, electric: false, :electric).color,
+    "green"
+  );
+}
+
+#[test]
+fn base_after_overrides() {
+  let vehicle = create!(Vehicle, color: "blue", ..no_default, :bike, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(vehicle.color, "blue");
+}
@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static SETUPS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn expensive_setup() -> String {
+  SETUPS.with(|setups| setups.set(setups.get() + 1));
+  "expensive".to_string()
+}
+
+fn setups() -> u32 {
+  SETUPS.with(Cell::get)
+}
+
+pub struct Fixture {
+  name: &'static str,
+  data: String,
+}
+
+factori!(Fixture, {
+  default {
+    name: &'static str = "fixture",
+    lazy data: String = expensive_setup(),
+  }
+
+  mixin cheap {
+    data = "cheap".to_string(),
+  }
+});
+
+pub struct Counter {
+  lazy: u8,
+}
+
+factori!(Counter, {
+  default {
+    lazy = 1,
+  }
+});
+
+#[test]
+fn evaluated_when_not_set() {
+  let before = setups();
+  let fixture = create!(Fixture);
+  assert_eq!(fixture.data, "expensive");
+  assert_eq!(fixture.name, "fixture");
+  assert_eq!(setups(), before + 1);
+}
+
+#[test]
+fn not_evaluated_when_overridden() {
+  let before = setups();
+  let fixture = create!(Fixture, data: "given".to_string());
+  assert_eq!(fixture.data, "given");
+  assert_eq!(setups(), before);
+}
+
+#[test]
+fn not_evaluated_when_set_by_a_mixin() {
+  let before = setups();
+  let fixture = create!(Fixture, :cheap);
+  assert_eq!(fixture.data, "cheap");
+  assert_eq!(setups(), before);
+}
+
+#[test]
+fn field_named_lazy() {
+  assert_eq!(create!(Counter).lazy, 1);
+}
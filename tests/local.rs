@@ -0,0 +1,84 @@
+#[macro_use]
+extern crate factori_imp;
+
+#[test]
+fn struct_literal() {
+  struct Vehicle {
+    number_wheels: u8,
+    electric: bool,
+  }
+
+  factori_local!(Vehicle, {
+    default {
+      number_wheels = 4,
+      electric = false,
+    }
+
+    mixin bike {
+      number_wheels = 2,
+    }
+  });
+
+  let vehicle = create!(Vehicle, :bike, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn builder_and_transient_fields() {
+  struct User {
+    name: String,
+  }
+
+  factori_local!(User, {
+    default {
+      name: String = "Alice".to_string(),
+    }
+
+    transient {
+      upcased: bool = false,
+    }
+
+    builder {
+      let name = if upcased { name.to_uppercase() } else { name };
+      User { name }
+    }
+
+    mixin loud {
+      upcased = true,
+    }
+  });
+
+  assert_eq!(create!(User).name, "Alice");
+  assert_eq!(create!(User, :loud).name, "ALICE");
+  assert_eq!(create_vec!(User, 2, name: "Bob".to_string()).len(), 2);
+}
+
+#[test]
+fn several_factories() {
+  struct Wheel {
+    size: u8,
+  }
+
+  struct Vehicle {
+    wheels: Vec<Wheel>,
+  }
+
+  factori_local!(
+    Wheel, {
+      default {
+        size = 16,
+      }
+    }
+
+    Vehicle, {
+      default {
+        wheels = create_vec!(Wheel, 4),
+      }
+    }
+  );
+
+  let vehicle = create!(Vehicle);
+  assert_eq!(vehicle.wheels.len(), 4);
+  assert_eq!(vehicle.wheels[0].size, 16);
+}
@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct User {
+  pub name: String,
+  pub email: String,
+  pub age: u8,
+}
+
+factori!(User, {
+  default {
+    name: String = " Alice ".to_string() map |name| name.trim().to_string(),
+    email: String = "ALICE@EXAMPLE.COM".to_string() map |email| email.to_lowercase(),
+    age: u8 = 30,
+  }
+
+  mixin shouting {
+    name = " BOB ".to_string(),
+  }
+});
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4 map |n| n.max(2),
+  }
+
+  transient {
+    double: bool = false,
+  }
+
+  builder {
+    let number_wheels = if double { number_wheels * 2 } else { number_wheels };
+    Vehicle { number_wheels }
+  }
+});
+
+#[test]
+fn map_applies_to_default() {
+  let user = create!(User);
+  assert_eq!(user.name, "Alice");
+  assert_eq!(user.email, "alice@example.com");
+  assert_eq!(user.age, 30);
+}
+
+#[test]
+fn map_applies_to_override() {
+  let user = create!(User, name: "  Carol  ".to_string(), email: "Carol@Example.com".to_string());
+  assert_eq!(user.name, "Carol");
+  assert_eq!(user.email, "carol@example.com");
+}
+
+#[test]
+fn map_applies_to_mixin() {
+  let user = create!(User, :shouting);
+  assert_eq!(user.name, "BOB");
+}
+
+#[test]
+fn builder_keeps_unmapped_values() {
+  let builder = build!(User, name: " Dave ".to_string());
+  assert_eq!(builder.name, " Dave ");
+  assert_eq!(factori_imp::Builder::build(builder).name, "Dave");
+}
+
+#[test]
+fn map_applies_before_builder_block() {
+  assert_eq!(create!(Vehicle, number_wheels: 0).number_wheels, 2);
+  assert_eq!(
+    create!(Vehicle, number_wheels: 1, double: true).number_wheels,
+    4
+  );
+}
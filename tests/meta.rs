@@ -0,0 +1,51 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct User {
+  pub name: &'static str,
+}
+
+factori!(User, {
+  default {
+    name = "Alice",
+  }
+
+  meta {
+    category = "auth",
+    owner = "team-x",
+    priority = 3,
+    weight = 7u8,
+  }
+});
+
+pub struct Wrapper<T> {
+  pub inner: T,
+}
+
+factori!(Wrapper<T: Default>, {
+  default {
+    inner = Default::default(),
+  }
+
+  meta {
+    category = "generic",
+  }
+});
+
+#[test]
+fn meta_consts() {
+  assert_eq!(User::FACTORI_META_CATEGORY, "auth");
+  assert_eq!(User::FACTORI_META_OWNER, "team-x");
+
+  let priority: i64 = User::FACTORI_META_PRIORITY;
+  assert_eq!(priority, 3);
+  let weight: u8 = User::FACTORI_META_WEIGHT;
+  assert_eq!(weight, 7);
+
+  assert_eq!(create!(User).name, "Alice");
+}
+
+#[test]
+fn meta_consts_of_generic_type() {
+  assert_eq!(Wrapper::<u8>::FACTORI_META_CATEGORY, "generic");
+}
@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriMixins;
+
+#[derive(Debug, PartialEq)]
+pub enum State {
+  Pending,
+  Shipped,
+  Cancelled,
+}
+
+pub struct Order {
+  state: State,
+  items: u32,
+}
+
+factori!(Order, {
+  default {
+    state = State::Pending,
+    items = 1,
+  }
+
+  mixin_group state {
+    mixin shipped alias sent {
+      state = State::Shipped,
+    }
+
+    mixin cancelled {
+      state = State::Cancelled,
+    }
+  }
+
+  mixin bulk(items: u32) alias many {
+    items = items,
+  }
+});
+
+#[test]
+fn alias_applies_the_mixin() {
+  assert_eq!(create!(Order, :shipped).state, State::Shipped);
+  assert_eq!(create!(Order, :sent).state, State::Shipped);
+}
+
+#[test]
+fn alias_of_parameterized_mixin() {
+  assert_eq!(create!(Order, :bulk(10)).items, 10);
+  assert_eq!(create!(Order, :many(20)).items, 20);
+}
+
+#[test]
+fn alias_and_name_in_the_same_group() {
+  let order = create!(Order, :shipped, :sent, items: 3);
+  assert_eq!(order.state, State::Shipped);
+  assert_eq!(order.items, 3);
+}
+
+#[test]
+fn factori_with_mixins_accepts_aliases() {
+  assert_eq!(Order::factori_mixins(), &["shipped", "cancelled"]);
+
+  let order = Order::factori_with_mixins(&["sent"]).unwrap();
+  assert_eq!(order.state, State::Shipped);
+  assert!(Order::factori_with_mixins(&["shipped", "sent"]).is_some());
+  assert!(Order::factori_with_mixins(&["sent", "cancelled"]).is_none());
+}
@@ -0,0 +1,107 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriMixins;
+
+#[derive(Debug, PartialEq)]
+pub enum State {
+  Pending,
+  Shipped,
+  Cancelled,
+}
+
+pub struct Order {
+  state: State,
+  tracking: Option<&'static str>,
+  express: bool,
+}
+
+factori!(Order, {
+  default {
+    state = State::Pending,
+    tracking = None,
+    express = false,
+  }
+
+  mixin_group state {
+    mixin shipped {
+      state = State::Shipped,
+      tracking = Some("1Z999"),
+    }
+
+    mixin cancelled {
+      state = State::Cancelled,
+    }
+  }
+
+  mixin express {
+    express = true,
+  }
+});
+
+#[test]
+fn grouped_mixins() {
+  let order = create!(Order, :shipped);
+  assert_eq!(order.state, State::Shipped);
+  assert_eq!(order.tracking, Some("1Z999"));
+
+  let order = create!(Order, :cancelled);
+  assert_eq!(order.state, State::Cancelled);
+}
+
+#[test]
+fn with_other_mixins() {
+  let order = create!(Order, :express, :shipped);
+  assert_eq!(order.state, State::Shipped);
+  assert!(order.express);
+}
+
+#[test]
+fn same_mixin_twice() {
+  let order = create!(Order, :shipped, :shipped);
+  assert_eq!(order.state, State::Shipped);
+}
+
+#[test]
+fn conditional_mixins_are_not_checked() {
+  let cancelled = false;
+  let order = create!(Order, :shipped, :cancelled if cancelled);
+  assert_eq!(order.state, State::Shipped);
+}
+
+#[test]
+fn by_name() {
+  let order = Order::factori_with_mixins(&["express", "cancelled"]).unwrap();
+  assert_eq!(order.state, State::Cancelled);
+
+  assert!(Order::factori_with_mixins(&["shipped", "cancelled"]).is_none());
+  assert!(Order::factori_with_mixins(&["shipped", "shipped"]).is_some());
+}
+
+pub struct Account {
+  role: &'static str,
+}
+
+factories!(Account, {
+  mixin_group role {
+    mixin admin {
+      role = "admin",
+    }
+
+    mixin guest {
+      role = "guest",
+    }
+  }
+
+  active {
+    default {
+      role: &'static str = "user",
+    }
+  }
+});
+
+#[test]
+fn shared_groups() {
+  let account = create!(active, :admin);
+  assert_eq!(account.role, "admin");
+}
@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriMixins;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  mixin trike {
+    number_wheels = 3,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+});
+
+pub struct Passenger {
+  name: &'static str,
+}
+
+factori!(Passenger, {
+  default {
+    name = "Michael",
+  }
+});
+
+#[test]
+fn lists_mixins_in_order() {
+  assert_eq!(Vehicle::factori_mixins(), &["bike", "trike", "electric"]);
+}
+
+#[test]
+fn no_mixins() {
+  assert!(Passenger::factori_mixins().is_empty());
+  assert!(Passenger::factori_with_mixins(&["bike"]).is_none());
+  assert_eq!(Passenger::factori_with_mixins(&[]).unwrap().name, "Michael");
+}
+
+#[test]
+fn every_mixin_is_constructible() {
+  for name in Vehicle::factori_mixins() {
+    assert!(Vehicle::factori_with_mixins(&[name]).is_some());
+  }
+}
+
+#[test]
+fn applies_mixins_in_order() {
+  let vehicle = Vehicle::factori_with_mixins(&["bike", "electric", "trike"]).unwrap();
+  assert_eq!(vehicle.number_wheels, 3);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn unknown_mixin() {
+  assert!(Vehicle::factori_with_mixins(&["boat"]).is_none());
+}
+
+#[test]
+fn names_round_trip() {
+  for name in _Factori_Mixins_Vehicle::NAMES {
+    let mixin = _Factori_Mixins_Vehicle::from_name(name).unwrap();
+    assert_eq!(mixin.name(), *name);
+  }
+}
+
+#[test]
+fn debug_prints_the_name() {
+  let mixins: Vec<_> = _Factori_Mixins_Vehicle::NAMES
+    .iter()
+    .filter_map(|name| _Factori_Mixins_Vehicle::from_name(name))
+    .collect();
+
+  assert_eq!(format!("{:?}", mixins), "[bike, trike, electric]");
+}
@@ -0,0 +1,52 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::Mixin;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(#[factori(mixins_derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord))] Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  mixin wheels(number_wheels: u8) {
+    number_wheels = number_wheels,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+});
+
+type Mixins = _Factori_Mixins_Vehicle;
+
+#[test]
+fn derives_on_the_mixins_enum() {
+  assert_eq!(Mixins::bike, Mixins::bike);
+  assert_ne!(Mixins::wheels(3), Mixins::wheels(6));
+  assert!(Mixins::bike < Mixins::electric);
+
+  let mut mixins = vec![Mixins::electric, Mixins::wheels(3), Mixins::bike];
+  mixins.sort();
+  assert_eq!(mixins, [Mixins::bike, Mixins::wheels(3), Mixins::electric]);
+}
+
+#[test]
+fn copied_mixins_still_apply() {
+  let mixin = Mixins::wheels(3);
+  let vehicle = mixin.default();
+  assert_eq!(vehicle.number_wheels, 3);
+  assert_eq!(mixin, Mixins::wheels(3));
+
+  let vehicle = Mixins::electric.extend(mixin.default());
+  assert!(vehicle.electric);
+}
@@ -0,0 +1,82 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+const DEFAULT_WHEELS: u8 = 4;
+
+factori!(in vehicle_factory; Vehicle, {
+  default {
+    number_wheels = DEFAULT_WHEELS,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+mod factories {
+  pub struct Passenger {
+    pub name: String,
+  }
+
+  factori!(in passenger_factory; #[factori(prefix = "_Custom")] Passenger, {
+    default {
+      name: String = "Michael".to_string(),
+    }
+
+    builder {
+      Passenger { name }
+    }
+  });
+}
+
+#[test]
+fn create_through_the_module() {
+  let vehicle = create!(vehicle_factory::Vehicle);
+  assert_eq!(vehicle.number_wheels, 4);
+  assert!(!vehicle.electric);
+
+  let vehicle = create!(vehicle_factory::Vehicle, :bike, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn nested_module_path() {
+  let passenger = create!(
+    #[factori(prefix = "_Custom")] factories::passenger_factory::Passenger,
+    name: "Ann".to_string()
+  );
+  assert_eq!(passenger.name, "Ann");
+}
+
+#[test]
+fn other_macros() {
+  let vehicles = create_vec!(vehicle_factory::Vehicle, 3, :bike);
+  assert!(vehicles.iter().all(|vehicle| vehicle.number_wheels == 2));
+
+  let vehicles = create_vec!(vehicle_factory::Vehicle, [(:bike), ()]);
+  assert_eq!(vehicles[1].number_wheels, 4);
+
+  let vehicle = build!(vehicle_factory::Vehicle, :bike);
+  assert_eq!(vehicle.number_wheels, 2);
+}
+
+#[test]
+fn import_the_module() {
+  use vehicle_factory::*;
+
+  let vehicle = create!(Vehicle, :bike);
+  assert_eq!(vehicle.number_wheels, 2);
+}
+
+#[test]
+fn absolute_path() {
+  let vehicle = create!(crate::vehicle_factory::Vehicle);
+  assert_eq!(vehicle.number_wheels, 4);
+}
@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct DbConfig {
+  host: String,
+  pool_size: u32,
+}
+
+factori!(DbConfig, {
+  default {
+    host = "localhost".to_string(),
+    pool_size = 5,
+  }
+});
+
+pub struct CacheConfig {
+  enabled: bool,
+  ttl: u32,
+}
+
+pub struct Config {
+  name: &'static str,
+  db: DbConfig,
+  cache: CacheConfig,
+}
+
+factori!(Config, {
+  default {
+    name = "app",
+    db = create!(DbConfig),
+    cache = CacheConfig { enabled: true, ttl: 60 },
+  }
+
+  mixin no_cache {
+    cache = CacheConfig { enabled: false, ttl: 0 },
+  }
+});
+
+#[test]
+fn overrides_a_nested_field() {
+  let config = create!(Config, db.pool_size: 10);
+
+  assert_eq!(config.db.pool_size, 10);
+  assert_eq!(config.db.host, "localhost");
+  assert_eq!(config.name, "app");
+}
+
+#[test]
+fn overrides_several_nested_fields() {
+  let config = create!(Config, db.pool_size: 10, name: "other", db.host: "db".to_string(), cache.ttl: 5);
+
+  assert_eq!(config.db.pool_size, 10);
+  assert_eq!(config.db.host, "db");
+  assert_eq!(config.name, "other");
+  assert!(config.cache.enabled);
+  assert_eq!(config.cache.ttl, 5);
+}
+
+#[test]
+fn updates_the_value_set_by_a_mixin() {
+  let config = create!(Config, :no_cache, cache.ttl: 30);
+
+  assert!(!config.cache.enabled);
+  assert_eq!(config.cache.ttl, 30);
+}
+
+#[test]
+fn can_refer_to_the_nested_default() {
+  let config = create!(Config, db.pool_size: default * 2, db.host: default + ":5432");
+
+  assert_eq!(config.db.pool_size, 10);
+  assert_eq!(config.db.host, "localhost:5432");
+}
+
+#[test]
+fn works_with_create_vec() {
+  let configs = create_vec!(Config, 2, cache.enabled: false);
+
+  assert!(configs.iter().all(|config| !config.cache.enabled));
+}
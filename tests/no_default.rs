@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  mixin electric_bike {
+    number_wheels = 2,
+    electric = true,
+  }
+});
+
+pub struct User {
+  name: String,
+}
+
+factori!(User, {
+  default {
+    name: String = "Alice".to_string(),
+  }
+
+  transient {
+    upcased: bool = false,
+  }
+
+  builder {
+    let name = if upcased { name.to_uppercase() } else { name };
+    User { name }
+  }
+});
+
+#[test]
+fn explicit_default() {
+  let vehicle = create!(Vehicle, :bike, ..default, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, ..default);
+  assert_eq!(vehicle.number_wheels, 4);
+}
+
+#[test]
+fn no_default_with_overrides() {
+  let vehicle = create!(Vehicle, ..no_default, number_wheels: 3, electric: true);
+  assert_eq!(vehicle.number_wheels, 3);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn no_default_with_mixins() {
+  let vehicle = create!(Vehicle, :electric_bike, ..no_default);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, :bike, ..no_default, electric: false);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(!vehicle.electric);
+}
+
+#[test]
+fn no_default_with_transient_fields() {
+  let user = create!(User, ..no_default, name: "Bob".to_string(), upcased: true);
+  assert_eq!(user.name, "BOB");
+}
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate factori_imp;
+
+#[non_exhaustive]
+pub struct Vehicle {
+  pub number_wheels: u8,
+  pub electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[non_exhaustive]
+pub struct Order {
+  pub id: u64,
+}
+
+impl Order {
+  pub fn new(id: u64) -> Self {
+    Order { id }
+  }
+}
+
+factori!(Order, {
+  default {
+    id: u64 = 1,
+  }
+
+  transient {
+    base_id: u64 = 0,
+  }
+
+  builder {
+    Order::new(base_id + id)
+  }
+});
+
+#[test]
+fn without_builder() {
+  let vehicle = create!(Vehicle, :bike, electric: true);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+
+  let vehicles = create_vec!(Vehicle, 2);
+  assert_eq!(vehicles.len(), 2);
+}
+
+#[test]
+fn with_builder_calling_a_constructor() {
+  let order = create!(Order, id: 2, base_id: 100);
+  assert_eq!(order.id, 102);
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static LOADS: AtomicU32 = AtomicU32::new(0);
+
+pub struct Config {
+  base_url: String,
+}
+
+fn load_config() -> Config {
+  LOADS.fetch_add(1, Ordering::SeqCst);
+  Config {
+    base_url: "http://localhost".to_string(),
+  }
+}
+
+pub struct Client {
+  pub base_url: String,
+  pub timeout: u32,
+}
+
+factori!(Client, {
+  default {
+    base_url: String = once!(load_config().base_url),
+    timeout: u32 = 30,
+  }
+
+  mixin remote {
+    base_url = "http://example.com".to_string(),
+  }
+});
+
+#[test]
+fn once_evaluates_the_value_once() {
+  let clients = create_vec!(Client, 3);
+  assert!(clients.iter().all(|c| c.base_url == "http://localhost"));
+
+  let remote = create!(Client, :remote);
+  assert_eq!(remote.base_url, "http://example.com");
+
+  assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,81 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  name: String,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    name = "car".to_string(),
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+pub struct User {
+  age: u8,
+}
+
+factori!(User, {
+  default {
+    age: u8 = 42,
+  }
+
+  transient {
+    bonus: u8 = 1,
+  }
+
+  builder {
+    User { age: age + bonus }
+  }
+});
+
+pub struct Session {
+  user: String,
+  token: String,
+}
+
+factori!(Session, {
+  default {
+    user: String = "admin".to_string(),
+    lazy token: String = format!("{}-token", user),
+  }
+});
+
+#[test]
+fn refers_to_the_default() {
+  let vehicle = create!(Vehicle, number_wheels: default + 2);
+  assert_eq!(vehicle.number_wheels, 6);
+  assert_eq!(vehicle.name, "car");
+}
+
+#[test]
+fn refers_to_the_value_from_mixins() {
+  let vehicle = create!(Vehicle, :bike, number_wheels: default * 3, name: default + "go");
+  assert_eq!(vehicle.number_wheels, 6);
+  assert_eq!(vehicle.name, "cargo");
+}
+
+#[test]
+fn refers_to_builder_and_transient_fields() {
+  let user = create!(User, age: default - 2, bonus: default * 10);
+  assert_eq!(user.age, 50);
+}
+
+#[test]
+fn default_trait_method_is_not_the_default() {
+  let vehicle = create!(Vehicle, name: Default::default());
+  assert_eq!(vehicle.name, "");
+}
+
+#[test]
+fn refers_to_fields_next_to_lazy_fields() {
+  let session = create!(Session, user: default + "2");
+  assert_eq!(session.user, "admin2");
+  assert_eq!(session.token, "admin2-token");
+}
@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriMixins;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  name: String,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    electric: bool = false,
+  }
+
+  transient {
+    brand: &'static str = "Generic",
+  }
+
+  builder {
+    Vehicle {
+      number_wheels,
+      electric,
+      name: format!("{} ({} wheels)", brand, number_wheels),
+    }
+  }
+
+  mixin electric {
+    electric = true,
+  }
+
+  mixin wheels(n: u8) {
+    number_wheels = n,
+  }
+
+  mixin truck(axles: u8, electric: bool) {
+    number_wheels = axles * 4,
+    electric = electric,
+  }
+
+  mixin branded(brand: &'static str) {
+    brand = brand,
+  }
+});
+
+#[test]
+fn parameterized_mixin() {
+  let vehicle = create!(Vehicle, :wheels(6));
+  assert_eq!(vehicle.number_wheels, 6);
+  assert!(!vehicle.electric);
+}
+
+#[test]
+fn several_parameters() {
+  let vehicle = create!(Vehicle, :truck(3, true));
+  assert_eq!(vehicle.number_wheels, 12);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn arguments_are_expressions() {
+  let axles = 2;
+  let vehicle = create!(Vehicle, :truck(axles + 1, axles > 1));
+  assert_eq!(vehicle.number_wheels, 12);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn precedence() {
+  let vehicle = create!(Vehicle, :wheels(2), :truck(2, false), :electric);
+  assert_eq!(vehicle.number_wheels, 8);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, :electric, :truck(2, false), :wheels(2));
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(!vehicle.electric);
+
+  let vehicle = create!(Vehicle, :wheels(2), number_wheels: 3);
+  assert_eq!(vehicle.number_wheels, 3);
+}
+
+#[test]
+fn transient_fields() {
+  let vehicle = create!(Vehicle, :branded("Acme"), :wheels(2));
+  assert_eq!(vehicle.name, "Acme (2 wheels)");
+}
+
+#[test]
+fn conditional() {
+  let vehicle = create!(Vehicle, :wheels(6) if false);
+  assert_eq!(vehicle.number_wheels, 4);
+
+  let vehicle = create!(Vehicle, :wheels(6) if true);
+  assert_eq!(vehicle.number_wheels, 6);
+}
+
+#[test]
+fn not_applied_by_name() {
+  assert_eq!(Vehicle::factori_mixins(), &["electric"]);
+  assert!(Vehicle::factori_with_mixins(&["wheels"]).is_none());
+}
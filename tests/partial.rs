@@ -0,0 +1,82 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct User {
+  pub name: String,
+  pub email: String,
+  pub age: u8,
+}
+
+factori!(User, {
+  default {
+    name: String = "Alice".to_string(),
+  }
+
+  partial {
+    email: String,
+    age: u8,
+  }
+
+  mixin adult {
+    age = 30,
+  }
+});
+
+pub struct Account {
+  pub owner: String,
+  pub balance: u32,
+}
+
+factori!(Account, {
+  default {
+    balance: u32 = 0,
+  }
+
+  partial {
+    owner: String,
+  }
+
+  transient {
+    bonus: u32 = 0,
+  }
+
+  builder {
+    Account { owner, balance: balance + bonus }
+  }
+});
+
+#[test]
+fn partial_fields_set_in_create() {
+  let user = create!(User, :adult, email: "a@example.com".to_string());
+  assert_eq!(user.name, "Alice");
+  assert_eq!(user.email, "a@example.com");
+  assert_eq!(user.age, 30);
+}
+
+#[test]
+fn partial_fields_set_with_setters() {
+  let user = build!(User, age: 20)
+    .email("b@example.com".to_string())
+    .build();
+  assert_eq!(user.email, "b@example.com");
+  assert_eq!(user.age, 20);
+}
+
+#[test]
+#[should_panic(expected = "the partial field `email` isn't set")]
+fn panics_when_unset() {
+  let _ = create!(User, :adult);
+}
+
+#[test]
+fn partial_fields_with_builder_block() {
+  let account = create!(Account, owner: "Carol".to_string(), bonus: 5);
+  assert_eq!(account.owner, "Carol");
+  assert_eq!(account.balance, 5);
+}
+
+#[test]
+#[should_panic(expected = "the partial field `owner` isn't set")]
+fn panics_when_unset_with_builder_block() {
+  let _ = create!(Account);
+}
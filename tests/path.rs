@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::time::Duration;
+
+mod separate_module {
+  pub struct Vehicle {
+    pub number_wheels: u8,
+    pub electric: bool,
+  }
+}
+
+// The type doesn't need to be imported, its path is enough.
+factori!(separate_module::Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+// Types from other crates need a builder {} block.
+factori!(std::time::Duration, {
+  default {
+    secs: u64 = 1,
+  }
+
+  builder {
+    std::time::Duration::from_secs(secs)
+  }
+
+  mixin minute {
+    secs = 60,
+  }
+});
+
+#[test]
+fn type_from_module_path() {
+  let bike = create!(Vehicle, :bike, electric: true);
+  assert_eq!(bike.number_wheels, 2);
+  assert!(bike.electric);
+}
+
+#[test]
+fn type_from_other_crate() {
+  assert_eq!(create!(Duration), Duration::from_secs(1));
+  assert_eq!(create!(Duration, :minute), Duration::from_secs(60));
+  assert_eq!(create!(Duration, secs: 5), Duration::from_secs(5));
+}
+
+#[test]
+fn create_vec_with_path() {
+  let durations = create_vec!(Duration, 2, secs: 3);
+  assert_eq!(durations, vec![Duration::from_secs(3); 2]);
+}
@@ -0,0 +1,57 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+mod factories {
+  use super::Vehicle;
+
+  factori!(#[factori(prefix = "__MyFac")] Vehicle, {
+    default {
+      number_wheels = 4,
+      electric = false,
+    }
+
+    mixin bike {
+      number_wheels = 2,
+    }
+  });
+}
+
+use factories::*;
+
+// Would clash with the types generated with the default prefix.
+#[allow(non_camel_case_types, dead_code)]
+struct _Factori_Builder_Vehicle;
+
+#[test]
+fn creates_with_the_prefix() {
+  let vehicle = create!(#[factori(prefix = "__MyFac")] Vehicle, :bike, electric: true);
+
+  assert_eq!(vehicle.number_wheels, 2);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn names_the_generated_types_with_the_prefix() {
+  let vehicle = __MyFac_Builder_Vehicle { number_wheels: 3, electric: false };
+
+  assert_eq!(vehicle.number_wheels, 3);
+}
+
+#[test]
+fn works_with_create_vec() {
+  let vehicles = create_vec!(#[factori(prefix = "__MyFac")] Vehicle, 2);
+
+  assert_eq!(vehicles.len(), 2);
+}
+
+#[test]
+fn works_with_create_default() {
+  let vehicle: Vehicle = create_default!();
+
+  assert_eq!(vehicle.number_wheels, 4);
+}
@@ -0,0 +1,102 @@
+#[macro_use]
+extern crate factori_imp;
+
+#[derive(Clone)]
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  name: &'static str,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+    name = "car",
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+});
+
+pub struct User {
+  name: String,
+}
+
+factori!(#[factori(clone_builder)] User, {
+  default {
+    name: String = "Alice".to_string(),
+  }
+
+  transient {
+    title: &'static str,
+  }
+
+  builder {
+    User { name: format!("{} {}", title, name) }
+  }
+
+  mixin bob {
+    name = "Bob".to_string(),
+  }
+});
+
+#[test]
+fn replaces_the_defaults() {
+  let preset = build!(Vehicle, name: "preset", electric: true);
+  let vehicle = create!(Vehicle, ..preset);
+
+  assert_eq!(vehicle.name, "preset");
+  assert_eq!(vehicle.number_wheels, 4);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn precedence() {
+  let preset = build!(Vehicle, :electric, name: "preset", number_wheels: 6);
+
+  let vehicle = create!(Vehicle, :bike, ..preset.clone());
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(vehicle.name, "preset");
+
+  let vehicle = create!(Vehicle, :bike, ..preset.clone(), number_wheels: 3);
+  assert_eq!(vehicle.number_wheels, 3);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, :bike if false, ..preset);
+  assert_eq!(vehicle.number_wheels, 6);
+}
+
+#[test]
+fn reused_with_clone_builder() {
+  let preset = build!(User, title: "Dr.");
+
+  let alice = create!(User, ..preset.clone());
+  let bob = create!(User, :bob, ..preset.clone());
+  let carol = create!(User, ..preset, name: "Carol".to_string());
+
+  assert_eq!(alice.name, "Dr. Alice");
+  assert_eq!(bob.name, "Dr. Bob");
+  assert_eq!(carol.name, "Dr. Carol");
+}
+
+#[test]
+fn refers_to_the_preset_as_default() {
+  let preset = build!(Vehicle, number_wheels: 6);
+  let vehicle = create!(Vehicle, ..preset, number_wheels: default * 3);
+
+  assert_eq!(vehicle.number_wheels, 18);
+}
+
+#[test]
+fn in_create_vec() {
+  let preset = build!(User, title: "Mr.");
+  let users = create_vec!(User, 2, ..preset.clone(), name: "Dan".to_string());
+
+  assert!(users.iter().all(|user| user.name == "Mr. Dan"));
+}
@@ -0,0 +1,83 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  color: &'static str,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+    color = "white",
+  }
+
+  mixin bike {
+    number_wheels = 2,
+    color = "black",
+  }
+
+  mixin electric {
+    electric = true,
+    color = "green",
+  }
+
+  mixin wheels(number_wheels: u8) {
+    number_wheels = number_wheels,
+  }
+});
+
+fn mixins_of(vehicle: &Vehicle) -> (bool, bool) {
+  (vehicle.number_wheels == 2, vehicle.electric)
+}
+
+#[test]
+fn same_mixins_for_the_same_seed() {
+  for seed in 0..16 {
+    let vehicle = create_with_seed!(Vehicle, seed, random_mixins: [bike, electric]);
+    let same_vehicle = create_with_seed!(Vehicle, seed, random_mixins: [bike, electric]);
+    assert_eq!(mixins_of(&vehicle), mixins_of(&same_vehicle));
+  }
+}
+
+#[test]
+fn applies_every_subset() {
+  let mut subsets: Vec<_> = (0..64)
+    .map(|seed| mixins_of(&create_with_seed!(Vehicle, seed, random_mixins: [bike, electric])))
+    .collect();
+  subsets.sort_unstable();
+  subsets.dedup();
+  assert_eq!(
+    subsets,
+    [(false, false), (false, true), (true, false), (true, true)]
+  );
+}
+
+#[test]
+fn applied_in_the_listed_order() {
+  for seed in 0..16 {
+    let vehicle = create_with_seed!(Vehicle, seed, random_mixins: [bike, electric]);
+    let expected = match mixins_of(&vehicle) {
+      (_, true) => "green",
+      (true, false) => "black",
+      (false, false) => "white",
+    };
+    assert_eq!(vehicle.color, expected);
+  }
+}
+
+#[test]
+fn with_other_mixins_and_overrides() {
+  for seed in 0..16 {
+    let vehicle =
+      create_with_seed!(Vehicle, seed, :electric, random_mixins: [wheels(3)], color: "red");
+    assert!(vehicle.electric);
+    assert!(vehicle.number_wheels == 3 || vehicle.number_wheels == 4);
+    assert_eq!(vehicle.color, "red");
+  }
+
+  let vehicle = create!(Vehicle, random_mixins: [bike], number_wheels: 6);
+  assert_eq!(vehicle.number_wheels, 6);
+}
@@ -0,0 +1,86 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriMixins;
+
+pub struct Token {
+  r#type: u8,
+  r#ref: &'static str,
+}
+
+factori!(Token, {
+  default {
+    r#type = 1,
+    r#ref = "main",
+  }
+
+  mixin r#move {
+    r#type = 2,
+  }
+
+  mixin stock {
+    reset r#type,
+  }
+});
+
+pub struct Item {
+  r#type: String,
+}
+
+factori!(Item, {
+  default {
+    r#type: String = "item".to_string(),
+  }
+
+  transient {
+    r#async: bool = false,
+  }
+
+  builder {
+    let r#type = if r#async { format!("async {}", r#type) } else { r#type };
+    Item { r#type }
+  }
+
+  mixin r#async {
+    r#async = true,
+  }
+});
+
+#[test]
+fn defaults() {
+  let token = create!(Token);
+  assert_eq!(token.r#type, 1);
+  assert_eq!(token.r#ref, "main");
+}
+
+#[test]
+fn overrides_and_mixins() {
+  let token = create!(Token, :r#move, r#ref: "branch");
+  assert_eq!(token.r#type, 2);
+  assert_eq!(token.r#ref, "branch");
+
+  let token = create!(Token, :r#move, :stock);
+  assert_eq!(token.r#type, 1);
+
+  let token = create!(Token, r#type: default + 2);
+  assert_eq!(token.r#type, 3);
+
+  let token = create!(Token, ..no_default, r#type: 4, r#ref: "tag");
+  assert_eq!(token.r#type, 4);
+}
+
+#[test]
+fn builder_and_transient_fields() {
+  let item = create!(Item, r#type: "task".to_string(), r#async: true);
+  assert_eq!(item.r#type, "async task");
+
+  let item = create!(Item, :r#async);
+  assert_eq!(item.r#type, "async item");
+}
+
+#[test]
+fn mixin_names_are_unraw() {
+  assert_eq!(Token::factori_mixins(), &["move", "stock"]);
+  assert_eq!(Token::factori_with_mixins(&["move"]).unwrap().r#type, 2);
+  assert_eq!(format!("{:?}", _Factori_Mixins_Token::r#move), "move");
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "registry")]
+
+#[macro_use]
+extern crate factori_imp;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vehicle {
+  number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+});
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Garage {
+  name: &'static str,
+}
+
+factori!(Garage, {
+  default {
+    name = "garage",
+  }
+});
+
+fn drain<T: 'static>() -> Vec<T> {
+  factori_drain!()
+    .into_iter()
+    .filter_map(|created| created.downcast::<T>().ok())
+    .map(|created| *created)
+    .collect()
+}
+
+#[test]
+fn registers_created_objects() {
+  let vehicle = create!(Vehicle, :bike);
+
+  assert_eq!(drain::<Vehicle>(), [vehicle]);
+}
+
+#[test]
+fn drain_clears_the_registry() {
+  create!(Vehicle);
+  let _ = factori_drain!();
+
+  assert!(factori_drain!().is_empty());
+}
+
+#[test]
+fn keeps_the_order_of_creation_across_types() {
+  create!(Vehicle);
+  create!(Garage);
+  create!(Vehicle, :bike);
+
+  let created = factori_drain!();
+  assert_eq!(created.len(), 3);
+  assert!(created[0].is::<Vehicle>());
+  assert!(created[1].is::<Garage>());
+  assert!(created[2].is::<Vehicle>());
+}
+
+#[test]
+fn registers_every_element_of_create_vec() {
+  let _ = create_vec!(Vehicle, 3);
+
+  assert_eq!(drain::<Vehicle>().len(), 3);
+}
+
+#[test]
+fn registers_nothing_without_create() {
+  let _ = build!(Vehicle);
+
+  assert!(factori_drain!().is_empty());
+}
+
+pub struct Engine {
+  power: u32,
+}
+
+factori!(Engine, {
+  default {
+    power = 100,
+  }
+});
+
+#[test]
+fn skips_objects_which_arent_clone() {
+  let engine = create!(Engine);
+  create!(Vehicle);
+
+  assert_eq!(engine.power, 100);
+  assert_eq!(drain::<Vehicle>().len(), 1);
+}
@@ -0,0 +1,55 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Token {
+  r#type: u8,
+  value: String,
+}
+
+factori!(Token, {
+  default {
+    kind as r#type: u8 = 1,
+    value: String = "fn".to_string(),
+  }
+
+  mixin literal {
+    kind = 2,
+    value = "42".to_string(),
+  }
+});
+
+pub struct Vehicle {
+  wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels as wheels: u8 = 4,
+  }
+});
+
+#[test]
+fn default_uses_struct_field() {
+  let token = create!(Token);
+  assert_eq!(token.r#type, 1);
+  assert_eq!(token.value, "fn");
+}
+
+#[test]
+fn override_uses_factory_name() {
+  let token = create!(Token, kind: 3);
+  assert_eq!(token.r#type, 3);
+}
+
+#[test]
+fn mixin_uses_factory_name() {
+  let token = create!(Token, :literal);
+  assert_eq!(token.r#type, 2);
+  assert_eq!(token.value, "42");
+}
+
+#[test]
+fn plain_field_rename() {
+  let vehicle = create!(Vehicle, number_wheels: 2);
+  assert_eq!(vehicle.wheels, 2);
+}
@@ -0,0 +1,92 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static SEAT_NUMBER: Cell<u8> = const { Cell::new(0) };
+}
+
+fn next_seat_number() -> u8 {
+  SEAT_NUMBER.with(|number| {
+    number.set(number.get() + 1);
+    number.get()
+  })
+}
+
+pub struct Seat {
+  number: u8,
+  heated: bool,
+}
+
+factori!(Seat, {
+  default {
+    number = next_seat_number(),
+    heated = false,
+  }
+});
+
+pub struct Vehicle {
+  seats: Vec<Seat>,
+}
+
+factori!(Vehicle, {
+  default {
+    seats: Vec<Seat> = repeat!(association!(Seat, heated: heated_seats), number_seats),
+  }
+
+  transient {
+    number_seats: usize = 4,
+    heated_seats: bool = false,
+  }
+
+  builder {
+    Vehicle { seats }
+  }
+
+  mixin luxury {
+    heated_seats = true,
+  }
+});
+
+pub struct Bus {
+  seats: Vec<Seat>,
+}
+
+factori!(Bus, {
+  default {
+    seats = repeat!(association!(Seat), 3),
+  }
+});
+
+#[test]
+fn repeats_association() {
+  let vehicle = create!(Vehicle);
+  assert_eq!(vehicle.seats.len(), 4);
+  assert!(vehicle.seats.iter().all(|seat| !seat.heated));
+}
+
+#[test]
+fn evaluates_for_each_element() {
+  let bus = create!(Bus);
+  assert_ne!(bus.seats[0].number, bus.seats[1].number);
+  assert_ne!(bus.seats[1].number, bus.seats[2].number);
+}
+
+#[test]
+fn count_from_transient() {
+  let vehicle = create!(Vehicle, number_seats: 7);
+  assert_eq!(vehicle.seats.len(), 7);
+}
+
+#[test]
+fn value_from_transient() {
+  let vehicle = create!(Vehicle, :luxury);
+  assert!(vehicle.seats.iter().all(|seat| seat.heated));
+}
+
+#[test]
+fn overridden() {
+  let vehicle = create!(Vehicle, seats: vec![]);
+  assert!(vehicle.seats.is_empty());
+}
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Document {
+  tenant_id: u64,
+  title: String,
+}
+
+factori!(Document, {
+  default {
+    title: String = "Untitled".to_string(),
+  }
+
+  transient {
+    tenant: u64,
+  }
+
+  builder {
+    Document { tenant_id: tenant, title }
+  }
+
+  mixin acme {
+    tenant = 42,
+  }
+});
+
+#[test]
+fn can_be_set_in_create() {
+  let document = create!(Document, tenant: 7);
+
+  assert_eq!(document.tenant_id, 7);
+  assert_eq!(document.title, "Untitled");
+}
+
+#[test]
+fn can_be_set_by_a_mixin() {
+  let document = create!(Document, :acme);
+
+  assert_eq!(document.tenant_id, 42);
+}
+
+#[test]
+fn overrides_take_precedence_over_mixins() {
+  let document = create!(Document, :acme, tenant: 1);
+
+  assert_eq!(document.tenant_id, 1);
+}
+
+#[test]
+fn works_with_create_vec() {
+  let documents = create_vec!(Document, 2, tenant: 3);
+
+  assert!(documents.iter().all(|document| document.tenant_id == 3));
+}
+
+#[test]
+#[should_panic(expected = "the transient field `tenant` is required")]
+fn panics_when_not_set_at_runtime() {
+  let _document: Document = create_default!();
+}
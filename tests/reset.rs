@@ -0,0 +1,101 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::Cell;
+
+thread_local! {
+  static NEXT_ID: Cell<u32> = const { Cell::new(1) };
+}
+
+fn next_id() -> u32 {
+  NEXT_ID.with(|id| id.replace(id.get() + 1))
+}
+
+pub struct Vehicle {
+  id: u32,
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    id = next_id(),
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin bike {
+    number_wheels = 2,
+    electric = true,
+  }
+
+  mixin stock {
+    reset number_wheels,
+  }
+
+  mixin renumbered {
+    reset id,
+  }
+});
+
+pub struct User {
+  name: String,
+}
+
+factori!(User, {
+  default {
+    name: String = "Alice".to_string(),
+    lazy nickname: String = name.clone(),
+  }
+
+  transient {
+    upcased: bool = false,
+  }
+
+  builder {
+    let name = if upcased { name.to_uppercase() } else { name };
+    User { name: format!("{} ({})", name, nickname) }
+  }
+
+  mixin shouting {
+    upcased = true,
+    nickname = "Al".to_string(),
+  }
+
+  mixin calm {
+    reset upcased,
+    reset nickname,
+  }
+});
+
+#[test]
+fn resets_a_field_set_by_a_mixin() {
+  let vehicle = create!(Vehicle, :bike, :stock);
+  assert_eq!(vehicle.number_wheels, 4);
+  assert!(vehicle.electric);
+}
+
+#[test]
+fn later_mixins_and_overrides_win() {
+  let vehicle = create!(Vehicle, :stock, :bike);
+  assert_eq!(vehicle.number_wheels, 2);
+
+  let vehicle = create!(Vehicle, :bike, :stock, number_wheels: 3);
+  assert_eq!(vehicle.number_wheels, 3);
+}
+
+#[test]
+fn evaluates_the_default_again() {
+  let before = create!(Vehicle);
+  let vehicle = create!(Vehicle, :renumbered);
+  assert_eq!(vehicle.id, before.id + 2);
+}
+
+#[test]
+fn resets_transient_and_lazy_fields() {
+  let user = create!(User, :shouting);
+  assert_eq!(user.name, "ALICE (Al)");
+
+  let user = create!(User, :shouting, :calm);
+  assert_eq!(user.name, "Alice (Alice)");
+}
@@ -0,0 +1,51 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+static RESETS: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Clone)]
+pub struct Config {
+  id: u32,
+}
+
+factori!(Config, {
+  default {
+    id = NEXT_ID.fetch_add(1, Ordering::SeqCst),
+  }
+});
+
+// A single test, as the counters are global.
+#[test]
+fn resets_everything() {
+  factori_imp::reset::register(|| NEXT_ID.store(1, Ordering::SeqCst));
+  factori_imp::reset::register(|| {
+    RESETS.fetch_add(1, Ordering::SeqCst);
+  });
+
+  let shared = create_shared!(Config);
+  assert!(Arc::ptr_eq(&shared, &create_shared!(Config)));
+  assert_eq!(create!(Config).id, 2);
+
+  factori_reset_all!();
+
+  #[cfg(feature = "registry")]
+  assert!(factori_drain!().is_empty());
+
+  assert_eq!(RESETS.load(Ordering::SeqCst), 1);
+  let new_shared = create_shared!(Config);
+  assert!(!Arc::ptr_eq(&shared, &new_shared));
+  assert_eq!(new_shared.id, 1);
+
+  factori_reset_all!();
+  assert_eq!(RESETS.load(Ordering::SeqCst), 2);
+
+  // The hooks and the shared instances belong to the thread.
+  let shared = create_shared!(Config);
+  std::thread::spawn(|| factori_reset_all!()).join().unwrap();
+  assert_eq!(RESETS.load(Ordering::SeqCst), 2);
+  assert!(Arc::ptr_eq(&shared, &create_shared!(Config)));
+}
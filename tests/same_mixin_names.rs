@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriMixins;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    electric = false,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+
+  mixin wheels(n: u8) {
+    number_wheels = n,
+  }
+});
+
+// The same mixin names, setting fields of other types, some with the same
+// names as Vehicle's.
+pub struct Boat {
+  electric: &'static str,
+  number_engines: u8,
+}
+
+factori!(Boat, {
+  default {
+    electric = "no",
+    number_engines = 1,
+  }
+
+  mixin electric {
+    electric = "yes",
+    number_engines = 2,
+  }
+
+  mixin wheels(engines: u8, electric: &'static str) {
+    number_engines = engines,
+    electric = electric,
+  }
+});
+
+#[test]
+fn same_mixin_on_different_factories() {
+  let vehicle = create!(Vehicle, :electric);
+  assert!(vehicle.electric);
+  assert_eq!(vehicle.number_wheels, 4);
+
+  let boat = create!(Boat, :electric);
+  assert_eq!(boat.electric, "yes");
+  assert_eq!(boat.number_engines, 2);
+}
+
+#[test]
+fn same_parameterized_mixin_on_different_factories() {
+  let vehicle = create!(Vehicle, :wheels(3));
+  assert_eq!(vehicle.number_wheels, 3);
+
+  let boat = create!(Boat, :wheels(4, "hybrid"));
+  assert_eq!(boat.number_engines, 4);
+  assert_eq!(boat.electric, "hybrid");
+}
+
+#[test]
+fn same_mixin_by_name() {
+  // Parameterized mixins aren't listed, as they can't be applied by name.
+  assert_eq!(Vehicle::factori_mixins(), &["electric"]);
+  assert_eq!(Boat::factori_mixins(), &["electric"]);
+
+  assert!(Vehicle::factori_with_mixins(&["electric"]).unwrap().electric);
+  assert_eq!(
+    Boat::factori_with_mixins(&["electric"]).unwrap().electric,
+    "yes"
+  );
+}
+
+#[test]
+fn same_mixin_in_vecs() {
+  let vehicles = create_vec!(Vehicle, 2, :electric);
+  assert!(vehicles.iter().all(|vehicle| vehicle.electric));
+
+  let boats = create_vec!(Boat, 2, :electric);
+  assert!(boats.iter().all(|boat| boat.electric == "yes"));
+}
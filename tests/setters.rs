@@ -0,0 +1,132 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::{Builder, FactoriBuilder};
+
+pub struct Vehicle {
+  number_wheels: u8,
+  name: String,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    name: String = "car".to_string(),
+  }
+
+  transient {
+    brand: &'static str = "Generic",
+    owner: &'static str,
+  }
+
+  builder {
+    Vehicle { number_wheels, name: format!("{} {} of {}", brand, name, owner) }
+  }
+
+  mixin bike {
+    number_wheels = 2,
+    name = "bike".to_string(),
+  }
+});
+
+pub struct Garage {
+  vehicles: Vec<Vehicle>,
+}
+
+factori!(Garage, {
+  default {
+    vehicles: Vec<Vehicle> = has_many!(Vehicle, 2, owner: "garage"),
+  }
+
+  builder {
+    Garage { vehicles }
+  }
+});
+
+#[derive(Debug, PartialEq)]
+pub struct Passenger {
+  name: &'static str,
+}
+
+factori!(Passenger, {
+  default {
+    name = "Michael",
+  }
+});
+
+#[test]
+fn setters() {
+  let vehicle = Vehicle::factori_builder()
+    .number_wheels(3)
+    .brand("Acme")
+    .owner("Ann")
+    .build();
+
+  assert_eq!(vehicle.number_wheels, 3);
+  assert_eq!(vehicle.name, "Acme car of Ann");
+}
+
+#[test]
+fn collect() {
+  let vehicles: Vec<Vehicle> = (1..=3)
+    .map(|i| Vehicle::factori_builder().number_wheels(i).owner("Ann"))
+    .map(Builder::build)
+    .collect();
+
+  let wheels: Vec<_> = vehicles.iter().map(|vehicle| vehicle.number_wheels).collect();
+  assert_eq!(wheels, [1, 2, 3]);
+}
+
+#[test]
+fn on_top_of_macros() {
+  let vehicle = build!(Vehicle, :bike, owner: "Ann").brand("Acme").build();
+
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(vehicle.name, "Acme bike of Ann");
+}
+
+#[test]
+fn deferred_fields() {
+  let garage = Garage::factori_builder().build();
+  assert_eq!(garage.vehicles.len(), 2);
+
+  let garage = Garage::factori_builder().vehicles(Vec::new()).build();
+  assert!(garage.vehicles.is_empty());
+}
+
+#[test]
+fn without_builder_block() {
+  let passenger = Passenger::factori_builder();
+
+  assert_eq!(passenger, Passenger { name: "Michael" });
+}
+
+mod without_import {
+  use factori_imp::FactoriBuilder;
+
+  pub struct Part {
+    build: u32,
+  }
+
+  factori!(Part, {
+    default {
+      build: u32 = 1,
+    }
+
+    builder {
+      Part { build }
+    }
+  });
+
+  #[test]
+  fn build_method() {
+    let vehicle = super::Vehicle::factori_builder().owner("Ann").build();
+    assert_eq!(vehicle.name, "Generic car of Ann");
+  }
+
+  #[test]
+  fn field_named_build() {
+    let part = factori_imp::Builder::build(Part::factori_builder().build(2));
+    assert_eq!(part.build, 2);
+  }
+}
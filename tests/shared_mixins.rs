@@ -0,0 +1,208 @@
+#[macro_use]
+extern crate factori_imp;
+
+shared_mixins! {
+  archived {
+    archived = true,
+  }
+
+  weak renamed(name: &'static str) {
+    name = name,
+  }
+}
+
+pub struct Post {
+  name: &'static str,
+  archived: bool,
+}
+
+factori!(Post, {
+  use_mixins archived, renamed;
+
+  default {
+    name: &'static str = "Hello",
+    archived: bool = false,
+  }
+
+  mixin titled {
+    name = "Title",
+  }
+});
+
+pub struct Comment {
+  name: &'static str,
+  archived: bool,
+  likes: u32,
+}
+
+factori!(
+  Comment, {
+    default {
+      name: &'static str = "Nice",
+      archived: bool = false,
+      likes: u32 = 0,
+    }
+
+    use_mixins renamed;
+    use_mixins archived;
+  }
+
+  Reply, {
+    default {
+      archived = false,
+    }
+
+    use_mixins archived;
+  }
+);
+
+pub struct Reply {
+  archived: bool,
+}
+
+pub struct User {
+  name: &'static str,
+  archived: bool,
+}
+
+factories!(User, {
+  use_mixins archived;
+
+  admin {
+    default {
+      name: &'static str = "Root",
+      archived: bool = false,
+    }
+  }
+
+  guest {
+    default {
+      name: &'static str = "Anonymous",
+      archived: bool = false,
+    }
+
+    use_mixins renamed;
+  }
+});
+
+pub struct Settings {
+  use_mixins: bool,
+  archived: bool,
+}
+
+factori!(Settings, {
+  default {
+    use_mixins: bool = true,
+    archived: bool = false,
+  }
+
+  builder {
+    let use_mixins = !use_mixins;
+    Settings {
+      use_mixins: !use_mixins,
+      archived,
+    }
+  }
+
+  mixin plain {
+    use_mixins = false,
+  }
+
+  use_mixins archived;
+});
+
+factories!(Settings, {
+  use_mixins archived;
+
+  shared_settings {
+    default {
+      use_mixins: bool = true,
+      archived: bool = false,
+    }
+
+    mixin plain {
+      use_mixins = false,
+    }
+  }
+});
+
+mod nested {
+  pub struct Tag {
+    pub archived: bool,
+  }
+
+  // Defined in a parent module, before this one.
+  factori!(Tag, {
+    default {
+      archived = false,
+    }
+
+    use_mixins archived;
+  });
+}
+
+#[test]
+fn in_several_factories() {
+  assert!(create!(Post, :archived).archived);
+  assert!(create!(Comment, :archived).archived);
+  assert!(!create!(Comment).archived);
+  assert!(create!(Reply, :archived).archived);
+}
+
+#[test]
+fn with_the_other_mixins() {
+  let post = create!(Post, :titled, :renamed("Renamed"), :archived);
+  assert_eq!(post.name, "Title");
+  assert!(post.archived);
+
+  let post = create!(Post, :renamed("Renamed"), :titled);
+  assert_eq!(post.name, "Title");
+
+  let comment = create!(Comment, :renamed("Renamed"), likes: 2);
+  assert_eq!(comment.name, "Renamed");
+  assert_eq!(comment.likes, 2);
+}
+
+#[test]
+fn in_factories_groups() {
+  assert!(create!(admin, :archived).archived);
+
+  let guest = create!(guest, :archived, :renamed("Bob"));
+  assert!(guest.archived);
+  assert_eq!(guest.name, "Bob");
+}
+
+#[test]
+fn in_nested_module() {
+  assert!(create!(nested::Tag, :archived).archived);
+}
+
+#[test]
+fn local_factories() {
+  struct Note {
+    archived: bool,
+  }
+
+  factori_local!(Note, {
+    default {
+      archived = false,
+    }
+
+    use_mixins archived;
+  });
+
+  assert!(create!(Note, :archived).archived);
+}
+
+#[test]
+fn with_a_field_named_use_mixins() {
+  assert!(create!(Settings).use_mixins);
+
+  let settings = create!(Settings, :plain, :archived);
+  assert!(!settings.use_mixins);
+  assert!(settings.archived);
+
+  let settings = create!(shared_settings, :plain, :archived);
+  assert!(!settings.use_mixins);
+  assert!(settings.archived);
+}
@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Document {
+  pub body: String,
+  length: usize,
+  checksum: u8,
+}
+
+factori!(Document, {
+  default {
+    body: String = "Hello".to_string(),
+    #[factori(skip)]
+    length: usize = 0,
+    #[factori(skip)]
+    checksum: u8 = 0,
+  }
+
+  builder {
+    let checksum = body.bytes().fold(0u8, |sum, byte| sum.wrapping_add(byte));
+    Document { length: length + body.len(), body, checksum }
+  }
+
+  mixin padded {
+    length = 2,
+  }
+});
+
+#[test]
+fn other_fields_can_be_overridden() {
+  let document = create!(Document, body: "Hi".to_string());
+
+  assert_eq!(document.length, 2);
+  assert_eq!(document.checksum, b'H'.wrapping_add(b'i'));
+}
+
+#[test]
+fn mixins_can_set_skipped_fields() {
+  let document = create!(Document, :padded);
+
+  assert_eq!(document.length, 7);
+}
+
+#[test]
+fn in_other_macros() {
+  let documents = create_vec!(Document, body = ["a".to_string(), "bc".to_string()]);
+
+  assert_eq!(documents[1].length, 2);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "trace")]
+
+#[macro_use]
+extern crate factori_imp;
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+thread_local! {
+  static REPORTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+// The sink applies to every thread, so each test thread records its own.
+fn record(factory: &str, _elapsed: Duration) {
+  REPORTS.with(|reports| reports.borrow_mut().push(factory.to_string()));
+}
+
+fn reports() -> Vec<String> {
+  factori_imp::trace::set_sink(record);
+  REPORTS.with(|reports| reports.borrow_mut().drain(..).collect())
+}
+
+pub struct Vehicle {
+  number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+  }
+});
+
+pub struct Garage {
+  vehicle: Vehicle,
+}
+
+factori!(Garage, {
+  default {
+    vehicle = create!(Vehicle),
+  }
+});
+
+#[test]
+fn reports_the_factory() {
+  reports();
+  let vehicle = create!(Vehicle, number_wheels: 2);
+
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(reports(), vec!["Vehicle"]);
+}
+
+#[test]
+fn reports_every_instance() {
+  reports();
+  let _ = create_vec!(Vehicle, 3);
+
+  assert_eq!(reports(), vec!["Vehicle"; 3]);
+}
+
+#[test]
+fn reports_nested_factories_first() {
+  reports();
+  let garage = create!(Garage);
+
+  assert_eq!(garage.vehicle.number_wheels, 4);
+  assert_eq!(reports(), vec!["Vehicle", "Garage"]);
+}
+
+#[test]
+fn shadowing() {
+  reports();
+  let __factori_span = 3;
+  let vehicle = create!(Vehicle, number_wheels: __factori_span);
+
+  assert_eq!(vehicle.number_wheels, 3);
+}
@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::panic;
+use std::sync::Mutex;
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+  }
+
+  transient {
+    broken: bool = false,
+  }
+
+  builder {
+    if broken {
+      panic!("broken vehicle");
+    }
+    let number_wheels: u8 = Some(number_wheels).filter(|n| *n > 0).unwrap();
+    Vehicle { number_wheels }
+  }
+});
+
+static LOCATION: Mutex<Option<(String, u32)>> = Mutex::new(None);
+
+fn panic_location(f: impl FnOnce() + panic::UnwindSafe) -> (String, u32) {
+  panic::set_hook(Box::new(|info| {
+    let location = info.location().unwrap();
+    *LOCATION.lock().unwrap() = Some((location.file().to_string(), location.line()));
+  }));
+  let result = panic::catch_unwind(f);
+  let _ = panic::take_hook();
+  assert!(result.is_err());
+
+  LOCATION.lock().unwrap().take().unwrap()
+}
+
+// A single test, as the panic hook is global.
+#[test]
+fn panics_at_the_call_site() {
+  let (file, line) = panic_location(|| {
+    let _ = create!(Vehicle, broken: true);
+  });
+  assert_eq!(file, file!());
+  assert_eq!(line, line!() - 3);
+
+  let (file, line) = panic_location(|| {
+    let _ = create!(Vehicle, number_wheels: 0);
+  });
+  assert_eq!(file, file!());
+  assert_eq!(line, line!() - 3);
+
+  let (_, line) = panic_location(|| {
+    let _ = build!(Vehicle, broken: true).build();
+  });
+  assert_eq!(line, line!() - 2);
+}
@@ -18,6 +18,15 @@ factori!(User, {
     upcased = true
   }
 
+  mixin downcased {
+    upcased = false
+  }
+
+  mixin shouting_john {
+    name = "John".to_string(),
+    upcased = true,
+  }
+
   builder {
     let name = if upcased { name.to_uppercase() } else { name };
     User { name }
@@ -44,3 +53,77 @@ fn transient_works_with_mixins() {
 
   assert_eq!(user.name, "RICHARD");
 }
+
+#[test]
+fn mixin_sets_transient_and_real_fields() {
+  let user = create!(User, :shouting_john);
+
+  assert_eq!(user.name, "JOHN");
+}
+
+#[test]
+fn later_mixin_overrides_transient() {
+  let user = create!(User, :upcased, :downcased);
+  assert_eq!(user.name, "Richard");
+
+  let user = create!(User, :downcased, :upcased);
+  assert_eq!(user.name, "RICHARD");
+}
+
+#[test]
+fn later_mixin_overrides_part_of_mixin() {
+  let user = create!(User, :shouting_john, :downcased);
+
+  assert_eq!(user.name, "John");
+}
+
+#[test]
+fn override_wins_over_mixin_transient() {
+  let user = create!(User, :shouting_john, upcased: false, name: "Jane".into());
+
+  assert_eq!(user.name, "Jane");
+}
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    electric: bool = false,
+  }
+
+  transient {
+    double_wheels: bool = false,
+  }
+
+  builder {
+    let number_wheels = if double_wheels { number_wheels * 2 } else { number_wheels };
+    Vehicle { number_wheels, electric }
+  }
+
+  mixin double {
+    double_wheels = true,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+});
+
+#[test]
+fn transient_only_and_real_only_mixins() {
+  let vehicle = create!(Vehicle, :double, :electric);
+  assert_eq!(vehicle.number_wheels, 8);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, :electric, :double);
+  assert_eq!(vehicle.number_wheels, 8);
+  assert!(vehicle.electric);
+
+  let vehicle = create!(Vehicle, :double, :electric, number_wheels: 3);
+  assert_eq!(vehicle.number_wheels, 6);
+  assert!(vehicle.electric);
+}
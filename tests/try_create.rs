@@ -0,0 +1,90 @@
+#[macro_use]
+extern crate factori_imp;
+
+use std::convert::TryFrom;
+
+#[derive(Debug)]
+pub struct Vehicle {
+  number_wheels: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TooManyWheels(u8);
+
+impl TryFrom<u8> for Vehicle {
+  type Error = TooManyWheels;
+
+  fn try_from(number_wheels: u8) -> Result<Self, TooManyWheels> {
+    if number_wheels > 18 {
+      return Err(TooManyWheels(number_wheels));
+    }
+    Ok(Vehicle { number_wheels })
+  }
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+  }
+
+  transient {
+    doubled: bool = false,
+  }
+
+  try_builder(TooManyWheels) {
+    let number_wheels = if doubled { number_wheels * 2 } else { number_wheels };
+    Vehicle::try_from(number_wheels)?
+  }
+
+  mixin truck {
+    number_wheels = 18,
+  }
+});
+
+pub struct Passenger {
+  name: &'static str,
+}
+
+factori!(Passenger, {
+  default {
+    name = "Michael",
+  }
+});
+
+#[test]
+fn succeeds() {
+  let vehicle = try_create!(Vehicle, :truck).unwrap();
+  assert_eq!(vehicle.number_wheels, 18);
+}
+
+#[test]
+fn fails() {
+  let error = try_create!(Vehicle, :truck, doubled: true).unwrap_err();
+  assert_eq!(error, TooManyWheels(36));
+}
+
+#[test]
+fn create_builds_when_it_succeeds() {
+  assert_eq!(create!(Vehicle, doubled: true).number_wheels, 8);
+}
+
+#[test]
+#[should_panic(expected = "the builder of `Vehicle` failed: TooManyWheels(20)")]
+fn create_panics_when_it_fails() {
+  create!(Vehicle, number_wheels: 20);
+}
+
+#[test]
+fn infallible_factories() {
+  let passenger = try_create!(Passenger, name: "Hugo").unwrap();
+  assert_eq!(passenger.name, "Hugo");
+}
+
+#[test]
+fn try_converts_into_the_type() {
+  let vehicle = Vehicle::try_from(build!(Vehicle, :truck)).unwrap();
+  assert_eq!(vehicle.number_wheels, 18);
+
+  let error = Vehicle::try_from(build!(Vehicle, :truck, doubled: true)).unwrap_err();
+  assert_eq!(error, TooManyWheels(36));
+}
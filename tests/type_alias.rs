@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate factori_imp;
+
+type Meters = u32;
+type Waypoints<T> = Vec<(T, T)>;
+
+pub struct Route {
+  pub distance: Meters,
+  pub waypoints: Waypoints<Meters>,
+}
+
+factori!(Route, {
+  default {
+    distance: Meters = 5,
+    waypoints: Waypoints<Meters> = vec![(0, 0)],
+  }
+
+  transient {
+    double: bool = false,
+  }
+
+  builder {
+    let distance = if double { distance * 2 } else { distance };
+    Route { distance, waypoints }
+  }
+
+  mixin long {
+    distance = 5000,
+  }
+});
+
+pub struct Vehicle {
+  pub number_wheels: u8,
+}
+
+// A factory can be defined for an alias of the type too.
+type Car = Vehicle;
+
+factori!(Car, {
+  default {
+    number_wheels = 4,
+  }
+});
+
+#[test]
+fn alias_field_types_in_builder() {
+  let route = create!(Route, :long, double: true);
+  assert_eq!(route.distance, 10_000);
+  assert_eq!(route.waypoints, vec![(0, 0)]);
+
+  let distance: Meters = build!(Route).distance;
+  assert_eq!(distance, 5);
+}
+
+#[test]
+fn factory_for_alias() {
+  let car: Vehicle = create!(Car, number_wheels: 3);
+  assert_eq!(car.number_wheels, 3);
+}
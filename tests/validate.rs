@@ -0,0 +1,95 @@
+#[macro_use]
+extern crate factori_imp;
+
+use factori_imp::FactoriError;
+
+#[derive(Debug)]
+pub struct Vehicle {
+  pub number_wheels: u8,
+  pub number_seats: u8,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels = 4,
+    number_seats = 5,
+  }
+
+  validate {
+    |vehicle| {
+      if vehicle.number_wheels == 0 {
+        return Err("a vehicle needs wheels".to_string());
+      }
+      if vehicle.number_seats > vehicle.number_wheels * 2 {
+        return Err(format!("{} seats is too many", vehicle.number_seats));
+      }
+      Ok(())
+    }
+  }
+
+  mixin broken {
+    number_wheels = 0,
+  }
+});
+
+#[derive(Debug)]
+pub struct User {
+  pub name: String,
+}
+
+factori!(User, {
+  default {
+    name: String = "Alice".to_string(),
+  }
+
+  transient {
+    admin: bool = false,
+  }
+
+  validate {
+    |builder| match (builder.admin, builder.name.as_str()) {
+      (true, "Alice") => Ok(()),
+      (true, name) => Err(format!("{} can't be an admin", name)),
+      (false, _) => Ok(()),
+    }
+  }
+
+  builder {
+    let _ = admin;
+    User { name }
+  }
+});
+
+#[test]
+fn passing_validation() {
+  let vehicle = try_create!(Vehicle, number_seats: 8).unwrap();
+  assert_eq!(vehicle.number_seats, 8);
+  assert_eq!(create!(Vehicle).number_wheels, 4);
+}
+
+#[test]
+fn failing_validation() {
+  let error = try_create!(Vehicle, :broken).unwrap_err();
+  assert_eq!(
+    error,
+    FactoriError::new("Vehicle", "a vehicle needs wheels".to_string())
+  );
+  assert_eq!(error.factory(), "Vehicle");
+
+  let error = try_create!(Vehicle, number_seats: 9).unwrap_err();
+  assert_eq!(error.message(), "9 seats is too many");
+}
+
+#[test]
+#[should_panic(expected = "invalid `Vehicle`: a vehicle needs wheels")]
+fn create_panics_on_failing_validation() {
+  let _ = create!(Vehicle, :broken);
+}
+
+#[test]
+fn validation_of_builder_with_transients() {
+  assert!(try_create!(User, admin: true).is_ok());
+
+  let error = try_create!(User, admin: true, name: "Bob".to_string()).unwrap_err();
+  assert_eq!(error.to_string(), "invalid `User`: Bob can't be an admin");
+}
@@ -0,0 +1,86 @@
+#[macro_use]
+extern crate factori_imp;
+
+pub struct Vehicle {
+  number_wheels: u8,
+  electric: bool,
+  color: &'static str,
+}
+
+factori!(Vehicle, {
+  default {
+    number_wheels: u8 = 4,
+    electric: bool = false,
+    color: &'static str = "white",
+  }
+
+  mixin bike {
+    number_wheels = 2,
+  }
+
+  mixin electric {
+    electric = true,
+  }
+
+  mixin weak stock {
+    number_wheels = 6,
+    color = "red",
+  }
+
+  mixin weak painted(color: &'static str) {
+    color = color,
+  }
+
+  mixin weak {
+    electric = false,
+  }
+});
+
+#[test]
+fn sets_unset_fields() {
+  let vehicle = create!(Vehicle, :stock);
+  assert_eq!(vehicle.number_wheels, 6);
+  assert_eq!(vehicle.color, "red");
+}
+
+#[test]
+fn keeps_fields_set_by_mixins_before_it() {
+  let vehicle = create!(Vehicle, :bike, :stock);
+  assert_eq!(vehicle.number_wheels, 2);
+  assert_eq!(vehicle.color, "red");
+}
+
+#[test]
+fn mixins_after_it_override_it() {
+  let vehicle = create!(Vehicle, :stock, :bike);
+  assert_eq!(vehicle.number_wheels, 2);
+}
+
+#[test]
+fn overrides_replace_it() {
+  let vehicle = create!(Vehicle, :bike, :stock, color: "blue", number_wheels: 3);
+  assert_eq!(vehicle.number_wheels, 3);
+  assert_eq!(vehicle.color, "blue");
+}
+
+#[test]
+fn first_weak_mixin_wins() {
+  let vehicle = create!(Vehicle, :painted("green"), :stock);
+  assert_eq!(vehicle.color, "green");
+  assert_eq!(vehicle.number_wheels, 6);
+}
+
+#[test]
+fn conditional_mixins_before_it() {
+  let vehicle = create!(Vehicle, :bike if false, :stock);
+  assert_eq!(vehicle.number_wheels, 6);
+
+  let vehicle = create!(Vehicle, :bike if true, :stock);
+  assert_eq!(vehicle.number_wheels, 2);
+}
+
+#[test]
+fn mixin_named_weak() {
+  let vehicle = create!(Vehicle, :electric, :weak);
+  assert!(!vehicle.electric);
+}